@@ -36,7 +36,8 @@ fn criterion_benchmark(c: &mut Criterion) {
               ..Default::default()
             },
             Default::default(),
-          );
+          )
+          .unwrap();
           rolldown_bundler.scan().await.unwrap();
         })
       });
@@ -69,7 +70,8 @@ fn criterion_benchmark(c: &mut Criterion) {
               ..Default::default()
             },
             Default::default(),
-          );
+          )
+          .unwrap();
           rolldown_bundler.write().await.unwrap();
         })
       });
@@ -88,7 +90,8 @@ fn criterion_benchmark(c: &mut Criterion) {
               ..Default::default()
             },
             OutputOptions { sourcemap: Some(SourceMapType::File), ..Default::default() },
-          );
+          )
+          .unwrap();
           rolldown_bundler.write().await.unwrap();
         })
       });
@@ -27,7 +27,8 @@ pub async fn run_fixture(fixture_path: PathBuf) {
       ..Default::default()
     },
     OutputOptions::default(),
-  );
+  )
+  .unwrap();
 
   if fixture_path.join("dist").is_dir() {
     std::fs::remove_dir_all(fixture_path.join("dist")).unwrap();
@@ -20,7 +20,8 @@ async fn main() {
       ..Default::default()
     },
     OutputOptions { sourcemap: Some(SourceMapType::File), ..OutputOptions::default() },
-  );
+  )
+  .unwrap();
 
   let _outputs = bundler.write().await.unwrap();
   // println!("{outputs:#?}");
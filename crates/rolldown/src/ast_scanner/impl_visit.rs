@@ -4,7 +4,7 @@ use oxc::{
   ast::{ast::IdentifierReference, Visit},
   codegen::{self, Codegen, CodegenOptions, Gen},
 };
-use rolldown_common::ImportKind;
+use rolldown_common::{ImportKind, ImportRecordKey};
 use rolldown_error::BuildError;
 
 use super::{side_effect_detector::SideEffectDetector, AstScanner};
@@ -81,7 +81,12 @@ impl<'ast> Visit<'ast> for AstScanner<'ast> {
   fn visit_import_expression(&mut self, expr: &oxc::ast::ast::ImportExpression<'ast>) {
     if let oxc::ast::ast::Expression::StringLiteral(request) = &expr.source {
       let id = self.add_import_record(&request.value, ImportKind::DynamicImport);
-      self.result.imports.insert(expr.span, id);
+      self.result.imports.insert(ImportRecordKey::new(expr.span, &request.value), id);
+    }
+    // Non-literal specifiers could reference symbols, like `import(path)`
+    self.visit_expression(&expr.source);
+    for arg in &expr.arguments {
+      self.visit_expression(arg);
     }
   }
 
@@ -95,7 +100,7 @@ impl<'ast> Visit<'ast> for AstScanner<'ast> {
         )) = &expr.arguments.first()
         {
           let id = self.add_import_record(&request.value, ImportKind::Require);
-          self.result.imports.insert(expr.span, id);
+          self.result.imports.insert(ImportRecordKey::new(expr.span, &request.value), id);
         }
       }
       _ => {}
@@ -14,9 +14,9 @@ use oxc::{
   span::{Atom, Span},
 };
 use rolldown_common::{
-  representative_name, AstScope, ExportsKind, FilePath, ImportKind, ImportRecordId, LocalExport,
-  ModuleType, NamedImport, NormalModuleId, RawImportRecord, Specifier, StmtInfo, StmtInfos,
-  SymbolRef,
+  representative_name, AstScope, ExportsKind, FilePath, ImportKind, ImportRecordId,
+  ImportRecordKey, LocalExport, ModuleType, NamedImport, NormalModuleId, RawImportRecord,
+  Specifier, StmtInfo, StmtInfos, SymbolRef,
 };
 use rolldown_error::BuildError;
 use rolldown_oxc_utils::{BindingIdentifierExt, BindingPatternExt};
@@ -35,7 +35,7 @@ pub struct ScanResult {
   pub import_records: IndexVec<ImportRecordId, RawImportRecord>,
   pub star_exports: Vec<ImportRecordId>,
   pub default_export_ref: Option<SymbolRef>,
-  pub imports: FxHashMap<Span, ImportRecordId>,
+  pub imports: FxHashMap<ImportRecordKey, ImportRecordId>,
   pub exports_kind: ExportsKind,
   pub warnings: Vec<BuildError>,
 }
@@ -245,7 +245,7 @@ impl<'ast> AstScanner<'ast> {
       // export * from '...'
       self.result.star_exports.push(id);
     }
-    self.result.imports.insert(decl.span, id);
+    self.result.imports.insert(ImportRecordKey::new(decl.span, &decl.source.value), id);
   }
 
   fn scan_export_named_decl(&mut self, decl: &ExportNamedDeclaration) {
@@ -253,7 +253,7 @@ impl<'ast> AstScanner<'ast> {
       let record_id = self.add_import_record(&source.value, ImportKind::Import);
       decl.specifiers.iter().for_each(|spec| {
         self.add_re_export(spec.exported.name(), spec.local.name(), record_id);
-        self.result.imports.insert(decl.span, record_id);
+        self.result.imports.insert(ImportRecordKey::new(decl.span, &source.value), record_id);
       });
     } else {
       decl.specifiers.iter().for_each(|spec| {
@@ -314,7 +314,7 @@ impl<'ast> AstScanner<'ast> {
 
   fn scan_import_decl(&mut self, decl: &ImportDeclaration) {
     let id = self.add_import_record(&decl.source.value, ImportKind::Import);
-    self.result.imports.insert(decl.span, id);
+    self.result.imports.insert(ImportRecordKey::new(decl.span, &decl.source.value), id);
     let Some(specifiers) = &decl.specifiers else { return };
     specifiers.iter().for_each(|spec| match spec {
       oxc::ast::ast::ImportDeclarationSpecifier::ImportSpecifier(spec) => {
@@ -1,7 +1,11 @@
 use std::sync::Arc;
 
+use rolldown_common::{NormalizedOutputOptions, Output, SharedNormalizedInputOptions};
 use rolldown_fs::OsFileSystem;
-use rolldown_plugin::{BoxPlugin, HookBuildEndArgs, SharedPluginDriver};
+use rolldown_plugin::{
+  BoxPlugin, HookBuildEndArgs, HookRenderErrorArgs, HookRenderStartArgs, HookWatchChangeArgs,
+  SharedPluginDriver, WatcherChangeKind,
+};
 
 use sugar_path::AsPath;
 
@@ -12,25 +16,26 @@ use super::stages::{
 use crate::{
   bundler_builder::BundlerBuilder,
   error::{BatchedErrors, BatchedResult},
-  options::{
-    normalized_input_options::SharedNormalizedInputOptions,
-    normalized_output_options::NormalizedOutputOptions,
-  },
   stages::{bundle_stage::BundleStage, scan_stage::ScanStage},
   types::{bundler_fs::BundlerFileSystem, rolldown_output::RolldownOutput},
   InputOptions, OutputOptions, SharedResolver,
 };
 
+/// Bundles the inputs via `write` or `generate`, which could be called multiple times, like in watch mode.
+///
+/// Call `close` when the bundler is no longer needed, so plugins can clean up in the `close_bundle` hook. Dropping the
+/// bundler doesn't call the hook, since hooks are async and `Drop` can't wait for them.
 pub struct Bundler<T: BundlerFileSystem> {
   pub(crate) input_options: SharedNormalizedInputOptions,
   pub(crate) output_options: NormalizedOutputOptions,
   pub(crate) plugin_driver: SharedPluginDriver,
   pub(crate) fs: T,
   pub(crate) resolver: SharedResolver<T>,
+  pub(crate) closed: bool,
 }
 
 impl Bundler<OsFileSystem> {
-  pub fn new(input_options: InputOptions, output_options: OutputOptions) -> Self {
+  pub fn new(input_options: InputOptions, output_options: OutputOptions) -> BatchedResult<Self> {
     BundlerBuilder::default()
       .with_input_options(input_options)
       .with_output_options(output_options)
@@ -41,7 +46,7 @@ impl Bundler<OsFileSystem> {
     input_options: InputOptions,
     output_options: OutputOptions,
     plugins: Vec<BoxPlugin>,
-  ) -> Self {
+  ) -> BatchedResult<Self> {
     BundlerBuilder::default()
       .with_input_options(input_options)
       .with_output_options(output_options)
@@ -53,7 +58,7 @@ impl Bundler<OsFileSystem> {
 impl<T: BundlerFileSystem> Bundler<T> {
   pub async fn write(&mut self) -> BatchedResult<RolldownOutput> {
     let dir =
-      self.input_options.cwd.as_path().join(&self.output_options.dir).to_string_lossy().to_string();
+      self.output_options.out_dir(self.input_options.cwd.as_path()).to_string_lossy().to_string();
 
     let output = self.bundle_up(true).await?;
 
@@ -73,7 +78,7 @@ impl<T: BundlerFileSystem> Bundler<T> {
           self.fs.create_dir_all(p).unwrap();
         }
       };
-      self.fs.write(dest.as_path(), chunk.content().as_bytes()).unwrap_or_else(|_| {
+      self.fs.write(dest.as_path(), chunk.content_as_bytes()).unwrap_or_else(|_| {
         panic!("Failed to write file in {:?}", dir.as_path().join(chunk.file_name()))
       });
     }
@@ -85,6 +90,22 @@ impl<T: BundlerFileSystem> Bundler<T> {
     self.bundle_up(false).await
   }
 
+  /// Calls the `close_bundle` hook. Only the first call takes effect. It's not called on drop.
+  pub async fn close(&mut self) -> BatchedResult<()> {
+    if self.closed {
+      return Ok(());
+    }
+    self.closed = true;
+    self.plugin_driver.close_bundle().await?;
+    Ok(())
+  }
+
+  /// Notifies plugins that `id` watched by the bundler changed.
+  pub async fn watch_change(&self, id: &str, event: WatcherChangeKind) -> BatchedResult<()> {
+    self.plugin_driver.watch_change(&HookWatchChangeArgs { id, event }).await?;
+    Ok(())
+  }
+
   pub async fn scan(&mut self) -> BatchedResult<()> {
     self.plugin_driver.build_start().await?;
 
@@ -131,6 +152,7 @@ impl<T: BundlerFileSystem> Bundler<T> {
 
   #[tracing::instrument(skip_all)]
   async fn try_build(&mut self) -> BatchedResult<LinkStageOutput> {
+    self.plugin_driver.clear_build_state();
     self.plugin_driver.build_start().await?;
 
     let scan_ret = self.scan_inner().await;
@@ -139,27 +161,64 @@ impl<T: BundlerFileSystem> Bundler<T> {
 
     let build_info = scan_ret?;
 
-    let link_stage = LinkStage::new(build_info, &self.input_options);
+    let link_stage = LinkStage::new(build_info, &self.input_options, &self.output_options);
     Ok(link_stage.link())
   }
 
-  #[tracing::instrument(skip_all)]
-  async fn bundle_up(&mut self, is_write: bool) -> BatchedResult<RolldownOutput> {
-    tracing::trace!("InputOptions {:#?}", self.input_options);
-    tracing::trace!("OutputOptions: {:#?}", self.output_options);
-    let mut link_stage_output = self.try_build().await?;
+  async fn render(
+    &self,
+    link_stage_output: &mut LinkStageOutput,
+    is_write: bool,
+  ) -> BatchedResult<Vec<Output>> {
+    self
+      .plugin_driver
+      .render_start(&HookRenderStartArgs {
+        output_options: &self.output_options,
+        input_options: &self.input_options,
+      })
+      .await?;
 
     let mut bundle_stage = BundleStage::new(
-      &mut link_stage_output,
+      link_stage_output,
       &self.input_options,
       &self.output_options,
       &self.plugin_driver,
     );
 
-    let assets = bundle_stage.bundle().await?;
+    let mut assets = bundle_stage.bundle().await?;
 
     self.plugin_driver.generate_bundle(&assets, is_write).await?;
 
-    Ok(RolldownOutput { warnings: std::mem::take(&mut link_stage_output.warnings), assets })
+    // Assets emitted in `generate_bundle` are part of the bundle too
+    let emitted_assets = bundle_stage.generate_emitted_assets(&assets).await?;
+    assets.extend(emitted_assets);
+
+    Ok(assets)
+  }
+
+  #[tracing::instrument(skip_all)]
+  async fn bundle_up(&mut self, is_write: bool) -> BatchedResult<RolldownOutput> {
+    tracing::trace!("InputOptions {:#?}", self.input_options);
+    tracing::trace!("OutputOptions: {:#?}", self.output_options);
+    let mut link_stage_output = self.try_build().await?;
+
+    let assets = match self.render(&mut link_stage_output, is_write).await {
+      Ok(assets) => assets,
+      Err(mut errors) => {
+        let error = errors.get().expect("should have a error").to_string();
+        // The render error is what users need to see. A failing `render_error` hook is reported along with it.
+        if let Err(hook_error) =
+          self.plugin_driver.render_error(&HookRenderErrorArgs { error }).await
+        {
+          errors.push(hook_error);
+        }
+        return Err(errors);
+      }
+    };
+
+    let mut warnings = std::mem::take(&mut link_stage_output.warnings);
+    warnings.extend(self.plugin_driver.take_warnings());
+
+    Ok(RolldownOutput { warnings, assets, watch_files: self.plugin_driver.watch_files() })
   }
 }
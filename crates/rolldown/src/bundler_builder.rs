@@ -4,9 +4,13 @@ use rolldown_plugin::{BoxPlugin, PluginDriver};
 use rolldown_resolver::Resolver;
 
 use crate::{
+  error::BatchedResult,
   types::bundler_fs::BundlerFileSystem,
-  utils::normalize_options::{normalize_options, NormalizeOptionsReturn},
-  Bundler, InputOptions, OutputOptions,
+  utils::{
+    context_resolver::BundlerContextResolver,
+    normalize_options::{normalize_options, NormalizeOptionsReturn},
+  },
+  Bundler, InputOptions, OutputOptions, SharedResolver,
 };
 
 pub struct BundlerBuilder<Fs: BundlerFileSystem> {
@@ -17,19 +21,32 @@ pub struct BundlerBuilder<Fs: BundlerFileSystem> {
 }
 
 impl<Fs: BundlerFileSystem> BundlerBuilder<Fs> {
-  pub fn build(self) -> Bundler<Fs> {
+  pub fn build(self) -> BatchedResult<Bundler<Fs>> {
     rolldown_tracing::try_init_tracing();
 
+    let mut input_options = self.input_options;
+    let mut output_options = self.output_options;
+    PluginDriver::call_options_hooks(&self.plugins, &mut input_options, &mut output_options)?;
+
     let NormalizeOptionsReturn { input_options, output_options, resolve_options } =
-      normalize_options(self.input_options, self.output_options);
+      normalize_options(input_options, output_options)?;
+
+    let input_options = Arc::new(input_options);
+    let resolver: SharedResolver<Fs> =
+      Resolver::new(resolve_options, input_options.cwd.clone(), self.fs.share()).into();
+    let context_resolver = BundlerContextResolver {
+      input_options: Arc::clone(&input_options),
+      resolver: Arc::clone(&resolver),
+    };
 
-    Bundler {
-      resolver: Resolver::new(resolve_options, input_options.cwd.clone(), self.fs.share()).into(),
-      plugin_driver: PluginDriver::new_shared(self.plugins),
-      input_options: Arc::new(input_options),
+    Ok(Bundler {
+      resolver,
+      plugin_driver: PluginDriver::new_shared(self.plugins, Arc::new(context_resolver)),
+      input_options,
       output_options,
       fs: self.fs,
-    }
+      closed: false,
+    })
   }
 
   #[must_use]
@@ -1,12 +1,19 @@
 use std::borrow::Cow;
 
+use index_vec::IndexVec;
+use rolldown_common::{representative_name, ChunkId, NormalizedOutputOptions};
 use rolldown_rstr::ToRstr;
 
 use super::Chunk;
-use crate::{stages::link_stage::LinkStageOutput, utils::renamer::Renamer};
+use crate::{stages::link_stage::LinkStageOutput, utils::renamer::Renamer, OutputFormat};
 
 impl Chunk {
-  pub fn de_conflict(&mut self, graph: &LinkStageOutput) {
+  pub fn de_conflict(
+    &mut self,
+    graph: &LinkStageOutput,
+    output_options: &NormalizedOutputOptions,
+    chunk_file_names: &IndexVec<ChunkId, String>,
+  ) {
     let mut renamer = Renamer::new(&graph.symbols, graph.module_table.normal_modules.len());
 
     // TODO: reserve names for keywords in both non-strict and strict mode
@@ -22,9 +29,67 @@ impl Chunk {
         renamer.reserve(Cow::Owned(name.to_rstr()));
       });
 
-    self.imports_from_other_chunks.iter().flat_map(|(_, items)| items.iter()).for_each(|item| {
-      renamer.add_top_level_symbol(item.import_ref);
-    });
+    match output_options.format {
+      OutputFormat::Esm => {}
+      OutputFormat::Cjs => {
+        // Variables provided by the CommonJS module scope
+        ["exports", "module", "require"].into_iter().for_each(|name| {
+          renamer.reserve(Cow::Owned(name.into()));
+        });
+      }
+      OutputFormat::Iife | OutputFormat::Umd => {
+        // `exports` is the parameter of the wrapper function
+        renamer.reserve(Cow::Owned("exports".into()));
+      }
+      OutputFormat::System => {
+        // Parameters of the function passed to `System.register`
+        ["exports", "module"].into_iter().for_each(|name| {
+          renamer.reserve(Cow::Owned(name.into()));
+        });
+      }
+    }
+
+    if matches!(output_options.format, OutputFormat::Cjs | OutputFormat::Iife | OutputFormat::Umd) {
+      let mut external_modules =
+        self.imports_from_external_modules.keys().copied().collect::<Vec<_>>();
+      external_modules
+        .sort_unstable_by_key(|id| graph.module_table.external_modules[*id].exec_order);
+      self.external_module_bindings = external_modules
+        .into_iter()
+        .map(|id| {
+          let module = &graph.module_table.external_modules[id];
+          let name = renamer.create_conflictless_top_level_name(
+            &module.resource_id.expect_file().representative_name(),
+          );
+          (id, name)
+        })
+        .collect();
+    }
+
+    if matches!(output_options.format, OutputFormat::Cjs) {
+      // Imported symbols are accessed through the binding of the `require` call, such as `require_shared.foo`
+      let mut chunk_ids = self
+        .imports_from_other_chunks
+        .iter()
+        .filter(|(_, items)| !items.is_empty())
+        .map(|(chunk_id, _)| *chunk_id)
+        .collect::<Vec<_>>();
+      chunk_ids.sort_unstable();
+      self.require_binding_names_for_other_chunks = chunk_ids
+        .into_iter()
+        .map(|chunk_id| {
+          let name = renamer.create_conflictless_top_level_name(&format!(
+            "require_{}",
+            representative_name(&chunk_file_names[chunk_id])
+          ));
+          (chunk_id, name)
+        })
+        .collect();
+    } else {
+      self.imports_from_other_chunks.iter().flat_map(|(_, items)| items.iter()).for_each(|item| {
+        renamer.add_top_level_symbol(item.import_ref);
+      });
+    }
 
     self
       .modules
@@ -44,6 +109,21 @@ impl Chunk {
           });
       });
 
+    if matches!(output_options.format, OutputFormat::System) {
+      // Statements of modules are moved into these functions, while their declarations are hoisted out of them
+      self.system_executor_names = self
+        .modules
+        .iter()
+        .map(|id| {
+          let module = &graph.module_table.normal_modules[*id];
+          (
+            *id,
+            renamer.create_conflictless_top_level_name(&format!("execute_{}", module.repr_name)),
+          )
+        })
+        .collect();
+    }
+
     // rename non-top-level names
     renamer.rename_non_top_level_symbol(&self.modules, &graph.module_table.normal_modules);
 
@@ -3,27 +3,29 @@ mod de_conflict;
 pub mod render_chunk;
 mod render_chunk_exports;
 mod render_chunk_imports;
+mod render_chunk_wrapper;
 
 use index_vec::IndexVec;
-use rolldown_common::ChunkId;
+use rolldown_common::{ChunkId, NormalizedInputOptions, NormalizedOutputOptions};
 
 pub type ChunksVec = IndexVec<ChunkId, Chunk>;
 
 use rolldown_common::{
-  ChunkKind, ExternalModuleId, NamedImport, NormalModuleId, RenderedModule, Specifier, SymbolRef,
+  ChunkKind, ExternalModule, ExternalModuleId, NamedImport, NormalModuleId, Specifier, SymbolRef,
 };
 use rolldown_error::BuildError;
 use rolldown_rstr::Rstr;
 use rolldown_sourcemap::{collapse_sourcemaps, concat_sourcemaps, SourceMap};
 use rolldown_utils::BitSet;
 use rustc_hash::FxHashMap;
+use string_wizard::MagicString;
 
-use crate::options::normalized_input_options::NormalizedInputOptions;
-use crate::options::normalized_output_options::NormalizedOutputOptions;
+use self::render_chunk_wrapper::SystemChunkWrapper;
+use crate::utils::render_chunk_addons::ChunkAddons;
 use crate::utils::render_normal_module::render_normal_module;
 use crate::{
   error::BatchedResult,
-  FileNameTemplate,
+  FileNameTemplate, OutputFormat,
   {
     chunk_graph::ChunkGraph, stages::link_stage::LinkStageOutput,
     types::module_render_context::ModuleRenderContext,
@@ -48,12 +50,23 @@ pub struct Chunk {
   pub imports_from_external_modules: FxHashMap<ExternalModuleId, Vec<NamedImport>>,
   // meaningless if the chunk is an entrypoint
   pub exports_to_other_chunks: FxHashMap<SymbolRef, Rstr>,
+  /// Local bindings of external modules for non-ESM formats, such as the parameters of the IIFE/UMD wrapper or
+  /// the results of `require` calls in CJS output.
+  pub external_module_bindings: FxHashMap<ExternalModuleId, Rstr>,
+  /// Local bindings of `require` calls of other chunks in CJS output, such as `require_shared` in
+  /// `var require_shared = require('./shared.js')`.
+  pub require_binding_names_for_other_chunks: FxHashMap<ChunkId, Rstr>,
+  /// Paths of external modules rewritten by `output.paths` or `makeAbsoluteExternalsRelative`
+  pub external_import_paths: FxHashMap<ExternalModuleId, String>,
+  /// Names of the functions that execute the modules in SystemJS output, such as `execute_main` in
+  /// `function execute_main() { ... }`. They are called by the `execute` function of the chunk.
+  pub system_executor_names: FxHashMap<NormalModuleId, Rstr>,
 }
 
 pub struct ChunkRenderReturn {
   pub code: String,
   pub map: Option<SourceMap>,
-  pub rendered_modules: FxHashMap<String, RenderedModule>,
+  pub warnings: Vec<BuildError>,
 }
 
 impl Chunk {
@@ -66,31 +79,109 @@ impl Chunk {
     Self { modules, name, bits, kind, ..Self::default() }
   }
 
+  /// The path to import the external module from this chunk, which is its id unless it's rewritten.
+  pub fn external_import_path<'a>(&'a self, module: &'a ExternalModule) -> &'a str {
+    self
+      .external_import_paths
+      .get(&module.id)
+      .map_or_else(|| module.resource_id.expect_file().as_str(), String::as_str)
+  }
+
+  /// The relative path to import `importee` from this chunk, like `./foo.js` or `../foo.js`. File names of chunks
+  /// could contain directories, such as the ones of preserved modules.
+  pub fn import_path_for(&self, importee: &Chunk) -> String {
+    let importer_file_name =
+      self.file_name.as_ref().expect("At this point, file name should already be generated");
+    let importee_file_name =
+      importee.file_name.as_ref().expect("At this point, file name should already be generated");
+    let importer_dirs = importer_file_name.split('/').collect::<Vec<_>>();
+    let importer_dirs = &importer_dirs[..importer_dirs.len() - 1];
+    let importee_segments = importee_file_name.split('/').collect::<Vec<_>>();
+    let common_len = importer_dirs
+      .iter()
+      .zip(&importee_segments[..importee_segments.len() - 1])
+      .take_while(|(a, b)| a == b)
+      .count();
+    let mut segments = vec![".."; importer_dirs.len() - common_len];
+    segments.extend(&importee_segments[common_len..]);
+    if segments[0] == ".." {
+      segments.join("/")
+    } else {
+      format!("./{}", segments.join("/"))
+    }
+  }
+
   pub fn file_name_template<'a>(
     &mut self,
     output_options: &'a NormalizedOutputOptions,
   ) -> &'a FileNameTemplate {
-    if matches!(self.kind, ChunkKind::EntryPoint { is_user_defined, .. } if is_user_defined) {
+    // Preserved modules are named after their paths just like entries
+    if output_options.preserve_modules
+      || matches!(self.kind, ChunkKind::EntryPoint { is_user_defined, .. } if is_user_defined)
+    {
       &output_options.entry_file_names
     } else {
       &output_options.chunk_file_names
     }
   }
 
-  #[allow(clippy::unnecessary_wraps, clippy::cast_possible_truncation)]
+  #[allow(clippy::unnecessary_wraps, clippy::cast_possible_truncation, clippy::too_many_lines)]
   pub fn render(
     &self,
     input_options: &NormalizedInputOptions,
     graph: &LinkStageOutput,
     chunk_graph: &ChunkGraph,
     output_options: &NormalizedOutputOptions,
+    addons: &ChunkAddons,
   ) -> BatchedResult<ChunkRenderReturn> {
     use rayon::prelude::*;
-    let mut rendered_modules = FxHashMap::default();
     let mut content_and_sourcemaps = vec![];
+    let mut warnings = vec![];
+
+    let export_mode = self.resolve_export_mode(graph, output_options, &mut warnings)?;
+
+    // SystemJS output executes the modules in a function after they are declared
+    let mut system_execute = None;
+    let wrapper = match output_options.format {
+      OutputFormat::Esm => None,
+      OutputFormat::Cjs => Some(self.render_cjs_wrapper(graph, export_mode)),
+      OutputFormat::Iife => {
+        Some(self.render_iife_wrapper(graph, output_options, export_mode, &mut warnings)?)
+      }
+      OutputFormat::Umd => {
+        Some(self.render_umd_wrapper(graph, output_options, export_mode, &mut warnings)?)
+      }
+      OutputFormat::System => {
+        let SystemChunkWrapper { wrapper, execute_intro, executor_calls } =
+          self.render_system_wrapper(graph, chunk_graph);
+        system_execute = Some((execute_intro, executor_calls));
+        Some(wrapper)
+      }
+    };
+
+    if let Some(banner) = &addons.banner {
+      content_and_sourcemaps.push((banner.clone(), None));
+    }
+
+    if let Some(wrapper) = &wrapper {
+      content_and_sourcemaps.push((wrapper.intro.clone(), None));
+    }
 
-    content_and_sourcemaps
-      .push((self.render_imports_for_esm(graph, chunk_graph).to_string(), None));
+    if let (Some(intro), None) = (&addons.intro, &system_execute) {
+      content_and_sourcemaps.push((intro.clone(), None));
+    }
+
+    let imports = match output_options.format {
+      OutputFormat::Esm => self.render_imports_for_esm(graph, chunk_graph),
+      OutputFormat::Cjs => self.render_imports_for_cjs(graph, chunk_graph),
+      OutputFormat::Iife | OutputFormat::Umd => self.render_imports_for_wrapper(graph),
+      // Imports are bound by the setters of the wrapper
+      OutputFormat::System => MagicString::new(""),
+    }
+    .to_string();
+    if wrapper.is_none() || !imports.is_empty() {
+      content_and_sourcemaps.push((imports, None));
+    }
 
     self
       .modules
@@ -120,8 +211,6 @@ impl Chunk {
           },
         );
         Some((
-          m.resource_id.expect_file().to_string(),
-          RenderedModule { code: None },
           rendered_output.as_ref().map(|v| v.code.to_string()),
           if output_options.sourcemap.is_hidden() {
             None
@@ -136,36 +225,57 @@ impl Chunk {
       })
       .collect::<Vec<_>>()
       .into_iter()
-      .try_for_each(
-        |(module_path, rendered_module, rendered_content, map)| -> Result<(), BuildError> {
-          if let Some(rendered_content) = rendered_content {
-            content_and_sourcemaps.push((
-              rendered_content,
-              match map {
-                None => None,
-                Some(v) => v?,
-              },
-            ));
-          }
-          rendered_modules.insert(module_path, rendered_module);
-          Ok(())
-        },
-      )?;
-
-    if let Some(exports) = self.render_exports(graph, output_options) {
+      .try_for_each(|(rendered_content, map)| -> Result<(), BuildError> {
+        if let Some(rendered_content) = rendered_content {
+          content_and_sourcemaps.push((
+            rendered_content,
+            match map {
+              None => None,
+              Some(v) => v?,
+            },
+          ));
+        }
+        Ok(())
+      })?;
+
+    if let Some((execute_intro, executor_calls)) = system_execute {
+      content_and_sourcemaps.push((execute_intro, None));
+      if let Some(intro) = &addons.intro {
+        content_and_sourcemaps.push((intro.clone(), None));
+      }
+      if !executor_calls.is_empty() {
+        content_and_sourcemaps.push((executor_calls, None));
+      }
+    }
+
+    if let Some(exports) = self.render_exports(graph, chunk_graph, output_options, export_mode) {
       content_and_sourcemaps.push((exports.to_string(), None));
     }
 
+    if let Some(outro) = &addons.outro {
+      content_and_sourcemaps.push((outro.clone(), None));
+    }
+
+    if let Some(wrapper) = wrapper {
+      if !wrapper.outro.is_empty() {
+        content_and_sourcemaps.push((wrapper.outro, None));
+      }
+    }
+
+    if let Some(footer) = &addons.footer {
+      content_and_sourcemaps.push((footer.clone(), None));
+    }
+
     if output_options.sourcemap.is_hidden() {
       return Ok(ChunkRenderReturn {
         code: content_and_sourcemaps.into_iter().map(|(c, _)| c).collect::<Vec<_>>().join("\n"),
         map: None,
-        rendered_modules,
+        warnings,
       });
     }
 
     let (content, map) = concat_sourcemaps(&content_and_sourcemaps)?;
 
-    Ok(ChunkRenderReturn { code: content, map: Some(map), rendered_modules })
+    Ok(ChunkRenderReturn { code: content, map: Some(map), warnings })
   }
 }
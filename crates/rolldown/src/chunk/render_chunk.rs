@@ -1,9 +1,7 @@
 use rolldown_common::{ChunkKind, RenderedChunk, RenderedModule};
 use rustc_hash::FxHashMap;
 
-use crate::{
-  options::normalized_output_options::NormalizedOutputOptions, stages::link_stage::LinkStageOutput,
-};
+use crate::stages::link_stage::LinkStageOutput;
 
 use super::Chunk;
 
@@ -18,11 +16,7 @@ pub struct PreRenderedChunk {
 }
 
 impl Chunk {
-  pub fn get_pre_rendered_chunk_info(
-    &self,
-    graph: &LinkStageOutput,
-    output_options: &NormalizedOutputOptions,
-  ) -> PreRenderedChunk {
+  pub fn get_pre_rendered_chunk_info(&self, graph: &LinkStageOutput) -> PreRenderedChunk {
     PreRenderedChunk {
       is_entry: matches!(&self.kind, ChunkKind::EntryPoint { is_user_defined, .. } if *is_user_defined),
       is_dynamic_entry: matches!(&self.kind, ChunkKind::EntryPoint { is_user_defined, .. } if !*is_user_defined),
@@ -37,17 +31,12 @@ impl Chunk {
         .iter()
         .map(|id| graph.module_table.normal_modules[*id].resource_id.expect_file().to_string())
         .collect(),
-      exports: self.get_export_names(graph, output_options),
+      exports: self.get_export_names(graph),
     }
   }
 
-  pub fn get_rendered_chunk_info(
-    &self,
-    graph: &LinkStageOutput,
-    output_options: &NormalizedOutputOptions,
-    render_modules: FxHashMap<String, RenderedModule>,
-  ) -> RenderedChunk {
-    let pre_rendered_chunk = self.get_pre_rendered_chunk_info(graph, output_options);
+  pub fn get_rendered_chunk_info(&self, graph: &LinkStageOutput) -> RenderedChunk {
+    let pre_rendered_chunk = self.get_pre_rendered_chunk_info(graph);
     RenderedChunk {
       is_entry: pre_rendered_chunk.is_entry,
       is_dynamic_entry: pre_rendered_chunk.is_dynamic_entry,
@@ -55,7 +44,14 @@ impl Chunk {
       module_ids: pre_rendered_chunk.module_ids,
       exports: pre_rendered_chunk.exports,
       file_name: self.file_name.clone().expect("should have file name"),
-      modules: render_modules,
+      modules: self
+        .modules
+        .iter()
+        .map(|id| {
+          let module = &graph.module_table.normal_modules[*id];
+          (module.resource_id.expect_file().to_string(), RenderedModule { code: None })
+        })
+        .collect::<FxHashMap<_, _>>(),
     }
   }
 }
@@ -1,37 +1,102 @@
-use rolldown_common::{ChunkKind, SymbolRef, WrapKind};
+use rolldown_common::{ChunkKind, NormalizedOutputOptions, SymbolRef, WrapKind};
+use rolldown_error::BuildError;
 use rolldown_rstr::Rstr;
+use rustc_hash::FxHashMap;
 use string_wizard::MagicString;
 
 use crate::{
-  options::normalized_output_options::NormalizedOutputOptions, stages::link_stage::LinkStageOutput,
-  OutputFormat,
+  chunk_graph::ChunkGraph, stages::link_stage::LinkStageOutput, OutputExports, OutputFormat,
 };
 
 use super::Chunk;
 
 impl Chunk {
+  /// Resolve `output.exports` to `default`, `named` or `none`. Only user-defined entry chunks of CJS, IIFE and UMD
+  /// output could export a single value, other chunks always use named exports to be importable by other chunks.
+  pub fn resolve_export_mode(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &NormalizedOutputOptions,
+    warnings: &mut Vec<BuildError>,
+  ) -> Result<OutputExports, BuildError> {
+    let ChunkKind::EntryPoint { module: entry_module_id, is_user_defined: true, .. } = &self.kind
+    else {
+      return Ok(OutputExports::Named);
+    };
+    if matches!(output_options.format, OutputFormat::Esm | OutputFormat::System) {
+      return Ok(OutputExports::Named);
+    }
+    let entry_module =
+      graph.module_table.normal_modules[*entry_module_id].resource_id.expect_file();
+    let export_names = self.get_export_names(graph);
+    let is_default_only = export_names.len() == 1 && export_names[0] == "default";
+
+    match output_options.exports {
+      OutputExports::Auto => {
+        if export_names.is_empty() {
+          Ok(OutputExports::None)
+        } else if is_default_only {
+          Ok(OutputExports::Default)
+        } else {
+          if export_names.iter().any(|name| name == "default") {
+            warnings.push(
+              BuildError::mixed_exports(entry_module.as_str(), output_options.name.clone())
+                .with_severity_warning(),
+            );
+          }
+          Ok(OutputExports::Named)
+        }
+      }
+      OutputExports::Default if !is_default_only => Err(BuildError::invalid_export_option(
+        OutputExports::Default.to_string(),
+        entry_module.as_str(),
+        export_names,
+      )),
+      OutputExports::None if !export_names.is_empty() => Err(BuildError::invalid_export_option(
+        OutputExports::None.to_string(),
+        entry_module.as_str(),
+        export_names,
+      )),
+      export_mode => Ok(export_mode),
+    }
+  }
+
+  #[allow(clippy::too_many_lines)]
   pub fn render_exports(
     &self,
     graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
     output_options: &NormalizedOutputOptions,
+    export_mode: OutputExports,
   ) -> Option<MagicString<'static>> {
     if let ChunkKind::EntryPoint { module: entry_module_id, .. } = &self.kind {
       let linking_info = &graph.metas[*entry_module_id];
       if matches!(linking_info.wrap_kind, WrapKind::Cjs) {
+        let wrap_ref_name =
+          &self.canonical_names.get(&linking_info.wrapper_ref.unwrap()).unwrap_or_else(|| {
+            panic!(
+              "Cannot find canonical name for wrap ref {:?} of {:?}",
+              linking_info.wrapper_ref.unwrap(),
+              graph.module_table.normal_modules[*entry_module_id].resource_id
+            )
+          });
         match output_options.format {
           OutputFormat::Esm => {
-            let wrap_ref_name =
-              &self.canonical_names.get(&linking_info.wrapper_ref.unwrap()).unwrap_or_else(|| {
-                panic!(
-                  "Cannot find canonical name for wrap ref {:?} of {:?}",
-                  linking_info.wrapper_ref.unwrap(),
-                  graph.module_table.normal_modules[*entry_module_id].resource_id
-                )
-              });
             return Some(MagicString::new(format!("export default {wrap_ref_name}();\n")));
           }
+          OutputFormat::Cjs | OutputFormat::Iife | OutputFormat::Umd
+            if export_mode == OutputExports::Named =>
+          {
+            return Some(MagicString::new(format!("exports.default = {wrap_ref_name}();")));
+          }
+          OutputFormat::Iife | OutputFormat::Umd => {
+            return Some(MagicString::new(format!("return {wrap_ref_name}();")));
+          }
           OutputFormat::Cjs => {
-            unreachable!("entry CJS should not be wrapped in `OutputFormat::Cjs`")
+            return Some(MagicString::new(format!("module.exports = {wrap_ref_name}();")));
+          }
+          OutputFormat::System => {
+            return Some(MagicString::new(format!("exports(\"default\", {wrap_ref_name}());")));
           }
         }
       }
@@ -42,37 +107,122 @@ impl Chunk {
     if export_items.is_empty() {
       return None;
     }
-    let mut s = MagicString::new("");
-    let rendered_items = export_items
-      .into_iter()
-      .map(|(exported_name, export_ref)| {
-        let canonical_ref = graph.symbols.par_canonical_ref_for(export_ref);
-        let symbol = graph.symbols.get(canonical_ref);
-        let canonical_name = &self.canonical_names[&canonical_ref];
-        if let Some(ns_alias) = &symbol.namespace_alias {
-          let canonical_ns_name = &self.canonical_names[&ns_alias.namespace_ref];
-          let property_name = &ns_alias.property_name;
-          s.append(format!("var {canonical_name} = {canonical_ns_name}.{property_name};\n"));
+
+    if export_mode == OutputExports::Default {
+      let (_, export_ref) = &export_items[0];
+      let value = self.render_export_value(graph, chunk_graph, *export_ref);
+      return match output_options.format {
+        OutputFormat::Cjs => Some(MagicString::new(format!("module.exports = {value};"))),
+        OutputFormat::Iife | OutputFormat::Umd => {
+          Some(MagicString::new(format!("return {value};")))
         }
-        if canonical_name == &exported_name {
-          format!("{canonical_name}")
-        } else {
-          format!("{canonical_name} as {exported_name}")
+        OutputFormat::Esm | OutputFormat::System => {
+          unreachable!("`default` export mode is only used by CJS, IIFE and UMD output")
+        }
+      };
+    }
+    let mut s = MagicString::new("");
+    match output_options.format {
+      OutputFormat::Esm => {
+        let rendered_items = export_items
+          .into_iter()
+          .map(|(exported_name, export_ref)| {
+            let canonical_ref = graph.symbols.par_canonical_ref_for(export_ref);
+            let symbol = graph.symbols.get(canonical_ref);
+            let canonical_name = &self.canonical_names[&canonical_ref];
+            if let Some(ns_alias) = &symbol.namespace_alias {
+              let canonical_ns_name = &self.canonical_names[&ns_alias.namespace_ref];
+              let property_name = &ns_alias.property_name;
+              s.append(format!("var {canonical_name} = {canonical_ns_name}.{property_name};\n"));
+            }
+            if canonical_name == &exported_name {
+              format!("{canonical_name}")
+            } else {
+              format!("{canonical_name} as {exported_name}")
+            }
+          })
+          .collect::<Vec<_>>();
+        s.append(format!("export {{ {} }};", rendered_items.join(", "),));
+      }
+      OutputFormat::Cjs | OutputFormat::Iife | OutputFormat::Umd => {
+        // Use getters to keep bindings live
+        let rendered_items = export_items
+          .into_iter()
+          .map(|(exported_name, export_ref)| {
+            let value = self.render_export_value(graph, chunk_graph, export_ref);
+            let getter = format!("function () {{ return {value}; }}");
+            format!(
+              "Object.defineProperty(exports, \"{exported_name}\", {{ enumerable: true, get: {getter} }});"
+            )
+          })
+          .collect::<Vec<_>>();
+        s.append(rendered_items.join("\n"));
+      }
+      OutputFormat::System => {
+        // Importers are notified through their setters
+        let hoisted_export_items = self.get_system_hoisted_export_items(graph);
+        let rendered_items = export_items
+          .into_iter()
+          .filter(|item| !hoisted_export_items.contains(item))
+          .map(|(exported_name, export_ref)| {
+            let value = self.render_export_value(graph, chunk_graph, export_ref);
+            format!("exports(\"{exported_name}\", {value});")
+          })
+          .collect::<Vec<_>>();
+        if rendered_items.is_empty() {
+          return None;
         }
-      })
-      .collect::<Vec<_>>();
-    s.append(format!("export {{ {} }};", rendered_items.join(", "),));
+        s.append(rendered_items.join("\n"));
+      }
+    }
     Some(s)
   }
 
-  fn get_export_items(&self, graph: &LinkStageOutput) -> Vec<(Rstr, SymbolRef)> {
+  /// Render the expression that reads the exported symbol. Symbols from other chunks in CJS output are read
+  /// through the binding of the `require` call, such as `require_shared.foo`.
+  fn render_export_value(
+    &self,
+    graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
+    export_ref: SymbolRef,
+  ) -> String {
+    let canonical_ref = graph.symbols.par_canonical_ref_for(export_ref);
+    let symbol = graph.symbols.get(canonical_ref);
+    if let Some(ns_alias) = &symbol.namespace_alias {
+      let ns_value = self.render_export_value(graph, chunk_graph, ns_alias.namespace_ref);
+      return format!("{ns_value}.{}", ns_alias.property_name);
+    }
+    let require_binding = symbol.chunk_id.and_then(|chunk_id| {
+      self.require_binding_names_for_other_chunks.get(&chunk_id).map(|binding| (chunk_id, binding))
+    });
+    match require_binding {
+      Some((chunk_id, binding)) => {
+        let export_alias = &chunk_graph.chunks[chunk_id].exports_to_other_chunks[&canonical_ref];
+        format!("{binding}.{export_alias}")
+      }
+      None => self.canonical_names[&canonical_ref].to_string(),
+    }
+  }
+
+  pub fn get_export_items(&self, graph: &LinkStageOutput) -> Vec<(Rstr, SymbolRef)> {
     match self.kind {
       ChunkKind::EntryPoint { module, .. } => {
         let meta = &graph.metas[module];
-        meta
+        let mut export_items = meta
           .canonical_exports()
           .map(|(name, export)| (name.clone(), export.symbol_ref))
-          .collect::<Vec<_>>()
+          .collect::<Vec<_>>();
+        // Other chunks could import symbols that the entry module doesn't export, such as chunks emitted with
+        // `implicitly_loaded_after`
+        let mut exports_to_other_chunks = self
+          .exports_to_other_chunks
+          .iter()
+          .filter(|(_, alias)| !export_items.iter().any(|(name, _)| name == *alias))
+          .map(|(export_ref, alias)| (alias.clone(), *export_ref))
+          .collect::<Vec<_>>();
+        exports_to_other_chunks.sort_unstable_by(|a, b| a.0.as_str().cmp(b.0.as_str()));
+        export_items.extend(exports_to_other_chunks);
+        export_items
       }
       ChunkKind::Common => {
         let mut tmp = self
@@ -88,22 +238,24 @@ impl Chunk {
     }
   }
 
-  pub fn get_export_names(
+  /// Group names of exports by their canonical symbols.
+  pub fn get_exported_names_by_symbol(
     &self,
     graph: &LinkStageOutput,
-    output_options: &NormalizedOutputOptions,
-  ) -> Vec<String> {
+  ) -> FxHashMap<SymbolRef, Vec<Rstr>> {
+    let mut exported_names_by_symbol = FxHashMap::<SymbolRef, Vec<Rstr>>::default();
+    self.get_export_items(graph).into_iter().for_each(|(exported_name, export_ref)| {
+      let canonical_ref = graph.symbols.par_canonical_ref_for(export_ref);
+      exported_names_by_symbol.entry(canonical_ref).or_default().push(exported_name);
+    });
+    exported_names_by_symbol
+  }
+
+  pub fn get_export_names(&self, graph: &LinkStageOutput) -> Vec<String> {
     if let ChunkKind::EntryPoint { module: entry_module_id, .. } = &self.kind {
       let linking_info = &graph.metas[*entry_module_id];
       if matches!(linking_info.wrap_kind, WrapKind::Cjs) {
-        match output_options.format {
-          OutputFormat::Esm => {
-            return vec!["default".to_string()];
-          }
-          OutputFormat::Cjs => {
-            unreachable!("entry CJS should not be wrapped in `OutputFormat::Cjs`")
-          }
-        }
+        return vec!["default".to_string()];
       }
     }
 
@@ -1,4 +1,5 @@
-use rolldown_common::Specifier;
+use rolldown_common::{NamedImport, Specifier};
+use rolldown_rstr::Rstr;
 use string_wizard::MagicString;
 
 use crate::{chunk_graph::ChunkGraph, stages::link_stage::LinkStageOutput};
@@ -33,7 +34,7 @@ impl Chunk {
               is_importee_imported = true;
               s.append(format!(
                 "import * as {alias} from \"{module}\";\n",
-                module = importee.resource_id.expect_file().as_str()
+                module = self.external_import_path(importee)
               ));
               None
             }
@@ -50,11 +51,11 @@ impl Chunk {
         s.append(format!(
           "import {{ {} }} from \"{}\";\n",
           import_items.join(", "),
-          importee.resource_id.expect_file().as_str()
+          self.external_import_path(importee)
         ));
       } else if !is_importee_imported {
         // Ensure the side effect
-        s.append(format!("import \"{}\";\n", importee.resource_id.expect_file().as_str()));
+        s.append(format!("import \"{}\";\n", self.external_import_path(importee)));
       }
     });
 
@@ -77,17 +78,92 @@ impl Chunk {
           }
         })
         .collect::<Vec<_>>();
-      let file_name = importee_chunk
-        .file_name
-        .as_ref()
-        .expect("At this point, file name should already be generated");
+      let import_path = self.import_path_for(importee_chunk);
       if import_items.is_empty() {
-        s.append(format!("import \"./{file_name}\";\n"));
+        s.append(format!("import \"{import_path}\";\n"));
       } else {
         import_items.sort();
-        s.append(format!("import {{ {} }} from \"./{file_name}\";\n", import_items.join(", ")));
+        s.append(format!("import {{ {} }} from \"{import_path}\";\n", import_items.join(", ")));
       }
     });
     s
   }
+
+  /// Bind imports from external modules to the parameters of the IIFE/UMD wrapper.
+  pub fn render_imports_for_wrapper(&self, graph: &LinkStageOutput) -> MagicString<'static> {
+    let mut s = MagicString::new("");
+    let mut imports_from_external_modules =
+      self.imports_from_external_modules.iter().collect::<Vec<_>>();
+    imports_from_external_modules.sort_unstable_by_key(|(module_id, _)| {
+      graph.module_table.external_modules[**module_id].exec_order
+    });
+    imports_from_external_modules.into_iter().for_each(|(importee_id, named_imports)| {
+      let binding = &self.external_module_bindings[importee_id];
+      self.render_external_import_bindings(&mut s, graph, binding, named_imports);
+    });
+    s
+  }
+
+  /// Render `require` calls of external modules and other chunks for CJS output.
+  pub fn render_imports_for_cjs(
+    &self,
+    graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
+  ) -> MagicString<'static> {
+    let mut s = MagicString::new("");
+    let mut imports_from_external_modules =
+      self.imports_from_external_modules.iter().collect::<Vec<_>>();
+    imports_from_external_modules.sort_unstable_by_key(|(module_id, _)| {
+      graph.module_table.external_modules[**module_id].exec_order
+    });
+    imports_from_external_modules.into_iter().for_each(|(importee_id, named_imports)| {
+      let importee = &graph.module_table.external_modules[*importee_id];
+      let module_id = self.external_import_path(importee);
+      if named_imports.is_empty() {
+        // Ensure the side effect
+        s.append(format!("require(\"{module_id}\");\n"));
+      } else {
+        let binding = &self.external_module_bindings[importee_id];
+        s.append(format!("var {binding} = require(\"{module_id}\");\n"));
+        self.render_external_import_bindings(&mut s, graph, binding, named_imports);
+      }
+    });
+
+    self.imports_from_other_chunks.iter().for_each(|(exporter_id, _)| {
+      let import_path = self.import_path_for(&chunk_graph.chunks[*exporter_id]);
+      // Symbols from other chunks are accessed through the binding, such as `require_shared.foo`, to keep them live.
+      if let Some(binding) = self.require_binding_names_for_other_chunks.get(exporter_id) {
+        s.append(format!("var {binding} = require(\"{import_path}\");\n"));
+      } else {
+        s.append(format!("require(\"{import_path}\");\n"));
+      }
+    });
+    s
+  }
+
+  fn render_external_import_bindings(
+    &self,
+    s: &mut MagicString<'static>,
+    graph: &LinkStageOutput,
+    binding: &Rstr,
+    named_imports: &[NamedImport],
+  ) {
+    named_imports.iter().for_each(|item| {
+      let canonical_ref = graph.symbols.par_canonical_ref_for(item.imported_as);
+      let alias = &self.canonical_names[&canonical_ref];
+      match &item.imported {
+        Specifier::Star => {
+          s.append(format!("var {alias} = {binding};\n"));
+        }
+        Specifier::Literal(imported) if imported.as_str() == "default" => {
+          s.append(format!(
+            "var {alias} = {binding} && {binding}.__esModule ? {binding}.default : {binding};\n"
+          ));
+        }
+        Specifier::Literal(imported) => {
+          s.append(format!("var {alias} = {binding}.{imported};\n"));
+        }
+      }
+    });
+  }
 }
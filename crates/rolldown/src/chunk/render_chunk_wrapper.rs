@@ -0,0 +1,464 @@
+use oxc::ast::ast;
+use rolldown_common::{NormalModuleId, NormalizedOutputOptions, Specifier, SymbolRef};
+use rolldown_error::BuildError;
+use rolldown_rstr::Rstr;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{chunk_graph::ChunkGraph, stages::link_stage::LinkStageOutput, OutputExports};
+
+use super::Chunk;
+
+pub struct ChunkWrapper {
+  pub intro: String,
+  pub outro: String,
+}
+
+/// SystemJS output declares modules in the function passed to `System.register`, and executes them in the `execute`
+/// function it returns.
+pub struct SystemChunkWrapper {
+  /// The intro before the declared modules and the outro of the `execute` function
+  pub wrapper: ChunkWrapper,
+  /// The exports of hoisted functions and the start of the `execute` function
+  pub execute_intro: String,
+  /// Calls of the functions that execute the modules, like `execute_main();`
+  pub executor_calls: String,
+}
+
+/// An external module that is passed into the wrapper as a parameter.
+struct WrapperDependency<'a> {
+  /// The parameter name of the wrapper function
+  binding: &'a str,
+  /// The path passed to `require` or `define`, which could be rewritten by `output.paths`
+  import_path: &'a str,
+  /// The global variable name used in environments without a module loader. `None` if the dependency is
+  /// only imported for side effects and isn't passed into the wrapper.
+  global: Option<String>,
+}
+
+struct WrapperInfo<'a> {
+  /// The chunk exports a single value in `default` export mode
+  is_default_export: bool,
+  has_exports: bool,
+  dependencies: Vec<WrapperDependency<'a>>,
+}
+
+impl Chunk {
+  fn collect_wrapper_info<'a>(
+    &'a self,
+    graph: &'a LinkStageOutput,
+    output_options: &NormalizedOutputOptions,
+    export_mode: OutputExports,
+    warnings: &mut Vec<BuildError>,
+  ) -> WrapperInfo<'a> {
+    let is_default_export = export_mode == OutputExports::Default;
+    let has_exports =
+      export_mode == OutputExports::Named && !self.get_export_names(graph).is_empty();
+
+    let mut external_modules = self
+      .imports_from_external_modules
+      .iter()
+      .map(|(id, named_imports)| (&graph.module_table.external_modules[*id], named_imports))
+      .collect::<Vec<_>>();
+    external_modules.sort_unstable_by_key(|(module, _)| module.exec_order);
+    // Parameters are positional, so only trailing side-effect-only dependencies can be left out of the wrapper
+    let param_count = external_modules
+      .iter()
+      .rposition(|(_, named_imports)| !named_imports.is_empty())
+      .map_or(0, |index| index + 1);
+    let dependencies = external_modules
+      .into_iter()
+      .enumerate()
+      .map(|(index, (module, _))| {
+        let module_id = module.resource_id.expect_file().as_str();
+        let global = (index < param_count).then(|| {
+          output_options.globals.get(module_id).cloned().unwrap_or_else(|| {
+            let guessed_name = module.resource_id.expect_file().representative_name().into_owned();
+            warnings.push(
+              BuildError::missing_global_name(module_id, guessed_name.as_str())
+                .with_severity_warning(),
+            );
+            guessed_name
+          })
+        });
+        WrapperDependency {
+          binding: self.external_module_bindings[&module.id].as_str(),
+          import_path: self.external_import_path(module),
+          global,
+        }
+      })
+      .collect();
+
+    WrapperInfo { is_default_export, has_exports, dependencies }
+  }
+
+  /// CJS output doesn't need a function wrapper. It only marks the chunk as an ES module, like
+  ///
+  /// ```js
+  /// "use strict";
+  /// Object.defineProperty(exports, "__esModule", { value: true });
+  /// ```
+  pub fn render_cjs_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    export_mode: OutputExports,
+  ) -> ChunkWrapper {
+    let mut intro = "\"use strict\";\n".to_string();
+    if export_mode == OutputExports::Named && !self.get_export_names(graph).is_empty() {
+      intro.push_str("Object.defineProperty(exports, \"__esModule\", { value: true });\n");
+    }
+    ChunkWrapper { intro, outro: String::new() }
+  }
+
+  /// Render something like
+  ///
+  /// ```js
+  /// var name = (function (exports, foo) {
+  /// "use strict";
+  /// ...
+  /// return exports;
+  /// })({}, globalFoo);
+  /// ```
+  pub fn render_iife_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &NormalizedOutputOptions,
+    export_mode: OutputExports,
+    warnings: &mut Vec<BuildError>,
+  ) -> Result<ChunkWrapper, BuildError> {
+    let WrapperInfo { is_default_export, has_exports, dependencies } =
+      self.collect_wrapper_info(graph, output_options, export_mode, warnings);
+
+    if has_exports && output_options.name.is_none() {
+      return Err(BuildError::missing_name_option_for_iife_export());
+    }
+
+    let mut params = vec![];
+    let mut args = vec![];
+    if has_exports {
+      params.push("exports");
+      args.push("{}");
+    }
+    dependencies.iter().for_each(|dep| {
+      if let Some(global) = &dep.global {
+        params.push(dep.binding);
+        args.push(global);
+      }
+    });
+
+    let assignment = match &output_options.name {
+      Some(name) if has_exports || is_default_export => render_namespace_assignment(name),
+      _ => String::new(),
+    };
+
+    Ok(ChunkWrapper {
+      intro: format!("{assignment}(function ({}) {{\n\"use strict\";\n", params.join(", ")),
+      outro: format!(
+        "{}}})({});",
+        if has_exports { "return exports;\n" } else { "" },
+        args.join(", ")
+      ),
+    })
+  }
+
+  /// Render something like
+  ///
+  /// ```js
+  /// (function (global, factory) {
+  ///   typeof exports === 'object' && typeof module !== 'undefined' ? factory(exports, require('foo')) :
+  ///   typeof define === 'function' && define.amd ? define(['exports', 'foo'], factory) :
+  ///   (global = typeof globalThis !== 'undefined' ? globalThis : global || self, factory(global.name = {}, global.foo));
+  /// })(this, (function (exports, foo) {
+  /// "use strict";
+  /// ...
+  /// }));
+  /// ```
+  pub fn render_umd_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    output_options: &NormalizedOutputOptions,
+    export_mode: OutputExports,
+    warnings: &mut Vec<BuildError>,
+  ) -> Result<ChunkWrapper, BuildError> {
+    let WrapperInfo { is_default_export, has_exports, dependencies } =
+      self.collect_wrapper_info(graph, output_options, export_mode, warnings);
+
+    if has_exports && output_options.name.is_none() {
+      return Err(BuildError::missing_name_option_for_umd_export());
+    }
+
+    let mut params = vec![];
+    let mut cjs_args = vec![];
+    let mut amd_deps = vec![];
+    let mut global_args = vec![];
+    if has_exports {
+      params.push("exports".to_string());
+      cjs_args.push("exports".to_string());
+      amd_deps.push("'exports'".to_string());
+      let name = output_options.name.as_deref().unwrap_or_default();
+      global_args.push(render_global_namespace(name, "{}"));
+    }
+    dependencies.iter().for_each(|dep| {
+      cjs_args.push(format!("require('{}')", dep.import_path));
+      amd_deps.push(format!("'{}'", dep.import_path));
+      if let Some(global) = &dep.global {
+        params.push(dep.binding.to_string());
+        global_args.push(format!("global.{global}"));
+      }
+    });
+
+    let cjs_factory_call = format!("factory({})", cjs_args.join(", "));
+    let global_factory_call = format!("factory({})", global_args.join(", "));
+    let (cjs_factory_call, global_factory_call) = if is_default_export {
+      (
+        format!("module.exports = {cjs_factory_call}"),
+        match &output_options.name {
+          Some(name) => render_global_namespace(name, &global_factory_call),
+          None => global_factory_call,
+        },
+      )
+    } else {
+      (cjs_factory_call, global_factory_call)
+    };
+
+    let intro = format!(
+      "(function (global, factory) {{
+  typeof exports === 'object' && typeof module !== 'undefined' ? {cjs_factory_call} :
+  typeof define === 'function' && define.amd ? define([{amd_deps}], factory) :
+  (global = typeof globalThis !== 'undefined' ? globalThis : global || self, {global_factory_call});
+}})(this, (function ({params}) {{
+\"use strict\";
+",
+      amd_deps = amd_deps.join(", "),
+      params = params.join(", "),
+    );
+
+    Ok(ChunkWrapper { intro, outro: "}));".to_string() })
+  }
+
+  /// Render something like
+  ///
+  /// ```js
+  /// System.register(['foo', './shared.js'], (function (exports, module) {
+  /// "use strict";
+  /// var foo, shared;
+  /// ...
+  /// exports("bar", bar);
+  /// return {
+  /// setters: [function (module) {
+  /// foo = module.foo;
+  /// }, function (module) {
+  /// shared = module.shared;
+  /// }],
+  /// execute: (function () {
+  /// execute_main();
+  /// ...
+  /// })
+  /// };
+  /// }));
+  /// ```
+  ///
+  /// Modules are declared before `return`, where exported functions are exported right away, so that they are
+  /// available to circular importers before the chunk is executed.
+  pub fn render_system_wrapper(
+    &self,
+    graph: &LinkStageOutput,
+    chunk_graph: &ChunkGraph,
+  ) -> SystemChunkWrapper {
+    // Imported bindings that are re-exported need to be exported again once they are updated
+    let exported_names_by_symbol = self.get_exported_names_by_symbol(graph);
+    let mut dependencies = vec![];
+    let mut setters = vec![];
+    let mut imported_bindings = vec![];
+
+    let mut imports_from_external_modules =
+      self.imports_from_external_modules.iter().collect::<Vec<_>>();
+    imports_from_external_modules.sort_unstable_by_key(|(module_id, _)| {
+      graph.module_table.external_modules[**module_id].exec_order
+    });
+    imports_from_external_modules.into_iter().for_each(|(importee_id, named_imports)| {
+      let importee = &graph.module_table.external_modules[*importee_id];
+      dependencies.push(format!("'{}'", self.external_import_path(importee)));
+      let bindings = named_imports
+        .iter()
+        .map(|item| {
+          let canonical_ref = graph.symbols.par_canonical_ref_for(item.imported_as);
+          let value = match &item.imported {
+            Specifier::Star => "module".to_string(),
+            Specifier::Literal(imported) => format!("module.{imported}"),
+          };
+          (canonical_ref, value)
+        })
+        .collect::<Vec<_>>();
+      imported_bindings.extend(bindings.iter().map(|(canonical_ref, _)| *canonical_ref));
+      setters.push(self.render_system_setter(bindings, &exported_names_by_symbol));
+    });
+
+    let mut importee_chunk_ids = self.imports_from_other_chunks.keys().copied().collect::<Vec<_>>();
+    importee_chunk_ids.sort_unstable();
+    importee_chunk_ids.into_iter().for_each(|importee_chunk_id| {
+      let import_path = self.import_path_for(&chunk_graph.chunks[importee_chunk_id]);
+      dependencies.push(format!("'{import_path}'"));
+      let bindings = self.imports_from_other_chunks[&importee_chunk_id]
+        .iter()
+        .map(|item| {
+          let canonical_ref = graph.symbols.par_canonical_ref_for(item.import_ref);
+          let Specifier::Literal(export_alias) = item.export_alias.as_ref().unwrap() else {
+            panic!("should not be star import from other chunks")
+          };
+          (canonical_ref, format!("module.{export_alias}"))
+        })
+        .collect::<Vec<_>>();
+      imported_bindings.extend(bindings.iter().map(|(canonical_ref, _)| *canonical_ref));
+      setters.push(self.render_system_setter(bindings, &exported_names_by_symbol));
+    });
+
+    let mut imported_bindings = imported_bindings
+      .into_iter()
+      .map(|canonical_ref| self.canonical_names[&canonical_ref].as_str())
+      .collect::<Vec<_>>();
+    imported_bindings.sort_unstable();
+    imported_bindings.dedup();
+    let imported_bindings_decl = if imported_bindings.is_empty() {
+      String::new()
+    } else {
+      format!("var {};\n", imported_bindings.join(", "))
+    };
+
+    let intro = format!(
+      "System.register([{dependencies}], (function (exports, module) {{
+\"use strict\";
+{imported_bindings_decl}",
+      dependencies = dependencies.join(", "),
+    );
+
+    let mut hoisted_exports = String::new();
+    self.get_system_hoisted_export_items(graph).into_iter().for_each(
+      |(exported_name, export_ref)| {
+        let canonical_ref = graph.symbols.par_canonical_ref_for(export_ref);
+        let canonical_name = &self.canonical_names[&canonical_ref];
+        hoisted_exports.push_str(&format!("exports(\"{exported_name}\", {canonical_name});\n"));
+      },
+    );
+    let execute_intro = format!(
+      "{hoisted_exports}return {{
+setters: [{setters}],
+execute: (function () {{",
+      setters = setters.join(", "),
+    );
+
+    let executor_calls = self
+      .modules
+      .iter()
+      .filter(|id| has_system_executor(graph, **id, &self.system_executor_names[*id]))
+      .map(|id| format!("{}();", self.system_executor_names[id]))
+      .collect::<Vec<_>>()
+      .join("\n");
+
+    SystemChunkWrapper {
+      wrapper: ChunkWrapper { intro, outro: "})\n};\n}));".to_string() },
+      execute_intro,
+      executor_calls,
+    }
+  }
+
+  /// Exports of functions that are declared at the top level of modules in SystemJS output. They are exported
+  /// before the chunk is executed, just like function declarations are hoisted.
+  pub fn get_system_hoisted_export_items(&self, graph: &LinkStageOutput) -> Vec<(Rstr, SymbolRef)> {
+    let function_names = self
+      .modules
+      .iter()
+      .flat_map(|id| graph.ast_table[*id].program().body.iter())
+      .filter_map(|stmt| match stmt {
+        ast::Statement::Declaration(ast::Declaration::FunctionDeclaration(func)) => {
+          func.id.as_ref().map(|id| id.name.as_str())
+        }
+        _ => None,
+      })
+      .collect::<FxHashSet<_>>();
+
+    self
+      .get_export_items(graph)
+      .into_iter()
+      .filter(|(_, export_ref)| {
+        let canonical_ref = graph.symbols.par_canonical_ref_for(*export_ref);
+        graph.symbols.get(canonical_ref).namespace_alias.is_none()
+          && self
+            .canonical_names
+            .get(&canonical_ref)
+            .is_some_and(|canonical_name| function_names.contains(canonical_name.as_str()))
+      })
+      .collect()
+  }
+
+  /// Render a setter that updates imported bindings with the latest exports of the dependency, or `null` if
+  /// nothing is imported from it.
+  fn render_system_setter(
+    &self,
+    mut bindings: Vec<(SymbolRef, String)>,
+    exported_names_by_symbol: &FxHashMap<SymbolRef, Vec<Rstr>>,
+  ) -> String {
+    if bindings.is_empty() {
+      return "null".to_string();
+    }
+    bindings.sort_unstable_by(|a, b| self.canonical_names[&a.0].cmp(&self.canonical_names[&b.0]));
+    bindings.dedup_by_key(|(canonical_ref, _)| *canonical_ref);
+    let mut lines = vec![];
+    bindings.into_iter().for_each(|(canonical_ref, value)| {
+      let local_binding = &self.canonical_names[&canonical_ref];
+      lines.push(format!("{local_binding} = {value};"));
+      exported_names_by_symbol.get(&canonical_ref).into_iter().flatten().for_each(|name| {
+        lines.push(format!("exports(\"{name}\", {local_binding});"));
+      });
+    });
+    format!("function (module) {{\n{}\n}}", lines.join("\n"))
+  }
+}
+
+/// Render the left side of the assignment. Dotted names like `a.b.c` are treated as nested namespaces.
+fn render_namespace_assignment(name: &str) -> String {
+  let mut parts = name.split('.').collect::<Vec<_>>();
+  if parts.len() == 1 {
+    return format!("var {name} = ");
+  }
+  parts.pop();
+  let mut ret = String::new();
+  let mut namespace = "this".to_string();
+  parts.into_iter().for_each(|part| {
+    namespace = format!("{namespace}.{part}");
+    ret.push_str(&format!("{namespace} = {namespace} || {{}};\n"));
+  });
+  ret.push_str(&format!("this.{name} = "));
+  ret
+}
+
+/// Render the assignment of `value` to the global, like `global.name = value` or
+/// `(global.a = global.a || {}, global.a.b = value)` for dotted names.
+fn render_global_namespace(name: &str, value: &str) -> String {
+  let mut parts = name.split('.').collect::<Vec<_>>();
+  if parts.len() == 1 {
+    return format!("global.{name} = {value}");
+  }
+  parts.pop();
+  let mut expressions = vec![];
+  let mut namespace = "global".to_string();
+  parts.into_iter().for_each(|part| {
+    namespace = format!("{namespace}.{part}");
+    expressions.push(format!("{namespace} = {namespace} || {{}}"));
+  });
+  expressions.push(format!("global.{name} = {value}"));
+  format!("({})", expressions.join(", "))
+}
+
+/// Whether the module is executed by a function with the given name, which is generated by the finalizer if the
+/// module has any statement other than declarations.
+fn has_system_executor(
+  graph: &LinkStageOutput,
+  module_id: NormalModuleId,
+  executor_name: &str,
+) -> bool {
+  matches!(
+    graph.ast_table[module_id].program().body.last(),
+    Some(ast::Statement::Declaration(ast::Declaration::FunctionDeclaration(func)))
+      if func.id.as_ref().is_some_and(|id| id.name == executor_name)
+  )
+}
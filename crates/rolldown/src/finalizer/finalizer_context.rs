@@ -1,4 +1,4 @@
-use rolldown_common::{NormalModule, NormalModuleId, SymbolRef};
+use rolldown_common::{NormalModule, NormalModuleId, NormalizedOutputOptions, SymbolRef};
 
 use rolldown_rstr::Rstr;
 use rustc_hash::FxHashMap;
@@ -8,7 +8,7 @@ use crate::{
   runtime::RuntimeModuleBrief,
   types::{
     linking_metadata::{LinkingMetadata, LinkingMetadataVec},
-    module_table::NormalModuleVec,
+    module_table::{ExternalModuleVec, NormalModuleVec},
     symbols::Symbols,
   },
 };
@@ -17,10 +17,15 @@ pub struct FinalizerContext<'me> {
   pub id: NormalModuleId,
   pub module: &'me NormalModule,
   pub modules: &'me NormalModuleVec,
+  pub external_modules: &'me ExternalModuleVec,
   pub linking_info: &'me LinkingMetadata,
   pub linking_infos: &'me LinkingMetadataVec,
   pub symbols: &'me Symbols,
   pub canonical_names: &'me FxHashMap<SymbolRef, Rstr>,
   pub runtime: &'me RuntimeModuleBrief,
   pub chunk_graph: &'me ChunkGraph,
+  pub output_options: &'me NormalizedOutputOptions,
+  /// Names that the current chunk exports its symbols as. Only collected for SystemJS output, where reassigning
+  /// an exported symbol needs to notify importers.
+  pub exported_names_by_symbol: &'me FxHashMap<SymbolRef, Vec<Rstr>>,
 }
@@ -1,15 +1,20 @@
 // cSpell:disable
 
 use oxc::{
-  allocator,
   ast::{
     ast::{self, SimpleAssignmentTarget},
     VisitMut,
   },
   span::{Span, SPAN},
+  syntax::operator::{BinaryOperator, UpdateOperator},
+};
+use rolldown_common::{
+  ExportsKind, ImportRecordId, ImportRecordKey, ModuleId, NormalModuleId, SymbolRef, WrapKind,
 };
-use rolldown_common::{ExportsKind, ModuleId, SymbolRef, WrapKind};
 use rolldown_oxc_utils::{Dummy, ExpressionExt, IntoIn, StatementExt, TakeIn};
+use rolldown_rstr::Rstr;
+
+use crate::OutputFormat;
 
 use super::Finalizer;
 
@@ -17,6 +22,246 @@ impl<'ast, 'me: 'ast> Finalizer<'me, 'ast> {
   fn visit_top_level_statement_mut(&mut self, stmt: &mut ast::Statement<'ast>) {
     self.visit_statement(stmt);
   }
+
+  /// The import record of `import('...')`. It needs to be looked up before the source is rewritten.
+  fn dynamic_import_record_id(&self, expr: &ast::Expression<'ast>) -> Option<ImportRecordId> {
+    let ast::Expression::ImportExpression(import_expr) = expr else {
+      return None;
+    };
+    let ast::Expression::StringLiteral(source) = &import_expr.source else {
+      return None;
+    };
+    if import_expr.arguments.len() != 0 {
+      return None;
+    }
+    Some(self.ctx.module.imports[&ImportRecordKey::new(import_expr.span, &source.value)])
+  }
+
+  /// Rewrite dynamic imports for formats that can't use `import()` directly. The source is expected to be already
+  /// rewritten to the file name of the imported chunk.
+  /// - CJS: `import('./foo.js')` => `Promise.resolve().then(() => require('./foo.js'))`
+  /// - SystemJS: `import('./foo.js')` => `module.import('./foo.js')`
+  fn rewrite_dynamic_import(&self, expr: &mut ast::Expression<'ast>, rec_id: ImportRecordId) {
+    let ast::Expression::ImportExpression(import_expr) = expr else {
+      return;
+    };
+    let ast::Expression::StringLiteral(source) = &import_expr.source else {
+      return;
+    };
+    if self.ctx.output_options.inline_dynamic_imports {
+      if let ModuleId::Normal(importee_id) = self.ctx.module.import_records[rec_id].resolved_module
+      {
+        *expr =
+          self.snippet.promise_resolve_then_expr(self.inlined_dynamic_import_expr(importee_id));
+        return;
+      }
+    }
+    match self.ctx.output_options.format {
+      OutputFormat::Cjs => {
+        if !matches!(self.ctx.module.import_records[rec_id].resolved_module, ModuleId::Normal(_)) {
+          return;
+        }
+        let require_call = self.snippet.callee_call_expr_with_arg_expr(
+          self.snippet.id_ref_expr("require", SPAN),
+          self.snippet.string_literal_expr(&source.value),
+        );
+        *expr = self.snippet.promise_resolve_then_expr(require_call);
+      }
+      OutputFormat::System => {
+        *expr = self.snippet.callee_call_expr_with_arg_expr(
+          self.snippet.static_member_expr_expr(self.snippet.id_ref_expr("module", SPAN), "import"),
+          self.snippet.string_literal_expr(&source.value),
+        );
+      }
+      OutputFormat::Esm | OutputFormat::Iife | OutputFormat::Umd => {}
+    }
+  }
+
+  /// The namespace object of an inlined dynamically imported module
+  /// - `foo_exports`
+  /// - `(init_foo(), foo_exports)` for lazily initialized ESM
+  /// - `__toESM(require_foo())` for CommonJS
+  fn inlined_dynamic_import_expr(&self, importee_id: NormalModuleId) -> ast::Expression<'ast> {
+    let importee = &self.ctx.modules[importee_id];
+    let importee_linking_info = &self.ctx.linking_infos[importee_id];
+    let namespace_expr =
+      || self.generate_finalized_expr_for_symbol_ref(importee.namespace_symbol, false);
+    let wrapper_call_expr =
+      || {
+        self.snippet.callee_call_expr_expr(self.generate_finalized_expr_for_symbol_ref(
+          importee_linking_info.wrapper_ref.unwrap(),
+          false,
+        ))
+      };
+    match importee_linking_info.wrap_kind {
+      WrapKind::None => namespace_expr(),
+      WrapKind::Esm => self.snippet.seq2_in_paren_expr(wrapper_call_expr(), namespace_expr()),
+      WrapKind::Cjs => self.snippet.callee_call_expr_with_arg_expr(
+        self.generate_finalized_expr_for_runtime_symbol("__toESM"),
+        wrapper_call_expr(),
+      ),
+    }
+  }
+
+  /// Return the names that the current chunk exports the assigned symbol as. In SystemJS output, importers need to
+  /// be notified when these symbols are reassigned.
+  fn exported_names_for_assignment(&self, expr: &ast::Expression<'ast>) -> Option<&'me [Rstr]> {
+    let id_ref = match expr {
+      ast::Expression::AssignmentExpression(assign_expr) => match &assign_expr.left {
+        ast::AssignmentTarget::SimpleAssignmentTarget(
+          SimpleAssignmentTarget::AssignmentTargetIdentifier(id_ref),
+        ) => id_ref,
+        _ => return None,
+      },
+      ast::Expression::UpdateExpression(update_expr) => match &update_expr.argument {
+        SimpleAssignmentTarget::AssignmentTargetIdentifier(id_ref) => id_ref,
+        _ => return None,
+      },
+      _ => return None,
+    };
+    let canonical_ref = self.exported_symbol_for_reference(id_ref)?;
+    self.ctx.exported_names_by_symbol.get(&canonical_ref).map(Vec::as_slice)
+  }
+
+  /// Return the exported symbols assigned by the destructuring assignment, like `a` and `b` in `[a, b] = [b, a]`.
+  fn exported_symbols_for_destructuring_assignment(
+    &self,
+    expr: &ast::Expression<'ast>,
+  ) -> Vec<SymbolRef> {
+    let ast::Expression::AssignmentExpression(assign_expr) = expr else {
+      return vec![];
+    };
+    if assign_expr.left.is_simple() {
+      return vec![];
+    }
+    let mut id_refs = vec![];
+    collect_assignment_target_identifiers(&assign_expr.left, &mut id_refs);
+    let mut symbols = vec![];
+    id_refs.into_iter().filter_map(|id_ref| self.exported_symbol_for_reference(id_ref)).for_each(
+      |canonical_ref| {
+        if !symbols.contains(&canonical_ref) {
+          symbols.push(canonical_ref);
+        }
+      },
+    );
+    symbols
+  }
+
+  /// Return the canonical symbol of the reference if the current chunk exports it.
+  fn exported_symbol_for_reference(&self, id_ref: &ast::IdentifierReference) -> Option<SymbolRef> {
+    let reference_id = id_ref.reference_id.get()?;
+    let symbol_id = self.scope.symbol_id_for(reference_id)?;
+    let canonical_ref = self.ctx.symbols.par_canonical_ref_for((self.ctx.id, symbol_id).into());
+    self.ctx.exported_names_by_symbol.contains_key(&canonical_ref).then_some(canonical_ref)
+  }
+
+  /// Wrap the destructuring assignment with an `exports` call that updates all the assigned symbols, while keeping the
+  /// value of the assignment, like
+  /// - `[a, b] = [b, a]` => `(v => (exports({ "a": a, "b": b }), v))([a, b] = [b, a])`
+  fn wrap_destructuring_assignment_with_exports_call(
+    &self,
+    expr: &mut ast::Expression<'ast>,
+    exported_symbols: &[SymbolRef],
+  ) {
+    let mut exported_values = ast::ObjectExpression::dummy(self.alloc);
+    let mut local_names = vec![];
+    exported_symbols.iter().for_each(|canonical_ref| {
+      let local_name = self.canonical_name_for(*canonical_ref);
+      local_names.push(local_name.as_str());
+      self.ctx.exported_names_by_symbol[canonical_ref].iter().for_each(|exported_name| {
+        exported_values.properties.push(ast::ObjectPropertyKind::ObjectProperty(
+          ast::ObjectProperty {
+            key: ast::PropertyKey::Expression(self.snippet.string_literal_expr(exported_name)),
+            value: self.snippet.id_ref_expr(local_name, SPAN),
+            ..Dummy::dummy(self.alloc)
+          }
+          .into_in(self.alloc),
+        ));
+      });
+    });
+
+    // The parameter shouldn't shadow the symbols that are read in the arrow function
+    let mut param_name = "v".to_string();
+    while local_names.contains(&param_name.as_str()) {
+      param_name.push('$');
+    }
+
+    // v => (exports({ ... }), v)
+    let mut arrow_expr = self.snippet.only_return_arrow_expr(self.snippet.seq2_in_paren_expr(
+      self.snippet.callee_call_expr_with_arg_expr(
+        self.snippet.id_ref_expr("exports", SPAN),
+        ast::Expression::ObjectExpression(exported_values.into_in(self.alloc)),
+      ),
+      self.snippet.id_ref_expr(&param_name, SPAN),
+    ));
+    if let ast::Expression::ArrowFunctionExpression(arrow_expr) = &mut arrow_expr {
+      arrow_expr.params.items.push(ast::FormalParameter {
+        pattern: ast::BindingPattern {
+          kind: ast::BindingPatternKind::BindingIdentifier(
+            self.snippet.id(&param_name, SPAN).into_in(self.alloc),
+          ),
+          ..Dummy::dummy(self.alloc)
+        },
+        ..Dummy::dummy(self.alloc)
+      });
+    }
+
+    *expr = self.snippet.callee_call_expr_with_arg_expr(
+      ast::Expression::ParenthesizedExpression(
+        ast::ParenthesizedExpression { expression: arrow_expr, ..Dummy::dummy(self.alloc) }
+          .into_in(self.alloc),
+      ),
+      expr.take_in(self.alloc),
+    );
+  }
+
+  /// Wrap the assignment with `exports` calls, like
+  /// - `count = 1` => `exports("count", count = 1)`
+  /// - `count++` => `(exports("count", count + 1), count++)`
+  fn wrap_assignment_with_exports_calls(
+    &self,
+    expr: &mut ast::Expression<'ast>,
+    exported_names: &[Rstr],
+  ) {
+    let wrap_with_exports_calls = |value: ast::Expression<'ast>| {
+      exported_names.iter().fold(value, |value, exported_name| {
+        self.snippet.callee_call_expr_with_2arg_expr(
+          self.snippet.id_ref_expr("exports", SPAN),
+          self.snippet.string_literal_expr(exported_name),
+          value,
+        )
+      })
+    };
+
+    let new_value_of_postfix_update = match expr {
+      ast::Expression::UpdateExpression(update_expr) if !update_expr.prefix => {
+        let SimpleAssignmentTarget::AssignmentTargetIdentifier(id_ref) = &update_expr.argument
+        else {
+          unreachable!("should be checked in `exported_names_for_assignment`")
+        };
+        let operator = match update_expr.operator {
+          UpdateOperator::Increment => BinaryOperator::Addition,
+          UpdateOperator::Decrement => BinaryOperator::Subtraction,
+        };
+        Some(ast::Expression::BinaryExpression(
+          ast::BinaryExpression {
+            span: SPAN,
+            left: self.snippet.id_ref_expr(&id_ref.name, SPAN),
+            operator,
+            right: self.snippet.number_expr(1.0),
+          }
+          .into_in(self.alloc),
+        ))
+      }
+      _ => None,
+    };
+
+    *expr = if let Some(new_value) = new_value_of_postfix_update {
+      self.snippet.seq2_in_paren_expr(wrap_with_exports_calls(new_value), expr.take_in(self.alloc))
+    } else {
+      wrap_with_exports_calls(expr.take_in(self.alloc))
+    };
+  }
 }
 
 impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
@@ -41,12 +286,14 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
         }
 
         if let Some(import_decl) = top_stmt.as_import_declaration() {
-          let rec_id = self.ctx.module.imports[&import_decl.span];
+          let rec_id = self.ctx.module.imports
+            [&ImportRecordKey::new(import_decl.span, &import_decl.source.value)];
           if self.should_remove_import_export_stmt(&mut top_stmt, rec_id) {
             return;
           }
         } else if let Some(export_all_decl) = top_stmt.as_export_all_declaration() {
-          let rec_id = self.ctx.module.imports[&export_all_decl.span];
+          let rec_id = self.ctx.module.imports
+            [&ImportRecordKey::new(export_all_decl.span, &export_all_decl.source.value)];
           // "export * as ns from 'path'"
           if let Some(_alias) = &export_all_decl.exported {
             if self.should_remove_import_export_stmt(&mut top_stmt, rec_id) {
@@ -62,52 +309,48 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
             let importee_linking_info = &self.ctx.linking_infos[importee_id];
             let importee = &self.ctx.modules[importee_id];
             if matches!(importee_linking_info.wrap_kind, WrapKind::Esm) {
-              let wrapper_ref_name =
-                self.canonical_name_for(importee_linking_info.wrapper_ref.unwrap());
-              program.body.push(self.snippet.call_expr_stmt(wrapper_ref_name));
+              program.body.push(self.snippet.expr_stmt(self.snippet.callee_call_expr_expr(
+                self.generate_finalized_expr_for_symbol_ref(
+                  importee_linking_info.wrapper_ref.unwrap(),
+                  false,
+                ),
+              )));
             }
 
             match importee.exports_kind {
               ExportsKind::Esm => {
                 if importee_linking_info.has_dynamic_exports {
-                  let re_export_fn_name = self.canonical_name_for_runtime("__reExport");
                   let importer_namespace_name =
                     self.canonical_name_for(self.ctx.module.namespace_symbol);
                   // __reExport(exports, otherExports)
-                  let importee_namespace_name = self.canonical_name_for(importee.namespace_symbol);
-                  program.body.push(
-                    self
-                      .snippet
-                      .call_expr_with_2arg_expr(
-                        re_export_fn_name,
-                        importer_namespace_name,
-                        importee_namespace_name,
-                      )
-                      .into_in(self.alloc),
-                  );
+                  program.body.push(self.snippet.expr_stmt(
+                    self.snippet.callee_call_expr_with_2arg_expr(
+                      self.generate_finalized_expr_for_runtime_symbol("__reExport"),
+                      self.snippet.id_ref_expr(importer_namespace_name, SPAN),
+                      self.generate_finalized_expr_for_symbol_ref(importee.namespace_symbol, false),
+                    ),
+                  ));
                 }
               }
               ExportsKind::CommonJs => {
-                let re_export_fn_name = self.canonical_name_for_runtime("__reExport");
                 let importer_namespace_name =
                   self.canonical_name_for(self.ctx.module.namespace_symbol);
                 // __reExport(exports, __toESM(require_xxxx()))
-                let to_esm_fn_name = self.canonical_name_for_runtime("__toESM");
-                let importee_wrapper_ref_name =
-                  self.canonical_name_for(importee_linking_info.wrapper_ref.unwrap());
-                program.body.push(
-                  self
-                    .snippet
-                    .call_expr_with_2arg_expr_expr(
-                      re_export_fn_name,
-                      self.snippet.id_ref_expr(importer_namespace_name, SPAN),
-                      self.snippet.call_expr_with_arg_expr_expr(
-                        to_esm_fn_name,
-                        self.snippet.call_expr_expr(importee_wrapper_ref_name),
+                program.body.push(self.snippet.expr_stmt(
+                  self.snippet.callee_call_expr_with_2arg_expr(
+                    self.generate_finalized_expr_for_runtime_symbol("__reExport"),
+                    self.snippet.id_ref_expr(importer_namespace_name, SPAN),
+                    self.snippet.callee_call_expr_with_arg_expr(
+                      self.generate_finalized_expr_for_runtime_symbol("__toESM"),
+                      self.snippet.callee_call_expr_expr(
+                        self.generate_finalized_expr_for_symbol_ref(
+                          importee_linking_info.wrapper_ref.unwrap(),
+                          false,
+                        ),
                       ),
-                    )
-                    .into_in(self.alloc),
-                );
+                    ),
+                  ),
+                ));
               }
               ExportsKind::None => {}
             }
@@ -163,7 +406,9 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
             }
           } else {
             // `export { foo } from 'path'`
-            let rec_id = self.ctx.module.imports[&named_decl.span];
+            let source = named_decl.source.as_ref().expect("re-exports should have a source");
+            let rec_id =
+              self.ctx.module.imports[&ImportRecordKey::new(named_decl.span, &source.value)];
             if self.should_remove_import_export_stmt(&mut top_stmt, rec_id) {
               return;
             }
@@ -194,79 +439,32 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
       match self.ctx.linking_info.wrap_kind {
         WrapKind::Cjs => {
           let wrap_ref_name = self.canonical_name_for(self.ctx.linking_info.wrapper_ref.unwrap());
-          let commonjs_ref_name = self.canonical_name_for_runtime("__commonJSMin");
           let old_body = program.body.take_in(self.alloc);
 
           program.body.push(self.snippet.commonjs_wrapper_stmt(
             wrap_ref_name,
-            commonjs_ref_name,
+            self.generate_finalized_expr_for_runtime_symbol("__commonJSMin"),
             old_body,
           ));
         }
         WrapKind::Esm => {
           let wrap_ref_name = self.canonical_name_for(self.ctx.linking_info.wrapper_ref.unwrap());
-          let esm_ref_name = self.canonical_name_for_runtime("__esmMin");
           let old_body = program.body.take_in(self.alloc);
 
-          let mut fn_stmts = allocator::Vec::new_in(self.alloc);
-          let mut hoisted_names = vec![];
-          let mut stmts_inside_closure = allocator::Vec::new_in(self.alloc);
-
-          // Hoist all top-level "var" and "function" declarations out of the closure
-          old_body.into_iter().for_each(|mut stmt| match &mut stmt {
-            ast::Statement::Declaration(decl) => match decl {
-              ast::Declaration::VariableDeclaration(_) | ast::Declaration::ClassDeclaration(_) => {
-                if let Some(converted) = self.convert_decl_to_assignment(decl, &mut hoisted_names) {
-                  stmts_inside_closure.push(converted);
-                }
-              }
-              ast::Declaration::FunctionDeclaration(_) => {
-                fn_stmts.push(stmt);
-              }
-              ast::Declaration::UsingDeclaration(_) => unimplemented!(),
-              _ => {}
-            },
-            ast::Statement::ModuleDeclaration(_) => unreachable!(
-              "At this point, all module declarations should have been removed or transformed"
-            ),
-            _ => {
-              stmts_inside_closure.push(stmt);
-            }
-          });
-          program.body.extend(fn_stmts);
-          if !hoisted_names.is_empty() {
-            let mut declarators = allocator::Vec::new_in(self.alloc);
-            declarators.reserve_exact(hoisted_names.len());
-            hoisted_names.into_iter().for_each(|var_name| {
-              declarators.push(ast::VariableDeclarator {
-                id: ast::BindingPattern {
-                  kind: ast::BindingPatternKind::BindingIdentifier(
-                    self.snippet.id(&var_name, SPAN).into_in(self.alloc),
-                  ),
-                  ..Dummy::dummy(self.alloc)
-                },
-                kind: ast::VariableDeclarationKind::Var,
-                ..Dummy::dummy(self.alloc)
-              });
-            });
-            program.body.push(ast::Statement::Declaration(ast::Declaration::VariableDeclaration(
-              ast::VariableDeclaration {
-                declarations: declarators,
-                kind: ast::VariableDeclarationKind::Var,
-                ..Dummy::dummy(self.alloc)
-              }
-              .into_in(self.alloc),
-            )));
-          }
+          let stmts_inside_closure = self.hoist_declarations(program, old_body);
           program.body.push(self.snippet.esm_wrapper_stmt(
             wrap_ref_name,
-            esm_ref_name,
+            self.generate_finalized_expr_for_runtime_symbol("__esmMin"),
             stmts_inside_closure,
           ));
         }
         WrapKind::None => {}
       }
     }
+
+    if matches!(self.ctx.output_options.format, OutputFormat::System) {
+      self.move_statements_into_system_executor(program);
+    }
   }
 
   fn visit_binding_identifier(&mut self, ident: &mut ast::BindingIdentifier<'ast>) {
@@ -311,24 +509,45 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
 
   #[allow(clippy::collapsible_else_if)]
   fn visit_expression(&mut self, expr: &mut ast::Expression<'ast>) {
+    // This needs to be checked before the assignment target gets renamed
+    let (exported_names, exported_symbols_of_pattern) =
+      if matches!(self.ctx.output_options.format, OutputFormat::System) {
+        (
+          self.exported_names_for_assignment(expr),
+          self.exported_symbols_for_destructuring_assignment(expr),
+        )
+      } else {
+        (None, vec![])
+      };
+    let dynamic_import_rec_id = self.dynamic_import_record_id(expr);
+
     if let Some(call_expr) = expr.as_call_expression() {
       // Rewrite `require(...)` to `require_xxx(...)` or `(init_xxx(), __toCommonJS(xxx_exports))`
-      if let ast::Expression::Identifier(callee) = &call_expr.callee {
+      if let (
+        ast::Expression::Identifier(callee),
+        Some(ast::Argument::Expression(ast::Expression::StringLiteral(request))),
+      ) = (&call_expr.callee, call_expr.arguments.first())
+      {
         if callee.name == "require" && self.is_global_identifier_reference(callee) {
-          let rec_id = self.ctx.module.imports[&call_expr.span];
+          let rec_id =
+            self.ctx.module.imports[&ImportRecordKey::new(call_expr.span, &request.value)];
           let rec = &self.ctx.module.import_records[rec_id];
           if let ModuleId::Normal(importee_id) = rec.resolved_module {
             let importee = &self.ctx.modules[importee_id];
             let importee_linking_info = &self.ctx.linking_infos[importee.id];
-            let wrap_ref_name = self.canonical_name_for(importee_linking_info.wrapper_ref.unwrap());
+            let wrap_ref_expr = self.generate_finalized_expr_for_symbol_ref(
+              importee_linking_info.wrapper_ref.unwrap(),
+              false,
+            );
             if matches!(importee.exports_kind, ExportsKind::CommonJs) {
-              *expr = self.snippet.call_expr_expr(wrap_ref_name);
+              *expr = self.snippet.callee_call_expr_expr(wrap_ref_expr);
             } else {
-              let ns_name = self.canonical_name_for(importee.namespace_symbol);
-              let to_commonjs_ref_name = self.canonical_name_for_runtime("__toCommonJS");
               *expr = self.snippet.seq2_in_paren_expr(
-                self.snippet.call_expr_expr(wrap_ref_name),
-                self.snippet.call_expr_with_arg_expr(to_commonjs_ref_name, ns_name),
+                self.snippet.callee_call_expr_expr(wrap_ref_expr),
+                self.snippet.callee_call_expr_with_arg_expr(
+                  self.generate_finalized_expr_for_runtime_symbol("__toCommonJS"),
+                  self.generate_finalized_expr_for_symbol_ref(importee.namespace_symbol, false),
+                ),
               );
             }
           }
@@ -340,6 +559,16 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
 
     // visit children
     self.visit_expression_match(expr);
+
+    if let Some(exported_names) = exported_names {
+      self.wrap_assignment_with_exports_calls(expr, exported_names);
+    }
+    if !exported_symbols_of_pattern.is_empty() {
+      self.wrap_destructuring_assignment_with_exports_call(expr, &exported_symbols_of_pattern);
+    }
+    if let Some(rec_id) = dynamic_import_rec_id {
+      self.rewrite_dynamic_import(expr, rec_id);
+    }
   }
 
   fn visit_object_property(&mut self, prop: &mut ast::ObjectProperty<'ast>) {
@@ -419,19 +648,31 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
   fn visit_import_expression(&mut self, expr: &mut ast::ImportExpression<'ast>) {
     // Make sure the import expression is in correct form. If it's not, we should leave it as it is.
     match &mut expr.source {
-      ast::Expression::StringLiteral(str) if expr.arguments.len() == 0 => {
-        let rec_id = self.ctx.module.imports[&expr.span];
+      ast::Expression::StringLiteral(str)
+        if expr.arguments.len() == 0 && !self.ctx.output_options.inline_dynamic_imports =>
+      {
+        let rec_id = self.ctx.module.imports[&ImportRecordKey::new(expr.span, &str.value)];
         let rec = &self.ctx.module.import_records[rec_id];
         let importee_id = rec.resolved_module;
         match importee_id {
           ModuleId::Normal(importee_id) => {
-            let chunk_id = self.ctx.chunk_graph.module_to_chunk[importee_id]
+            let chunk_graph = self.ctx.chunk_graph;
+            let importer_chunk_id = chunk_graph.module_to_chunk[self.ctx.id]
               .expect("Normal module should belong to a chunk");
-            let chunk = &self.ctx.chunk_graph.chunks[chunk_id];
-            str.value = self.snippet.atom(&format!("./{}", chunk.file_name.as_ref().unwrap()));
+            let importee_chunk_id = chunk_graph.module_to_chunk[importee_id]
+              .expect("Normal module should belong to a chunk");
+            let import_path = chunk_graph.chunks[importer_chunk_id]
+              .import_path_for(&chunk_graph.chunks[importee_chunk_id]);
+            str.value = self.snippet.atom(&import_path);
           }
-          ModuleId::External(_) => {
-            // external module doesn't belong to any chunk, just keep this as it is
+          ModuleId::External(importee_id) => {
+            // external module doesn't belong to any chunk, it's imported by its resolved id unless the path is
+            // rewritten
+            let importer_chunk_id = self.ctx.chunk_graph.module_to_chunk[self.ctx.id]
+              .expect("Normal module should belong to a chunk");
+            let import_path = self.ctx.chunk_graph.chunks[importer_chunk_id]
+              .external_import_path(&self.ctx.external_modules[importee_id]);
+            str.value = self.snippet.atom(import_path);
           }
         }
       }
@@ -518,3 +759,55 @@ impl<'ast, 'me: 'ast> VisitMut<'ast> for Finalizer<'me, 'ast> {
     }
   }
 }
+
+/// Collect the identifiers that are assigned by the assignment target, like `a`, `b` and `c` in `[a, { b, c: c }]`.
+fn collect_assignment_target_identifiers<'a, 'ast>(
+  target: &'a ast::AssignmentTarget<'ast>,
+  id_refs: &mut Vec<&'a ast::IdentifierReference<'ast>>,
+) {
+  match target {
+    ast::AssignmentTarget::SimpleAssignmentTarget(
+      SimpleAssignmentTarget::AssignmentTargetIdentifier(id_ref),
+    ) => id_refs.push(id_ref),
+    ast::AssignmentTarget::SimpleAssignmentTarget(_) => {}
+    ast::AssignmentTarget::AssignmentTargetPattern(
+      ast::AssignmentTargetPattern::ArrayAssignmentTarget(array_target),
+    ) => {
+      array_target.elements.iter().flatten().for_each(|element| {
+        collect_maybe_default_assignment_target_identifiers(element, id_refs);
+      });
+      if let Some(rest) = &array_target.rest {
+        collect_assignment_target_identifiers(&rest.target, id_refs);
+      }
+    }
+    ast::AssignmentTarget::AssignmentTargetPattern(
+      ast::AssignmentTargetPattern::ObjectAssignmentTarget(object_target),
+    ) => {
+      object_target.properties.iter().for_each(|property| match property {
+        ast::AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(property) => {
+          id_refs.push(&property.binding);
+        }
+        ast::AssignmentTargetProperty::AssignmentTargetPropertyProperty(property) => {
+          collect_maybe_default_assignment_target_identifiers(&property.binding, id_refs);
+        }
+      });
+      if let Some(rest) = &object_target.rest {
+        collect_assignment_target_identifiers(&rest.target, id_refs);
+      }
+    }
+  }
+}
+
+fn collect_maybe_default_assignment_target_identifiers<'a, 'ast>(
+  target: &'a ast::AssignmentTargetMaybeDefault<'ast>,
+  id_refs: &mut Vec<&'a ast::IdentifierReference<'ast>>,
+) {
+  match target {
+    ast::AssignmentTargetMaybeDefault::AssignmentTarget(target) => {
+      collect_assignment_target_identifiers(target, id_refs);
+    }
+    ast::AssignmentTargetMaybeDefault::AssignmentTargetWithDefault(target) => {
+      collect_assignment_target_identifiers(&target.binding, id_refs);
+    }
+  }
+}
@@ -1,5 +1,5 @@
 use oxc::{
-  allocator::Allocator,
+  allocator::{self, Allocator},
   ast::ast::{self, IdentifierReference, Statement},
   span::{Atom, SPAN},
 };
@@ -12,6 +12,8 @@ pub use finalizer_context::FinalizerContext;
 use rolldown_rstr::Rstr;
 mod rename;
 
+use crate::OutputFormat;
+
 pub struct Finalizer<'me, 'ast> {
   pub alloc: &'ast Allocator,
   pub ctx: FinalizerContext<'me>,
@@ -36,11 +38,6 @@ where
     self.ctx.symbols.canonical_name_for(symbol, self.ctx.canonical_names)
   }
 
-  pub fn canonical_name_for_runtime(&self, name: &str) -> &Rstr {
-    let symbol = self.ctx.runtime.resolve_symbol(name);
-    self.canonical_name_for(symbol)
-  }
-
   fn should_remove_import_export_stmt(
     &self,
     stmt: &mut Statement<'ast>,
@@ -57,45 +54,92 @@ where
       }
       WrapKind::Cjs => {
         // Replace the statement with something like `var import_foo = __toESM(require_foo())`
-        let to_esm_fn_name = self.canonical_name_for_runtime("__toESM");
-        let wrapper_ref_name = self.canonical_name_for(importee_linking_info.wrapper_ref.unwrap());
         let binding_name_for_wrapper_call_ret = self.canonical_name_for(rec.namespace_ref);
         *stmt = self.snippet.var_decl_stmt(
           binding_name_for_wrapper_call_ret,
-          self.snippet.call_expr_with_arg_expr_expr(
-            to_esm_fn_name,
-            self.snippet.call_expr_expr(wrapper_ref_name),
+          self.snippet.callee_call_expr_with_arg_expr(
+            self.generate_finalized_expr_for_runtime_symbol("__toESM"),
+            self.snippet.callee_call_expr_expr(self.generate_finalized_expr_for_symbol_ref(
+              importee_linking_info.wrapper_ref.unwrap(),
+              false,
+            )),
           ),
         );
         return false;
       }
       // Replace the statement with something like `init_foo()`
       WrapKind::Esm => {
-        let wrapper_ref_name = self.canonical_name_for(importee_linking_info.wrapper_ref.unwrap());
-        *stmt = self.snippet.call_expr_stmt(wrapper_ref_name);
+        *stmt = self.snippet.expr_stmt(self.snippet.callee_call_expr_expr(
+          self.generate_finalized_expr_for_symbol_ref(
+            importee_linking_info.wrapper_ref.unwrap(),
+            false,
+          ),
+        ));
         return false;
       }
     }
     true
   }
 
-  fn generate_finalized_expr_for_symbol_ref(&self, symbol_ref: SymbolRef) -> ast::Expression<'ast> {
+  /// Return the chunk-level binding and the export alias if the symbol is declared in another chunk and has to
+  /// be accessed through the `require` call of that chunk. This only happens in CJS output.
+  pub fn binding_in_other_chunk_for(
+    &self,
+    canonical_ref: SymbolRef,
+  ) -> Option<(&'me Rstr, &'me Rstr)> {
+    if !matches!(self.ctx.output_options.format, OutputFormat::Cjs) {
+      return None;
+    }
+    let importee_chunk_id = self.ctx.symbols.get(canonical_ref).chunk_id?;
+    let chunk_id = self.ctx.chunk_graph.module_to_chunk[self.ctx.id]?;
+    if chunk_id == importee_chunk_id {
+      return None;
+    }
+    let chunk = &self.ctx.chunk_graph.chunks[chunk_id];
+    let importee_chunk = &self.ctx.chunk_graph.chunks[importee_chunk_id];
+    Some((
+      &chunk.require_binding_names_for_other_chunks[&importee_chunk_id],
+      &importee_chunk.exports_to_other_chunks[&canonical_ref],
+    ))
+  }
+
+  /// Generate the expression that refers to the symbol in the current module, such as
+  /// - `foo` for a symbol in the same chunk
+  /// - `ns.foo` for a symbol that is an alias of a property of a namespace
+  /// - `require_shared.foo` for a symbol from another chunk in CJS output
+  pub fn generate_finalized_expr_for_symbol_ref(
+    &self,
+    symbol_ref: SymbolRef,
+    is_callee: bool,
+  ) -> ast::Expression<'ast> {
     let canonical_ref = self.ctx.symbols.par_canonical_ref_for(symbol_ref);
     let symbol = self.ctx.symbols.get(canonical_ref);
 
-    if let Some(ns_alias) = &symbol.namespace_alias {
-      let canonical_ns_name = self.canonical_name_for(ns_alias.namespace_ref);
-      let prop_name = &ns_alias.property_name;
-      let access_expr =
-        self.snippet.literal_prop_access_member_expr_expr(canonical_ns_name, prop_name);
-
-      access_expr
+    let access_expr = if let Some(ns_alias) = &symbol.namespace_alias {
+      self.snippet.static_member_expr_expr(
+        self.generate_finalized_expr_for_symbol_ref(ns_alias.namespace_ref, false),
+        &ns_alias.property_name,
+      )
+    } else if let Some((binding, export_alias)) = self.binding_in_other_chunk_for(canonical_ref) {
+      self.snippet.literal_prop_access_member_expr_expr(binding, export_alias)
     } else {
       let canonical_name = self.canonical_name_for(canonical_ref);
-      self.snippet.id_ref_expr(canonical_name, SPAN)
+      return self.snippet.id_ref_expr(canonical_name, SPAN);
+    };
+
+    if is_callee {
+      // `foo()` might be transformed to `xxx.foo()`. To keep the semantic of callee's `this` binding,
+      // we need to wrap the transformed callee. Make it like `(0, xxx.foo)()`.
+      self.snippet.seq2_in_paren_expr(self.snippet.number_expr(0.0), access_expr)
+    } else {
+      access_expr
     }
   }
 
+  fn generate_finalized_expr_for_runtime_symbol(&self, name: &str) -> ast::Expression<'ast> {
+    self.generate_finalized_expr_for_symbol_ref(self.ctx.runtime.resolve_symbol(name), false)
+  }
+
   fn convert_decl_to_assignment(
     &self,
     decl: &mut ast::Declaration<'ast>,
@@ -163,6 +207,99 @@ where
     }
   }
 
+  /// Hoist all top-level "var", "class" and "function" declarations of `body` to `program`, and return the rest of the
+  /// statements, where "var" and "class" declarations are turned into assignments.
+  fn hoist_declarations(
+    &self,
+    program: &mut ast::Program<'ast>,
+    body: allocator::Vec<'ast, Statement<'ast>>,
+  ) -> allocator::Vec<'ast, Statement<'ast>> {
+    let mut fn_stmts = allocator::Vec::new_in(self.alloc);
+    let mut hoisted_names = vec![];
+    let mut rest_stmts = allocator::Vec::new_in(self.alloc);
+
+    body.into_iter().for_each(|mut stmt| match &mut stmt {
+      ast::Statement::Declaration(decl) => match decl {
+        ast::Declaration::VariableDeclaration(_) | ast::Declaration::ClassDeclaration(_) => {
+          if let Some(converted) = self.convert_decl_to_assignment(decl, &mut hoisted_names) {
+            rest_stmts.push(converted);
+          }
+        }
+        ast::Declaration::FunctionDeclaration(_) => {
+          fn_stmts.push(stmt);
+        }
+        ast::Declaration::UsingDeclaration(_) => unimplemented!(),
+        _ => {}
+      },
+      ast::Statement::ModuleDeclaration(_) => unreachable!(
+        "At this point, all module declarations should have been removed or transformed"
+      ),
+      _ => {
+        rest_stmts.push(stmt);
+      }
+    });
+    program.body.extend(fn_stmts);
+    if !hoisted_names.is_empty() {
+      let mut declarators = allocator::Vec::new_in(self.alloc);
+      declarators.reserve_exact(hoisted_names.len());
+      hoisted_names.into_iter().for_each(|var_name| {
+        declarators.push(ast::VariableDeclarator {
+          id: ast::BindingPattern {
+            kind: ast::BindingPatternKind::BindingIdentifier(
+              self.snippet.id(&var_name, SPAN).into_in(self.alloc),
+            ),
+            ..Dummy::dummy(self.alloc)
+          },
+          kind: ast::VariableDeclarationKind::Var,
+          ..Dummy::dummy(self.alloc)
+        });
+      });
+      program.body.push(ast::Statement::Declaration(ast::Declaration::VariableDeclaration(
+        ast::VariableDeclaration {
+          declarations: declarators,
+          kind: ast::VariableDeclarationKind::Var,
+          ..Dummy::dummy(self.alloc)
+        }
+        .into_in(self.alloc),
+      )));
+    }
+    rest_stmts
+  }
+
+  /// SystemJS output declares modules in the function passed to `System.register`, so that exported functions are
+  /// available to circular importers before the chunk is executed. Declarations stay at the top level, while the
+  /// rest of the module is moved into a function called by `execute`, like
+  ///
+  /// ```js
+  /// function foo() {}
+  /// var bar;
+  /// function execute_main() {
+  ///   bar = foo();
+  /// }
+  /// ```
+  fn move_statements_into_system_executor(&self, program: &mut ast::Program<'ast>) {
+    let old_body = program.body.take_in(self.alloc);
+    let stmts_inside_executor = self.hoist_declarations(program, old_body);
+    if stmts_inside_executor.is_empty() {
+      return;
+    }
+
+    let chunk_id = self.ctx.chunk_graph.module_to_chunk[self.ctx.id].expect("should be in a chunk");
+    let executor_name = &self.ctx.chunk_graph.chunks[chunk_id].system_executor_names[&self.ctx.id];
+    program.body.push(ast::Statement::Declaration(ast::Declaration::FunctionDeclaration(
+      ast::Function {
+        r#type: ast::FunctionType::FunctionDeclaration,
+        id: Some(self.snippet.id(executor_name, SPAN)),
+        body: Some(
+          ast::FunctionBody { statements: stmts_inside_executor, ..Dummy::dummy(self.alloc) }
+            .into_in(self.alloc),
+        ),
+        ..Dummy::dummy(self.alloc)
+      }
+      .into_in(self.alloc),
+    )));
+  }
+
   fn generate_namespace_variable_declaration(&self) -> Vec<ast::Statement<'ast>> {
     let ns_name = self.canonical_name_for(self.ctx.module.namespace_symbol);
     // construct `var ns_name = {}`
@@ -183,7 +320,7 @@ where
     self.ctx.linking_info.canonical_exports().for_each(|(export, resolved_export)| {
       // prop_name: () => returned
       let prop_name = export;
-      let returned = self.generate_finalized_expr_for_symbol_ref(resolved_export.symbol_ref);
+      let returned = self.generate_finalized_expr_for_symbol_ref(resolved_export.symbol_ref, false);
       arg_obj_expr.properties.push(ast::ObjectPropertyKind::ObjectProperty(
         ast::ObjectProperty {
           key: ast::PropertyKey::Identifier(
@@ -197,7 +334,8 @@ where
     });
 
     // construct `__export(ns_name, { prop_name: () => returned, ... })`
-    let mut export_call_expr = self.snippet.call_expr(self.canonical_name_for_runtime("__export"));
+    let mut export_call_expr =
+      self.snippet.callee_call_expr(self.generate_finalized_expr_for_runtime_symbol("__export"));
     export_call_expr
       .arguments
       .push(ast::Argument::Expression(self.snippet.id_ref_expr(ns_name, SPAN)));
@@ -27,21 +27,9 @@ where
     let canonical_ref = self.ctx.symbols.par_canonical_ref_for(symbol_ref);
     let symbol = self.ctx.symbols.get(canonical_ref);
 
-    if let Some(ns_alias) = &symbol.namespace_alias {
-      let canonical_ns_name = self.canonical_name_for(ns_alias.namespace_ref);
-      let prop_name = &ns_alias.property_name;
-      let access_expr =
-        self.snippet.literal_prop_access_member_expr_expr(canonical_ns_name, prop_name);
-
-      return Some(if is_callee {
-        // `foo()` might be transformed to `xxx.foo()`. To keep the semantic of callee's `this` binding,
-        // we need to wrap the transformed callee. Make it like `(0, xxx.foo)()`.
-        let wrapped_callee =
-          self.snippet.seq2_in_paren_expr(self.snippet.number_expr(0.0), access_expr);
-        wrapped_callee
-      } else {
-        access_expr
-      });
+    if symbol.namespace_alias.is_some() || self.binding_in_other_chunk_for(canonical_ref).is_some()
+    {
+      return Some(self.generate_finalized_expr_for_symbol_ref(canonical_ref, is_callee));
     }
 
     let canonical_name = self.canonical_name_for(canonical_ref);
@@ -6,7 +6,6 @@ mod chunk_graph;
 mod error;
 mod finalizer;
 mod module_loader;
-mod options;
 mod runtime;
 mod stages;
 mod types;
@@ -19,14 +18,14 @@ use rolldown_resolver::Resolver;
 pub(crate) type SharedResolver<T> = Arc<Resolver<T>>;
 
 pub use crate::{
-  bundler::Bundler,
-  bundler_builder::BundlerBuilder,
-  chunk::render_chunk::PreRenderedChunk,
-  options::{
-    file_name_template::FileNameTemplate,
-    input_options::{resolve_options::ResolveOptions, External, InputOptions},
-    output_options::{OutputFormat, OutputOptions, SourceMapType},
-    types::input_item::InputItem,
-  },
+  bundler::Bundler, bundler_builder::BundlerBuilder, chunk::render_chunk::PreRenderedChunk,
   types::rolldown_output::RolldownOutput,
 };
+
+// Options are defined in `rolldown_common`, so plugins could access them in the `options` and `output_options` hooks.
+pub use rolldown_common::{
+  AddonFn, AddonOutputOption, AdvancedChunksOptions, External, FileNameTemplate, InputItem,
+  InputOptions, ManualChunks, ManualChunksFn, MatchGroup, MatchGroupTest, MatchGroupTestFn,
+  OutputExports, OutputFormat, OutputOptions, PathsFn, PathsOutputOption, ResolveOptions,
+  SanitizeFileName, SanitizeFileNameFn, SourceMapType,
+};
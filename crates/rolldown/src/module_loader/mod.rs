@@ -16,4 +16,8 @@ pub enum Msg {
   NormalModuleDone(NormalModuleTaskResult),
   RuntimeNormalModuleDone(RuntimeNormalModuleTaskResult),
   Errors(BatchedErrors),
+  /// The `module_parsed` hook of a module finished.
+  ModuleParsedDone,
+  /// A module requested by plugins via `PluginContext::load`.
+  FetchModule(String),
 }
@@ -3,12 +3,13 @@ use std::sync::Arc;
 use index_vec::IndexVec;
 use rolldown_common::{
   EntryPoint, EntryPointKind, ExternalModule, FilePath, ImportKind, ImportRecordId, ModuleId,
-  NormalModule, NormalModuleId, ResourceId,
+  ModuleInfo, ModuleType, NormalModule, NormalModuleId, RawImportRecord, ResourceId,
+  SharedNormalizedInputOptions,
 };
 use rolldown_error::BuildError;
 use rolldown_fs::FileSystem;
 use rolldown_oxc_utils::OxcProgram;
-use rolldown_plugin::SharedPluginDriver;
+use rolldown_plugin::{HookResolveIdExtraOptions, SharedPluginDriver};
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use super::normal_module_task::NormalModuleTask;
@@ -17,11 +18,12 @@ use super::task_result::NormalModuleTaskResult;
 use super::Msg;
 use crate::module_loader::module_task_context::ModuleTaskCommonData;
 use crate::module_loader::runtime_normal_module_task::RuntimeNormalModuleTaskResult;
-use crate::options::normalized_input_options::SharedNormalizedInputOptions;
 use crate::runtime::RuntimeModuleBrief;
 use crate::types::module_table::{ExternalModuleVec, ModuleTable};
+use crate::types::normal_module_builder::NormalModuleBuilder;
 use crate::types::resolved_request_info::ResolvedRequestInfo;
 use crate::types::symbols::Symbols;
+use crate::utils::resolve_id::resolve_id;
 
 use crate::error::{BatchedErrors, BatchedResult};
 use crate::SharedResolver;
@@ -61,7 +63,7 @@ pub struct ModuleLoaderOutput {
   pub module_table: ModuleTable,
   pub ast_table: IndexVec<NormalModuleId, OxcProgram>,
   pub symbols: Symbols,
-  // Entries that user defined + dynamic import entries
+  // Entries that user defined + entries emitted by plugins + dynamic import entries
   pub entry_points: Vec<EntryPoint>,
   pub runtime: RuntimeModuleBrief,
   pub warnings: Vec<BuildError>,
@@ -76,6 +78,12 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
   ) -> Self {
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<Msg>();
 
+    let fetch_tx = tx.clone();
+    plugin_driver.module_infos().set_module_loader(Some(Box::new(move |id| {
+      // The loader might have finished when the request arrives. The request is dropped then.
+      let _ = fetch_tx.send(Msg::FetchModule(id));
+    })));
+
     let common_data = ModuleTaskCommonData {
       input_options: Arc::clone(&input_options),
       tx,
@@ -127,6 +135,75 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
     }
   }
 
+  fn module_info(
+    builder: &NormalModuleBuilder,
+    raw_import_records: &IndexVec<ImportRecordId, RawImportRecord>,
+    resolved_deps: &IndexVec<ImportRecordId, ResolvedRequestInfo>,
+    is_entry: bool,
+  ) -> ModuleInfo {
+    let ids_of = |is_dynamic: bool| {
+      raw_import_records
+        .iter()
+        .zip(resolved_deps.iter())
+        .filter(|(rec, _)| (rec.kind == ImportKind::DynamicImport) == is_dynamic)
+        .map(|(_, info)| info.path.path.to_string())
+        .collect()
+    };
+    ModuleInfo {
+      id: builder.path.as_ref().expect("Module path should be set").expect_file().to_string(),
+      is_entry,
+      imported_ids: ids_of(false),
+      dynamically_imported_ids: ids_of(true),
+    }
+  }
+
+  /// Load chunks emitted by plugins as extra entries. A chunk whose module is an entry already doesn't create a new one,
+  /// and a module that is imported dynamically as well is no longer a dynamic entry.
+  async fn load_emitted_chunks(
+    &mut self,
+    entry_points: &mut Vec<EntryPoint>,
+    user_defined_entry_ids: &mut FxHashSet<NormalModuleId>,
+    dynamic_import_entry_ids: &mut FxHashSet<NormalModuleId>,
+  ) -> BatchedResult<()> {
+    let file_emitter = Arc::clone(self.common_data.plugin_driver.file_emitter());
+    for (reference_id, chunk) in file_emitter.take_emitted_chunks() {
+      let info = self.resolve_emitted_chunk_entry(&chunk.id).await?;
+      file_emitter.set_chunk_module_id(reference_id, info.path.path.to_string());
+      let mut implicitly_loaded_after = Vec::with_capacity(chunk.implicitly_loaded_after.len());
+      for id in &chunk.implicitly_loaded_after {
+        let info = self.resolve_emitted_chunk_entry(id).await?;
+        implicitly_loaded_after.push(self.try_spawn_new_task(info).expect_normal());
+      }
+      let id = self.try_spawn_new_task(info).expect_normal();
+      if user_defined_entry_ids.insert(id) {
+        dynamic_import_entry_ids.remove(&id);
+        entry_points.push(EntryPoint {
+          name: chunk.name,
+          id,
+          kind: EntryPointKind::EmittedChunk { implicitly_loaded_after },
+        });
+      }
+    }
+    Ok(())
+  }
+
+  async fn resolve_emitted_chunk_entry(&self, id: &str) -> Result<ResolvedRequestInfo, BuildError> {
+    let info = resolve_id(
+      &self.common_data.resolver,
+      &self.common_data.plugin_driver,
+      id,
+      None,
+      HookResolveIdExtraOptions { is_entry: true, kind: ImportKind::Import },
+      None,
+      false,
+    )
+    .await?;
+    if info.is_external {
+      return Err(BuildError::entry_cannot_be_external(info.path.path.as_str()));
+    }
+    Ok(info)
+  }
+
   pub fn try_spawn_runtime_module_task(&mut self) -> NormalModuleId {
     *self.runtime_id.get_or_insert_with(|| {
       let id = self.intermediate_normal_modules.alloc_module_id(&mut self.symbols);
@@ -137,6 +214,21 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
     })
   }
 
+  /// The hook runs in its own task, so that the loader keeps going if it waits for other modules via
+  /// `PluginContext::load`.
+  fn spawn_module_parsed_hook(&mut self, module_info: ModuleInfo) {
+    self.remaining += 1;
+    let plugin_driver = Arc::clone(&self.common_data.plugin_driver);
+    let tx = self.common_data.tx.clone();
+    tokio::spawn(async move {
+      let msg = match plugin_driver.module_parsed(&module_info).await {
+        Ok(()) => Msg::ModuleParsedDone,
+        Err(err) => Msg::Errors(err.into()),
+      };
+      tx.send(msg).expect("Send should not fail");
+    });
+  }
+
   #[allow(clippy::too_many_lines)]
   pub async fn fetch_all_modules(
     mut self,
@@ -156,7 +248,7 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
       .ast_table
       .reserve(user_defined_entries.len() + 1 /* runtime */);
 
-    // Store the already consider as entry module
+    // Store the already consider as entry module, including modules of chunks emitted by plugins
     let mut user_defined_entry_ids = {
       let mut tmp = FxHashSet::default();
       tmp.reserve(user_defined_entries.len());
@@ -179,7 +271,20 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
 
     let mut runtime_brief: Option<RuntimeModuleBrief> = None;
 
-    while self.remaining > 0 {
+    loop {
+      if let Err(errs) = self
+        .load_emitted_chunks(
+          &mut entry_points,
+          &mut user_defined_entry_ids,
+          &mut dynamic_import_entry_ids,
+        )
+        .await
+      {
+        errors.extend(errs);
+      }
+      if self.remaining == 0 {
+        break;
+      }
       let Some(msg) = self.rx.recv().await else {
         break;
       };
@@ -195,6 +300,11 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
             ast,
           } = task_result;
           all_warnings.extend(warnings);
+          let is_user_defined_entry = user_defined_entry_ids.contains(&module_id);
+          let module_info =
+            Self::module_info(&builder, &raw_import_records, &resolved_deps, is_user_defined_entry);
+          self.common_data.plugin_driver.module_infos().insert(module_info.clone());
+          self.spawn_module_parsed_hook(module_info);
           let import_records = raw_import_records
             .into_iter()
             .zip(resolved_deps)
@@ -212,7 +322,7 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
             })
             .collect::<IndexVec<ImportRecordId, _>>();
           builder.import_records = Some(import_records);
-          builder.is_user_defined_entry = Some(user_defined_entry_ids.contains(&module_id));
+          builder.is_user_defined_entry = Some(is_user_defined_entry);
           self.intermediate_normal_modules.modules[module_id] = Some(builder.build());
           self.intermediate_normal_modules.ast_table[module_id] = Some(ast);
 
@@ -231,16 +341,36 @@ impl<T: FileSystem + 'static + Default> ModuleLoader<T> {
         Msg::Errors(errs) => {
           errors.extend(errs);
         }
+        Msg::ModuleParsedDone => {}
+        Msg::FetchModule(id) => {
+          let info = ResolvedRequestInfo {
+            path: id.clone().into(),
+            module_type: ModuleType::Unknown,
+            is_external: false,
+          };
+          if matches!(self.try_spawn_new_task(info), ModuleId::External(_)) {
+            self.common_data.plugin_driver.module_infos().mark_failed(id);
+          }
+          // It's a request rather than a finished task
+          continue;
+        }
       }
       self.remaining -= 1;
     }
 
+    self.common_data.plugin_driver.module_infos().set_module_loader(None);
+    self.common_data.plugin_driver.file_emitter().finish_loading();
+
     if !errors.is_empty() {
       return Err(errors);
     }
 
-    let modules: IndexVec<NormalModuleId, NormalModule> =
+    let mut modules: IndexVec<NormalModuleId, NormalModule> =
       self.intermediate_normal_modules.modules.into_iter().map(Option::unwrap).collect();
+    // Modules could be loaded before chunks emitted by plugins make them entries
+    for entry_point in &entry_points {
+      modules[entry_point.id].is_user_defined_entry = true;
+    }
 
     let ast_table: IndexVec<NormalModuleId, OxcProgram> =
       self.intermediate_normal_modules.ast_table.into_iter().map(Option::unwrap).collect();
@@ -1,7 +1,8 @@
 use rolldown_fs::FileSystem;
 use rolldown_plugin::SharedPluginDriver;
 
-use crate::{options::normalized_input_options::SharedNormalizedInputOptions, SharedResolver};
+use crate::SharedResolver;
+use rolldown_common::SharedNormalizedInputOptions;
 
 use super::Msg;
 
@@ -1,16 +1,16 @@
-use std::{path::Path, sync::Arc};
+use std::sync::Arc;
 
 use futures::future::join_all;
 use index_vec::IndexVec;
 use oxc::span::SourceType;
 use rolldown_common::{
-  AstScope, FilePath, ImportRecordId, ModuleType, NormalModuleId, RawImportRecord, ResolvedPath,
-  ResourceId, SymbolRef,
+  AstScope, ImportKind, ImportRecordId, ImportRecordKey, ModuleType, NormalModuleId,
+  RawImportRecord, ResolvedPath, ResourceId, SymbolRef,
 };
 use rolldown_fs::FileSystem;
 use rolldown_oxc_utils::{OxcCompiler, OxcProgram};
-use rolldown_plugin::{HookResolveIdExtraOptions, SharedPluginDriver};
-use rolldown_resolver::Resolver;
+use rolldown_plugin::{DynamicImportSpecifier, HookResolveIdExtraOptions};
+use rustc_hash::FxHashMap;
 use sugar_path::AsPath;
 
 use super::{module_task_context::ModuleTaskCommonData, Msg};
@@ -18,12 +18,17 @@ use crate::{
   ast_scanner::{AstScanner, ScanResult},
   error::{BatchedErrors, BatchedResult},
   module_loader::NormalModuleTaskResult,
-  options::normalized_input_options::SharedNormalizedInputOptions,
   types::{
     ast_symbols::AstSymbols, normal_module_builder::NormalModuleBuilder,
     resolved_request_info::ResolvedRequestInfo,
   },
-  utils::{load_source::load_source, resolve_id::resolve_id, transform_source::transform_source},
+  utils::{
+    dynamic_import_exprs::{collect_dynamic_import_exprs, rewrite_dynamic_import_exprs},
+    dynamic_import_vars::expand_dynamic_import_vars,
+    load_source::load_source,
+    resolve_id::{resolve_dynamic_import, resolve_id_with_external},
+    transform_source::transform_source,
+  },
 };
 pub struct NormalModuleTask<'task, T: FileSystem + Default> {
   ctx: &'task ModuleTaskCommonData<T>,
@@ -43,6 +48,7 @@ impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
   }
   pub async fn run(mut self) {
     if let Err(errs) = self.run_inner().await {
+      self.ctx.plugin_driver.module_infos().mark_failed(self.resolved_path.path.to_string());
       self.ctx.tx.send(Msg::Errors(errs)).expect("Send should not fail");
     }
   }
@@ -54,20 +60,47 @@ impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
     let mut warnings = vec![];
 
     // Run plugin load to get content first, if it is None using read fs as fallback.
-    let source =
-      load_source(&self.ctx.plugin_driver, &self.resolved_path, &self.ctx.fs, &mut sourcemap_chain)
-        .await?;
+    let source = load_source(
+      &self.ctx.plugin_driver,
+      &self.resolved_path,
+      self.module_type,
+      &self.ctx.fs,
+      &mut sourcemap_chain,
+    )
+    .await?;
 
     // Run plugin transform.
-    let source: Arc<str> =
-      transform_source(&self.ctx.plugin_driver, &self.resolved_path, source, &mut sourcemap_chain)
-        .await?
-        .into();
+    let source: Arc<str> = transform_source(
+      &self.ctx.plugin_driver,
+      &self.resolved_path,
+      self.module_type,
+      source,
+      &mut sourcemap_chain,
+    )
+    .await?
+    .into();
+
+    let source_type = self.determine_oxc_source_type();
+    let mut program = OxcCompiler::parse(Arc::clone(&source), source_type);
+
+    let resolved_dynamic_imports = self.resolve_dynamic_import_exprs(&source, &mut program).await?;
+    if self.ctx.input_options.dynamic_import_vars {
+      // Other non-literal dynamic imports are expanded with the files they might import
+      let (program, alloc) = program.program_mut_and_allocator();
+      expand_dynamic_import_vars(program, alloc, &self.ctx.fs, self.resolved_path.path.as_path());
+    }
 
-    let (ast, scope, scan_result, ast_symbol, namespace_symbol) = self.scan(&source);
+    let (ast, scope, scan_result, ast_symbol, namespace_symbol) =
+      self.scan(&source, program, source_type);
     tracing::trace!("scan {:?}", self.resolved_path);
 
-    let res = self.resolve_dependencies(&scan_result.import_records).await?;
+    let res = self
+      .resolve_dependencies(
+        &scan_result.import_records,
+        &scan_result.imports,
+        resolved_dynamic_imports,
+      )
+      .await?;
 
     let ScanResult {
       named_imports,
@@ -120,33 +153,86 @@ impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
     Ok(())
   }
 
-  fn scan(&self, source: &Arc<str>) -> (OxcProgram, AstScope, ScanResult, AstSymbols, SymbolRef) {
-    fn determine_oxc_source_type(path: impl AsRef<Path>, ty: ModuleType) -> SourceType {
-      // Determine oxc source type for parsing
-      let mut default = SourceType::default().with_module(true);
-      // Rolldown considers module as esm by default.
-      debug_assert!(default.is_module());
-      debug_assert!(default.is_javascript());
-      debug_assert!(!default.is_jsx());
-      let extension = path.as_ref().extension().and_then(std::ffi::OsStr::to_str);
-      default = match ty {
-        ModuleType::CJS | ModuleType::CjsPackageJson => default.with_script(true),
+  fn determine_oxc_source_type(&self) -> SourceType {
+    // Determine oxc source type for parsing
+    let mut default = SourceType::default().with_module(true);
+    // Rolldown considers module as esm by default.
+    debug_assert!(default.is_module());
+    debug_assert!(default.is_javascript());
+    debug_assert!(!default.is_jsx());
+    let extension = self.resolved_path.path.as_path().extension().and_then(std::ffi::OsStr::to_str);
+    default = match self.module_type {
+      ModuleType::CJS | ModuleType::CjsPackageJson => default.with_script(true),
+      _ => default,
+    };
+    if let Some(ext) = extension {
+      default = match ext {
+        "cjs" => default.with_script(true),
+        "jsx" => default.with_jsx(true),
         _ => default,
       };
-      if let Some(ext) = extension {
-        default = match ext {
-          "cjs" => default.with_script(true),
-          "jsx" => default.with_jsx(true),
-          _ => default,
-        };
+    };
+    default
+  }
+
+  /// Dynamic imports with non-literal specifiers are only bundled if plugins resolve them. Resolved ones are
+  /// rewritten to string literals of the resolved ids before the program is scanned. Returns the resolved
+  /// requests by the keys of the import records they become, so that literal imports of the same ids are still
+  /// resolved as usual.
+  async fn resolve_dynamic_import_exprs(
+    &self,
+    source: &str,
+    program: &mut OxcProgram,
+  ) -> BatchedResult<FxHashMap<ImportRecordKey, ResolvedRequestInfo>> {
+    let spans = collect_dynamic_import_exprs(program.program());
+    if spans.is_empty() {
+      return Ok(FxHashMap::default());
+    }
+
+    let jobs = spans.into_iter().map(|(span, source_span)| async move {
+      let specifier = DynamicImportSpecifier::Expression {
+        source_text: source_span.source_text(source),
+        span: source_span,
       };
-      default
+      resolve_dynamic_import(
+        &self.ctx.input_options,
+        &self.ctx.plugin_driver,
+        specifier,
+        &self.resolved_path.path,
+      )
+      .await
+      .map(|info| info.map(|info| (span, info)))
+    });
+
+    let mut errors = BatchedErrors::default();
+    let mut resolved_ids = FxHashMap::default();
+    let mut resolved_requests = FxHashMap::default();
+    for ret in join_all(jobs).await {
+      match ret {
+        Ok(Some((span, info))) => {
+          let id = info.path.path.to_string();
+          resolved_requests.insert(ImportRecordKey::new(span, &id), info);
+          resolved_ids.insert(span, id);
+        }
+        Ok(None) => {}
+        Err(e) => errors.push(e),
+      }
+    }
+    if !errors.is_empty() {
+      return Err(errors);
     }
 
-    let source_type =
-      determine_oxc_source_type(self.resolved_path.path.as_path(), self.module_type);
-    let mut program = OxcCompiler::parse(Arc::clone(source), source_type);
+    let (program, alloc) = program.program_mut_and_allocator();
+    rewrite_dynamic_import_exprs(program, alloc, &resolved_ids);
+    Ok(resolved_requests)
+  }
 
+  fn scan(
+    &self,
+    source: &Arc<str>,
+    mut program: OxcProgram,
+    source_type: SourceType,
+  ) -> (OxcProgram, AstScope, ScanResult, AstSymbols, SymbolRef) {
     let semantic = program.make_semantic(source_type);
     let (mut symbol_table, scope) = semantic.into_symbol_table_and_scope_tree();
     let ast_scope = AstScope::new(
@@ -172,45 +258,21 @@ impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
     (program, ast_scope, scan_result, symbol_for_module, namespace_symbol)
   }
 
-  #[allow(clippy::option_if_let_else)]
-  pub(crate) async fn resolve_id<F: FileSystem + Default>(
-    input_options: &SharedNormalizedInputOptions,
-    resolver: &Resolver<F>,
-    plugin_driver: &SharedPluginDriver,
-    importer: &FilePath,
-    specifier: &str,
-    options: HookResolveIdExtraOptions,
-  ) -> BatchedResult<ResolvedRequestInfo> {
-    // Check external with unresolved path
-    if input_options.external.call(specifier.to_string(), Some(importer.to_string()), false).await?
-    {
-      return Ok(ResolvedRequestInfo {
-        path: specifier.to_string().into(),
-        module_type: ModuleType::Unknown,
-        is_external: true,
-      });
-    }
-
-    let mut info =
-      resolve_id(resolver, plugin_driver, specifier, Some(importer), options, false).await?;
-
-    if !info.is_external {
-      // Check external with resolved path
-      info.is_external = input_options
-        .external
-        .call(specifier.to_string(), Some(importer.to_string()), true)
-        .await?;
-    }
-    Ok(info)
-  }
-
   #[tracing::instrument(skip_all)]
   async fn resolve_dependencies(
     &mut self,
     dependencies: &IndexVec<ImportRecordId, RawImportRecord>,
+    imports: &FxHashMap<ImportRecordKey, ImportRecordId>,
+    resolved_dynamic_imports: FxHashMap<ImportRecordKey, ResolvedRequestInfo>,
   ) -> BatchedResult<IndexVec<ImportRecordId, ResolvedRequestInfo>> {
+    // Dynamic imports rewritten from non-literal specifiers are already resolved
+    let mut resolved_dynamic_imports = resolved_dynamic_imports
+      .into_iter()
+      .filter_map(|(key, info)| imports.get(&key).map(|idx| (*idx, info)))
+      .collect::<FxHashMap<_, _>>();
     let jobs = dependencies.iter_enumerated().map(|(idx, item)| {
       let specifier = item.module_request.clone();
+      let resolved_dynamic_import = resolved_dynamic_imports.remove(&idx);
       let input_options = Arc::clone(&self.ctx.input_options);
       // FIXME(hyf0): should not use `Arc<Resolver>` here
       let resolver = Arc::clone(&self.ctx.resolver);
@@ -219,16 +281,34 @@ impl<'task, T: FileSystem + Default + 'static> NormalModuleTask<'task, T> {
       let kind = item.kind;
       // let on_warn = self.input_options.on_warn.clone();
       tokio::spawn(async move {
-        Self::resolve_id(
+        if let Some(info) = resolved_dynamic_import {
+          return Ok((idx, info));
+        }
+        if kind == ImportKind::DynamicImport {
+          if let Some(info) = resolve_dynamic_import(
+            &input_options,
+            &plugin_driver,
+            DynamicImportSpecifier::Str(&specifier),
+            &importer.path,
+          )
+          .await
+          .map_err(BatchedErrors::from)?
+          {
+            return Ok((idx, info));
+          }
+        }
+        resolve_id_with_external(
           &input_options,
           &resolver,
           &plugin_driver,
-          &importer.path,
           &specifier,
+          Some(&importer.path),
           HookResolveIdExtraOptions { is_entry: false, kind },
+          None,
         )
         .await
         .map(|id| (idx, id))
+        .map_err(BatchedErrors::from)
       })
     });
 
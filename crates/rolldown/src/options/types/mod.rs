@@ -1 +0,0 @@
-pub mod input_item;
@@ -0,0 +1,126 @@
+use index_vec::IndexVec;
+use rolldown_common::NormalModuleId;
+use rolldown_error::BuildError;
+use rolldown_utils::BitSet;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::BundleStage;
+
+impl<'a> BundleStage<'a> {
+  /// Returns indices of groups that each module passes the test of, sorted by priority from high to low. Entry modules
+  /// and modules in manual chunks are not considered.
+  pub async fn match_advanced_chunk_groups(
+    &self,
+    module_to_manual_chunk: &FxHashMap<NormalModuleId, String>,
+  ) -> Result<FxHashMap<NormalModuleId, Vec<usize>>, BuildError> {
+    let mut matched_groups = FxHashMap::default();
+    let Some(options) = &self.output_options.advanced_chunks else {
+      return Ok(matched_groups);
+    };
+
+    let mut group_indices = (0..options.groups.len()).collect::<Vec<_>>();
+    // The sort is stable, so groups with the same priority are kept in the order of declaration
+    group_indices.sort_by_key(|idx| std::cmp::Reverse(options.groups[*idx].priority.unwrap_or(0)));
+
+    let entry_modules =
+      self.link_output.entries.iter().map(|entry| entry.id).collect::<FxHashSet<_>>();
+    let runtime_id = self.link_output.runtime.id();
+
+    for module in &self.link_output.module_table.normal_modules {
+      if !module.is_included
+        || module.id == runtime_id
+        || entry_modules.contains(&module.id)
+        || module_to_manual_chunk.contains_key(&module.id)
+      {
+        continue;
+      }
+      let module_id = module.resource_id.expect_file().as_str();
+      let mut groups = vec![];
+      for idx in &group_indices {
+        let passed = match &options.groups[*idx].test {
+          Some(test) => test.call(module_id).await?,
+          None => true,
+        };
+        if passed {
+          groups.push(*idx);
+        }
+      }
+      if !groups.is_empty() {
+        matched_groups.insert(module.id, groups);
+      }
+    }
+
+    Ok(matched_groups)
+  }
+
+  /// Returns the name of the chunk that each module in groups belongs to. Groups smaller than `min_size` are dropped
+  /// and groups larger than `max_size` are split into `name`, `name-1`, `name-2`... A split part can't be named like
+  /// another group or a manual chunk, since chunks of the same name are merged.
+  pub fn assign_advanced_chunks(
+    &self,
+    matched_groups: &FxHashMap<NormalModuleId, Vec<usize>>,
+    module_to_bits: &IndexVec<NormalModuleId, BitSet>,
+    module_to_manual_chunk: &FxHashMap<NormalModuleId, String>,
+  ) -> Result<FxHashMap<NormalModuleId, String>, BuildError> {
+    let mut module_to_group_chunk = FxHashMap::default();
+    let Some(options) = &self.output_options.advanced_chunks else {
+      return Ok(module_to_group_chunk);
+    };
+    let modules = &self.link_output.module_table.normal_modules;
+
+    let mut module_ids = matched_groups.keys().copied().collect::<Vec<_>>();
+    module_ids.sort_by_key(|id| modules[*id].exec_order);
+
+    let mut modules_by_group = vec![vec![]; options.groups.len()];
+    for module_id in module_ids {
+      // The number of entries that the module is reachable from
+      let share_count = module_to_bits[module_id].count_ones();
+      let group_idx = matched_groups[&module_id].iter().copied().find(|idx| {
+        let min_share_count =
+          options.groups[*idx].min_share_count.or(options.min_share_count).unwrap_or(1);
+        share_count >= min_share_count
+      });
+      if let Some(group_idx) = group_idx {
+        modules_by_group[group_idx].push(module_id);
+      }
+    }
+
+    let explicit_names = options
+      .groups
+      .iter()
+      .map(|group| group.name.as_str())
+      .chain(module_to_manual_chunk.values().map(String::as_str))
+      .collect::<FxHashSet<_>>();
+    for (group, module_ids) in options.groups.iter().zip(modules_by_group) {
+      let size_of = |id: NormalModuleId| modules[id].source.len();
+      let total_size = module_ids.iter().copied().map(size_of).sum::<usize>();
+      if module_ids.is_empty() || total_size < group.min_size.or(options.min_size).unwrap_or(0) {
+        continue;
+      }
+
+      let max_size = group.max_size.or(options.max_size).unwrap_or(usize::MAX);
+      let mut part = 0;
+      let mut part_size = 0;
+      for module_id in module_ids {
+        let size = size_of(module_id);
+        if part_size > 0 && part_size + size > max_size {
+          part += 1;
+          part_size = 0;
+        }
+        part_size += size;
+        let name = if part == 0 {
+          group.name.clone()
+        } else {
+          let name = format!("{}-{part}", group.name);
+          if explicit_names.contains(name.as_str()) {
+            return Err(BuildError::advanced_chunk_name_conflict(&group.name, name));
+          }
+          name
+        };
+        module_to_group_chunk.insert(module_id, name);
+      }
+    }
+
+    Ok(module_to_group_chunk)
+  }
+}
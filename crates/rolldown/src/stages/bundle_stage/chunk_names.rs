@@ -0,0 +1,92 @@
+use index_vec::IndexVec;
+use rolldown_common::{ChunkId, ChunkKind, FileNameTemplate};
+use rolldown_error::BuildError;
+use rolldown_utils::xxhash::xxhash_base64_url;
+use rustc_hash::FxHashSet;
+
+use crate::{chunk::Chunk, chunk_graph::ChunkGraph};
+
+use super::{emitted_assets::make_unique_file_name, BundleStage};
+
+impl<'a> BundleStage<'a> {
+  /// Names of chunks, which are rendered into the `[name]` pattern of file names. Names are sanitized by
+  /// `sanitize_file_name`, but not deduplicated, since only the rendered file names need to be unique.
+  pub async fn generate_chunk_names(
+    &self,
+    chunk_graph: &ChunkGraph,
+  ) -> Result<IndexVec<ChunkId, String>, BuildError> {
+    let modules = &self.link_output.module_table.normal_modules;
+    let runtime_id = self.link_output.runtime.id();
+
+    let mut names = IndexVec::with_capacity(chunk_graph.chunks.len());
+    for chunk in &chunk_graph.chunks {
+      if self.has_standalone_runtime_chunk() && chunk.modules.first().copied() == Some(runtime_id) {
+        names.push("$runtime$".to_string());
+        continue;
+      }
+      let name = chunk.name.clone().unwrap_or_else(|| {
+        // Only dynamic entries and chunks emitted by plugins could be entries without names
+        let module_id = if let ChunkKind::EntryPoint { module: entry_module_id, .. } = &chunk.kind {
+          *entry_module_id
+        } else {
+          // TODO: we currently use the first executed module to calculate the chunk name for common chunks
+          // This is not perfect, should investigate more to find a better solution
+          chunk.modules.first().copied().unwrap()
+        };
+        modules[module_id].resource_id.expect_file().unique(&self.input_options.cwd)
+      });
+      names.push(self.output_options.sanitize_file_name.call(&name).await?);
+    }
+
+    Ok(names)
+  }
+
+  /// Render the file name of a chunk. If the file name is taken already, a hash of the modules in the chunk is
+  /// appended to its name, like `lib-a1B2c3D4.js`, so the file name doesn't change unless the chunk itself changes.
+  /// File names are compared in lower case for case-insensitive file systems.
+  pub fn render_unique_chunk_file_name(
+    &self,
+    chunk: &Chunk,
+    name: &str,
+    template: &FileNameTemplate,
+    render: impl Fn(&str) -> String,
+    used_file_names: &mut FxHashSet<String>,
+  ) -> String {
+    let mut file_name = render(name);
+    if used_file_names.contains(&file_name.to_lowercase()) {
+      file_name = if template.has_name_pattern() {
+        let modules = &self.link_output.module_table.normal_modules;
+        let module_ids = chunk
+          .modules
+          .iter()
+          .map(|id| modules[*id].resource_id.expect_file().relative_path(&self.input_options.cwd))
+          .map(|path| path.to_string_lossy().replace('\\', "/"))
+          .collect::<Vec<_>>()
+          .join("\n");
+        let hash = xxhash_base64_url(module_ids.as_bytes());
+        let mut unique_file_name = render(&format!("{name}-{}", &hash[..8]));
+        // Chunks never contain the same modules, so this is only for names picked by users
+        let mut index = 1;
+        while used_file_names.contains(&unique_file_name.to_lowercase()) {
+          unique_file_name = render(&format!("{name}-{}-{index}", &hash[..8]));
+          index += 1;
+        }
+        unique_file_name
+      } else {
+        // Names can't make file names unique, like `bundle.js` for all chunks
+        make_unique_file_name(file_name, used_file_names)
+      };
+    }
+    used_file_names.insert(file_name.to_lowercase());
+    file_name
+  }
+}
+
+/// Chunks in the order they claim file names. User-defined entries go first, so they are never renamed.
+pub fn chunk_ids_by_naming_priority(chunk_graph: &ChunkGraph) -> Vec<ChunkId> {
+  let mut chunk_ids = chunk_graph.chunks.indices().collect::<Vec<_>>();
+  chunk_ids.sort_by_key(|id| {
+    !matches!(chunk_graph.chunks[*id].kind, ChunkKind::EntryPoint { is_user_defined: true, .. })
+  });
+  chunk_ids
+}
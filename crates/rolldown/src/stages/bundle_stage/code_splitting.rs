@@ -1,17 +1,17 @@
 use std::hash::BuildHasherDefault;
 
 use index_vec::IndexVec;
-use rolldown_common::{ChunkId, ChunkKind, ImportKind, ModuleId, NormalModuleId};
+use rolldown_common::{ChunkId, ChunkKind, EntryPointKind, ImportKind, ModuleId, NormalModuleId};
+use rolldown_error::BuildError;
 use rolldown_utils::BitSet;
 use rustc_hash::FxHashMap;
 
 use crate::{
   chunk::{Chunk, ChunksVec},
   chunk_graph::ChunkGraph,
-  utils::is_in_rust_test_mode,
 };
 
-use super::BundleStage;
+use super::{preserve_modules::preserved_module_chunk_name, BundleStage};
 
 impl<'a> BundleStage<'a> {
   fn determine_reachable_modules_for_entry(
@@ -34,8 +34,8 @@ impl<'a> BundleStage<'a> {
     module.import_records.iter().for_each(|rec| {
       if let ModuleId::Normal(importee_id) = rec.resolved_module {
         // Module imported dynamically will be considered as an entry,
-        // so we don't need to include it in this chunk
-        if rec.kind != ImportKind::DynamicImport {
+        // so we don't need to include it in this chunk, unless it's inlined
+        if rec.kind != ImportKind::DynamicImport || self.output_options.inline_dynamic_imports {
           self.determine_reachable_modules_for_entry(importee_id, entry_index, module_to_bits);
         }
       }
@@ -56,12 +56,59 @@ impl<'a> BundleStage<'a> {
     });
   }
 
-  pub fn generate_chunks(&self) -> ChunkGraph {
+  /// Modules reachable from a chunk emitted with `implicitly_loaded_after` are loaded already if all of those entries
+  /// reach them. They are left to the chunks of those entries, instead of being duplicated into shared chunks.
+  /// Modules in `implicitly_loaded_after` need to be entries, otherwise nothing guarantees they are loaded before.
+  fn exclude_modules_loaded_before_emitted_chunks(
+    &self,
+    module_to_bits: &mut IndexVec<NormalModuleId, BitSet>,
+  ) -> Result<(), BuildError> {
+    let modules = &self.link_output.module_table.normal_modules;
+    let entries = &self.link_output.entries;
+    for (entry_index, entry_point) in entries.iter().enumerate() {
+      let EntryPointKind::EmittedChunk { implicitly_loaded_after } = &entry_point.kind else {
+        continue;
+      };
+      let loaded_before_bits = implicitly_loaded_after
+        .iter()
+        .map(|module_id| {
+          let index = entries.iter().position(|entry| entry.id == *module_id).ok_or_else(|| {
+            BuildError::implicit_dependant_not_entry(
+              modules[*module_id].resource_id.expect_file().as_str(),
+              modules[entry_point.id].resource_id.expect_file().as_str(),
+            )
+          })?;
+          Ok(u32::try_from(index).expect("Too many entries, u32 overflowed."))
+        })
+        .collect::<Result<Vec<_>, BuildError>>()?;
+      if loaded_before_bits.is_empty() {
+        continue;
+      }
+      let bit = u32::try_from(entry_index).expect("Too many entries, u32 overflowed.");
+      for (module_id, bits) in module_to_bits.iter_mut_enumerated() {
+        if module_id != entry_point.id
+          && bits.has_bit(bit)
+          && loaded_before_bits.iter().all(|loaded_before_bit| bits.has_bit(*loaded_before_bit))
+        {
+          bits.clear_bit(bit);
+        }
+      }
+    }
+    Ok(())
+  }
+
+  #[allow(clippy::too_many_lines)]
+  pub fn generate_chunks(
+    &self,
+    module_to_manual_chunk: &FxHashMap<NormalModuleId, String>,
+    matched_advanced_chunk_groups: &FxHashMap<NormalModuleId, Vec<usize>>,
+  ) -> Result<ChunkGraph, BuildError> {
     let entries_len: u32 =
       self.link_output.entries.len().try_into().expect("Too many entries, u32 overflowed.");
     // If we are in test environment, to make the runtime module always fall into a standalone chunk,
     // we create a facade entry point for it.
-    let entries_len = if is_in_rust_test_mode() { entries_len + 1 } else { entries_len };
+    let entries_len =
+      if self.has_standalone_runtime_chunk() { entries_len + 1 } else { entries_len };
 
     let mut module_to_bits = index_vec::index_vec![BitSet::new(entries_len); self.link_output.module_table.normal_modules.len()];
     let mut bits_to_chunk = FxHashMap::with_capacity_and_hasher(
@@ -69,6 +116,9 @@ impl<'a> BundleStage<'a> {
       BuildHasherDefault::default(),
     );
     let mut chunks = ChunksVec::with_capacity(self.link_output.entries.len());
+    let preserve_modules_root =
+      self.output_options.preserve_modules.then(|| self.preserve_modules_root());
+    let mut entry_module_to_chunk = FxHashMap::default();
 
     // Create chunk for each static and dynamic entry
     for (entry_index, entry_point) in self.link_output.entries.iter().enumerate() {
@@ -76,8 +126,12 @@ impl<'a> BundleStage<'a> {
       let mut bits = BitSet::new(entries_len);
       bits.set_bit(count);
       let module = &self.link_output.module_table.normal_modules[entry_point.id];
+      let name = match &preserve_modules_root {
+        Some(root) => Some(preserved_module_chunk_name(module, root)),
+        None => entry_point.name.clone(),
+      };
       let chunk = chunks.push(Chunk::new(
-        entry_point.name.clone(),
+        name,
         bits.clone(),
         vec![],
         ChunkKind::EntryPoint {
@@ -87,9 +141,10 @@ impl<'a> BundleStage<'a> {
         },
       ));
       bits_to_chunk.insert(bits, chunk);
+      entry_module_to_chunk.entry(entry_point.id).or_insert(chunk);
     }
 
-    if is_in_rust_test_mode() {
+    if self.has_standalone_runtime_chunk() {
       self.determine_reachable_modules_for_entry(
         self.link_output.runtime.id(),
         entries_len - 1,
@@ -106,11 +161,23 @@ impl<'a> BundleStage<'a> {
       );
     });
 
+    self.exclude_modules_loaded_before_emitted_chunks(&mut module_to_bits)?;
+
     let mut module_to_chunk: IndexVec<NormalModuleId, Option<ChunkId>> = index_vec::index_vec![
       None;
       self.link_output.module_table.normal_modules.len()
     ];
 
+    // Modules in manual chunks and advanced chunk groups are grouped by names instead of bits
+    let mut module_to_named_chunk = self.assign_advanced_chunks(
+      matched_advanced_chunk_groups,
+      &module_to_bits,
+      module_to_manual_chunk,
+    )?;
+    module_to_named_chunk
+      .extend(module_to_manual_chunk.iter().map(|(id, name)| (*id, name.clone())));
+    let mut named_chunks: FxHashMap<&str, ChunkId> = FxHashMap::default();
+
     // 1. Assign modules to corresponding chunks
     // 2. Create shared chunks to store modules that belong to multiple chunks.
     for normal_module in &self.link_output.module_table.normal_modules {
@@ -123,6 +190,39 @@ impl<'a> BundleStage<'a> {
         !bits.is_empty(),
         "Empty bits means the module is not reachable, so it should bail out with `is_included: false`"
       );
+
+      if let Some(root) = &preserve_modules_root {
+        // Every module gets its own chunk. Entry modules already have one.
+        let chunk_id = match entry_module_to_chunk.get(&normal_module.id) {
+          Some(chunk_id) => *chunk_id,
+          None => chunks.push(Chunk::new(
+            Some(preserved_module_chunk_name(normal_module, root)),
+            BitSet::new(entries_len),
+            vec![],
+            ChunkKind::Common,
+          )),
+        };
+        // Entry modules imported by other entries need to be loaded by them as well
+        chunks[chunk_id].bits.union(bits);
+        chunks[chunk_id].modules.push(normal_module.id);
+        module_to_chunk[normal_module.id] = Some(chunk_id);
+        continue;
+      }
+      if let Some(name) = module_to_named_chunk.get(&normal_module.id) {
+        let chunk_id = *named_chunks.entry(name).or_insert_with(|| {
+          chunks.push(Chunk::new(
+            Some(name.clone()),
+            BitSet::new(entries_len),
+            vec![],
+            ChunkKind::Common,
+          ))
+        });
+        // The named chunk is reachable from all entries that reach any of its modules
+        chunks[chunk_id].bits.union(bits);
+        chunks[chunk_id].modules.push(normal_module.id);
+        module_to_chunk[normal_module.id] = Some(chunk_id);
+        continue;
+      }
       if let Some(chunk_id) = bits_to_chunk.get(bits).copied() {
         chunks[chunk_id].modules.push(normal_module.id);
         module_to_chunk[normal_module.id] = Some(chunk_id);
@@ -134,6 +234,8 @@ impl<'a> BundleStage<'a> {
       }
     }
 
+    let mut chunks = self.merge_small_chunks(chunks, &mut module_to_chunk);
+
     // Sort modules in each chunk by execution order
     chunks.iter_mut().for_each(|chunk| {
       chunk.modules.sort_by_key(|module_id| {
@@ -143,6 +245,6 @@ impl<'a> BundleStage<'a> {
 
     tracing::trace!("Generated chunks: {:#?}", chunks);
 
-    ChunkGraph { chunks, module_to_chunk }
+    Ok(ChunkGraph { chunks, module_to_chunk })
   }
 }
@@ -111,6 +111,49 @@ impl<'a> BundleStage<'a> {
     tracing::info!("collect_potential_chunk_imports end");
   }
 
+  /// Assign aliases to symbols that chunks export to other chunks, avoiding collisions among all aliases.
+  fn generate_cross_chunk_exports(
+    &self,
+    chunk_graph: &mut ChunkGraph,
+    chunk_meta_exports_vec: &ChunkMetaExports,
+  ) {
+    let mut name_count = FxHashMap::default();
+    for (chunk_id, chunk) in chunk_graph.chunks.iter_mut_enumerated() {
+      // Entry chunks export symbols under the names of their entry modules already, which other chunks could use
+      let entry_exports = match chunk.kind {
+        ChunkKind::EntryPoint { module, .. } => self.link_output.metas[module]
+          .canonical_exports()
+          .map(|(name, export)| {
+            (self.link_output.symbols.par_canonical_ref_for(export.symbol_ref), name.clone())
+          })
+          .collect::<FxHashMap<_, _>>(),
+        ChunkKind::Common => FxHashMap::default(),
+      };
+      for export in chunk_meta_exports_vec[chunk_id].iter().copied() {
+        if let Some(exported_name) = entry_exports.get(&export) {
+          chunk.exports_to_other_chunks.insert(export, exported_name.clone());
+          continue;
+        }
+        let original_name: rolldown_rstr::Rstr =
+          self.link_output.symbols.get_original_name(export).to_rstr();
+        let alias = loop {
+          let key: Cow<'_, Rstr> = Cow::Owned(original_name.clone());
+          let count = name_count.entry(key).or_insert(0u32);
+          let alias: Rstr = if *count == 0 {
+            original_name.clone()
+          } else {
+            format!("{original_name}${count}").into()
+          };
+          *count += 1;
+          if !entry_exports.values().any(|exported_name| exported_name == &alias) {
+            break alias;
+          }
+        };
+        chunk.exports_to_other_chunks.insert(export, alias);
+      }
+    }
+  }
+
   pub fn compute_cross_chunk_links(&mut self, chunk_graph: &mut ChunkGraph) {
     let mut chunk_meta_imports_vec: ChunkMetaImports =
       index_vec![FxHashSet::<SymbolRef>::default(); chunk_graph.chunks.len()];
@@ -166,9 +209,9 @@ impl<'a> BundleStage<'a> {
           .filter(|(_, importee_chunk)| importee_chunk.bits.has_bit(*importer_chunk_bit))
           .filter(|(_, importee_chunk)| {
             // If we are in test environment, to get a cleaner output in snapshot, no need to import
-            // the runtime chunk as it for sure has no side effects.
-            is_in_rust_test_mode()
-              && importee_chunk.modules.first().copied() != Some(self.link_output.runtime.id())
+            // the runtime chunk as it for sure has no side effects. Other chunks are always imported.
+            !is_in_rust_test_mode()
+              || importee_chunk.modules.first().copied() != Some(self.link_output.runtime.id())
           })
           .for_each(|(importee_chunk_id, _)| {
             let imports_from_other_chunks = &mut imports_from_other_chunks_vec[chunk_id];
@@ -181,22 +224,7 @@ impl<'a> BundleStage<'a> {
     // Generate cross-chunk exports. These must be computed before cross-chunk
     // imports because of export alias renaming, which must consider all export
     // aliases simultaneously to avoid collisions.
-    let mut name_count = FxHashMap::default();
-    for (chunk_id, chunk) in chunk_graph.chunks.iter_mut_enumerated() {
-      for export in chunk_meta_exports_vec[chunk_id].iter().copied() {
-        let original_name: rolldown_rstr::Rstr =
-          self.link_output.symbols.get_original_name(export).to_rstr();
-        let key: Cow<'_, Rstr> = Cow::Owned(original_name.clone());
-        let count = name_count.entry(key).or_insert(0u32);
-        let alias = if *count == 0 {
-          original_name.clone()
-        } else {
-          format!("{original_name}${count}").into()
-        };
-        chunk.exports_to_other_chunks.insert(export, alias.clone());
-        *count += 1;
-      }
-    }
+    self.generate_cross_chunk_exports(chunk_graph, &chunk_meta_exports_vec);
     for chunk_id in chunk_graph.chunks.indices() {
       for (importee_chunk_id, import_items) in &mut imports_from_other_chunks_vec[chunk_id] {
         for item in import_items {
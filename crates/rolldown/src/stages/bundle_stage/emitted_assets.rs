@@ -0,0 +1,128 @@
+use std::path::Path;
+
+use rolldown_common::{EmittedAsset, FileNameRenderOptions, Output, OutputAsset};
+use rolldown_error::BuildError;
+use rolldown_utils::xxhash::xxhash_base64_url;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::utils::hash_placeholder::MAX_HASH_LENGTH;
+
+use super::BundleStage;
+
+impl<'a> BundleStage<'a> {
+  /// Turn assets emitted by plugins into outputs. Assets without explicit file names are named by `asset_file_names`
+  /// and deduplicated by content, so emitting the same file twice only writes it once. An explicit file name can't
+  /// overwrite another output, unless it's the same asset emitted again. Assets already among `outputs`, like those
+  /// emitted before `generate_bundle`, count as emitted before.
+  pub async fn generate_emitted_assets(
+    &self,
+    outputs: &[Output],
+  ) -> Result<Vec<Output>, BuildError> {
+    let mut used_file_names =
+      outputs.iter().map(|output| output.file_name().to_lowercase()).collect::<FxHashSet<_>>();
+    let mut file_name_by_content_hash = outputs
+      .iter()
+      .filter_map(|output| match output {
+        Output::Asset(asset) => {
+          Some((xxhash_base64_url(asset.source.as_bytes()), asset.file_name.clone()))
+        }
+        Output::Chunk(_) => None,
+      })
+      .collect::<FxHashMap<_, _>>();
+    let mut assets = vec![];
+
+    let file_emitter = self.plugin_driver.file_emitter();
+    for (reference_id, asset) in file_emitter.take_emitted_assets() {
+      let EmittedAsset { name, file_name, source } = asset;
+      let file_name = if let Some(file_name) = file_name {
+        if used_file_names.contains(&file_name.to_lowercase()) {
+          let is_same_asset = outputs.iter().chain(&assets).any(|output| match output {
+            Output::Asset(existing) => {
+              existing.file_name == file_name && existing.source.as_bytes() == source.as_bytes()
+            }
+            Output::Chunk(_) => false,
+          });
+          if !is_same_asset {
+            return Err(BuildError::file_name_conflict(file_name));
+          }
+          file_emitter.set_file_name(reference_id, file_name);
+          continue;
+        }
+        file_name
+      } else {
+        let content_hash = xxhash_base64_url(source.as_bytes());
+        if let Some(file_name) = file_name_by_content_hash.get(&content_hash) {
+          file_emitter.set_file_name(reference_id, String::clone(file_name));
+          continue;
+        }
+        let file_name = self.render_asset_file_name(name.as_deref(), &content_hash).await?;
+        let file_name = make_unique_file_name(file_name, &used_file_names);
+        file_name_by_content_hash.insert(content_hash, file_name.clone());
+        file_name
+      };
+      file_emitter.set_file_name(reference_id, file_name.clone());
+      used_file_names.insert(file_name.to_lowercase());
+      assets.push(Output::Asset(Box::new(OutputAsset { name, file_name, source })));
+    }
+
+    Ok(assets)
+  }
+
+  async fn render_asset_file_name(
+    &self,
+    name: Option<&str>,
+    content_hash: &str,
+  ) -> Result<String, BuildError> {
+    let template = &self.output_options.asset_file_names;
+    if let Some(len) = template.hash_lengths().into_iter().find(|len| *len > MAX_HASH_LENGTH) {
+      return Err(BuildError::hash_too_long(len, MAX_HASH_LENGTH));
+    }
+    let name = self.output_options.sanitize_file_name.call(name.unwrap_or("asset")).await?;
+    let path = Path::new(&name);
+    let stem = path.file_stem().map(|stem| stem.to_string_lossy()).unwrap_or_default();
+    let ext = path.extension().map(|ext| ext.to_string_lossy()).unwrap_or_default();
+    let extname = if ext.is_empty() { String::new() } else { format!(".{ext}") };
+    Ok(template.render(&FileNameRenderOptions {
+      name: Some(&stem),
+      hash: Some(&|len: usize| content_hash[..len].to_string()),
+      ext: Some(&ext),
+      extname: Some(&extname),
+    }))
+  }
+}
+
+/// Append a number to the file name if it's used already, like `logo2.png`. File names are compared in lower case
+/// for case-insensitive file systems.
+pub(super) fn make_unique_file_name(
+  file_name: String,
+  used_file_names: &FxHashSet<String>,
+) -> String {
+  if !used_file_names.contains(&file_name.to_lowercase()) {
+    return file_name;
+  }
+  let path = Path::new(&file_name);
+  let extname =
+    path.extension().map(|ext| format!(".{}", ext.to_string_lossy())).unwrap_or_default();
+  let base = &file_name[..file_name.len() - extname.len()];
+  let mut index = 2;
+  loop {
+    let candidate = format!("{base}{index}{extname}");
+    if !used_file_names.contains(&candidate.to_lowercase()) {
+      return candidate;
+    }
+    index += 1;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn unique_file_names_keep_the_extension() {
+    let used = ["logo.png", "logo2.png", "readme"].into_iter().map(String::from).collect();
+    assert_eq!(make_unique_file_name("icon.png".to_string(), &used), "icon.png");
+    assert_eq!(make_unique_file_name("Logo.png".to_string(), &used), "Logo3.png");
+    assert_eq!(make_unique_file_name("README".to_string(), &used), "README2");
+  }
+}
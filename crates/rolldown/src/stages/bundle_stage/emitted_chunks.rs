@@ -0,0 +1,27 @@
+use rustc_hash::FxHashMap;
+
+use super::BundleStage;
+
+impl<'a> BundleStage<'a> {
+  /// Record file names of chunks emitted by plugins for `PluginContext::get_file_name`. File names contain hash
+  /// placeholders while chunks are rendered, and they are recorded again once hashes are final.
+  pub fn set_emitted_chunk_file_names<'b>(
+    &self,
+    entry_file_names: impl Iterator<Item = (&'b str, &'b str)>,
+  ) {
+    let file_emitter = self.plugin_driver.file_emitter();
+    let chunk_module_ids = file_emitter.chunk_module_ids();
+    if chunk_module_ids.is_empty() {
+      return;
+    }
+    let mut file_name_by_module: FxHashMap<&str, &str> = FxHashMap::default();
+    for (module_id, file_name) in entry_file_names {
+      file_name_by_module.entry(module_id).or_insert(file_name);
+    }
+    for (reference_id, module_id) in chunk_module_ids {
+      if let Some(file_name) = file_name_by_module.get(module_id.as_str()) {
+        file_emitter.set_file_name(reference_id, (*file_name).to_string());
+      }
+    }
+  }
+}
@@ -0,0 +1,67 @@
+use std::path::Path;
+
+use rolldown_common::ModuleId;
+use rustc_hash::FxHashSet;
+use sugar_path::SugarPath;
+
+use crate::{chunk_graph::ChunkGraph, error::BatchedResult};
+
+use super::BundleStage;
+
+impl<'a> BundleStage<'a> {
+  /// Compute the paths used to import external modules in each chunk, which are rewritten by `output.paths` and
+  /// `makeAbsoluteExternalsRelative`. Externals that don't need to be rewritten are not recorded.
+  pub async fn compute_external_import_paths(
+    &self,
+    chunk_graph: &mut ChunkGraph,
+  ) -> BatchedResult<()> {
+    if self.output_options.paths.is_none() && !self.input_options.make_absolute_externals_relative {
+      return Ok(());
+    }
+    let out_dir = self.output_options.out_dir(&self.input_options.cwd);
+    let modules = &self.link_output.module_table;
+
+    for chunk in &mut chunk_graph.chunks {
+      let importees = chunk
+        .modules
+        .iter()
+        .flat_map(|id| modules.normal_modules[*id].import_records.iter())
+        .filter_map(|rec| match rec.resolved_module {
+          ModuleId::External(id) => Some(id),
+          ModuleId::Normal(_) => None,
+        })
+        .collect::<FxHashSet<_>>();
+      let chunk_dir = out_dir
+        .join(chunk.file_name.as_deref().expect("file name should be generated"))
+        .parent()
+        .map_or_else(|| out_dir.clone(), Path::to_path_buf);
+
+      for importee_id in importees {
+        let id = modules.external_modules[importee_id].resource_id.expect_file().as_str();
+        let mut import_path = match &self.output_options.paths {
+          Some(paths) => paths.call(id).await?,
+          None => id.to_string(),
+        };
+        if self.input_options.make_absolute_externals_relative
+          && Path::new(&import_path).is_absolute()
+        {
+          import_path = relative_import_path(Path::new(&import_path), &chunk_dir);
+        }
+        if import_path != id {
+          chunk.external_import_paths.insert(importee_id, import_path);
+        }
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Returns a path that could be imported from files in `dir`, like `./foo.js` or `../foo.js`.
+fn relative_import_path(path: &Path, dir: &Path) -> String {
+  let relative = path.relative(dir).to_string_lossy().replace('\\', "/");
+  if relative.starts_with("../") {
+    relative
+  } else {
+    format!("./{relative}")
+  }
+}
@@ -0,0 +1,63 @@
+use index_vec::IndexVec;
+use rolldown_common::{ChunkId, RenderedChunk};
+use rolldown_sourcemap::SourceMap;
+use rolldown_utils::xxhash::xxhash_base64_url;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::utils::hash_placeholder::{find_hash_placeholders, replace_hash_placeholders};
+
+/// Replace hash placeholders in file names and code with the content hashes of chunks. The hash of a chunk also
+/// covers the chunks it references transitively, so a change in an imported chunk changes the hashes of its
+/// importers as well.
+pub fn finalize_chunk_hashes(
+  chunk_by_placeholder: &FxHashMap<String, ChunkId>,
+  chunks: &mut [(String, Option<SourceMap>, RenderedChunk)],
+) {
+  if chunk_by_placeholder.is_empty() {
+    return;
+  }
+
+  let content_hashes = chunks
+    .iter()
+    .map(|(code, _, _)| xxhash_base64_url(code.as_bytes()))
+    .collect::<IndexVec<ChunkId, _>>();
+
+  let referenced_chunks = chunks
+    .iter()
+    .map(|(code, _, _)| {
+      find_hash_placeholders(code)
+        .filter_map(|placeholder| chunk_by_placeholder.get(placeholder).copied())
+        .collect::<Vec<_>>()
+    })
+    .collect::<IndexVec<ChunkId, _>>();
+
+  let final_hashes = content_hashes
+    .indices()
+    .map(|chunk_id| {
+      let mut visited = FxHashSet::default();
+      let mut stack = vec![chunk_id];
+      let mut hash_input = String::new();
+      while let Some(id) = stack.pop() {
+        if !visited.insert(id) {
+          continue;
+        }
+        hash_input.push_str(&content_hashes[id]);
+        stack.extend(referenced_chunks[id].iter().rev());
+      }
+      xxhash_base64_url(hash_input.as_bytes())
+    })
+    .collect::<IndexVec<ChunkId, _>>();
+
+  let hash_by_placeholder = chunk_by_placeholder
+    .iter()
+    .map(|(placeholder, chunk_id)| {
+      (placeholder.clone(), final_hashes[*chunk_id][..placeholder.len()].to_string())
+    })
+    .collect::<FxHashMap<_, _>>();
+
+  chunks.iter_mut().for_each(|(code, _, rendered_chunk)| {
+    *code = replace_hash_placeholders(code, &hash_by_placeholder);
+    rendered_chunk.file_name =
+      replace_hash_placeholders(&rendered_chunk.file_name, &hash_by_placeholder);
+  });
+}
@@ -0,0 +1,116 @@
+use std::path::Path;
+
+use rolldown_common::{
+  ImportKind, ManualChunks, ModuleId, ModuleInfo, NormalModule, NormalModuleId,
+};
+use rolldown_error::BuildError;
+use rustc_hash::{FxHashMap, FxHashSet};
+use sugar_path::SugarPath;
+
+use super::BundleStage;
+
+impl<'a> BundleStage<'a> {
+  /// Returns the name of the manual chunk that each module belongs to.
+  pub async fn assign_manual_chunks(
+    &self,
+  ) -> Result<FxHashMap<NormalModuleId, String>, BuildError> {
+    let mut module_to_manual_chunk = FxHashMap::default();
+    let Some(manual_chunks) = &self.output_options.manual_chunks else {
+      return Ok(module_to_manual_chunk);
+    };
+
+    let modules = &self.link_output.module_table.normal_modules;
+    // Entry modules always stay in their own chunks
+    let entry_modules =
+      self.link_output.entries.iter().map(|entry| entry.id).collect::<FxHashSet<_>>();
+    let runtime_id = self.link_output.runtime.id();
+    let candidates = modules
+      .iter()
+      .filter(|m| m.is_included && m.id != runtime_id && !entry_modules.contains(&m.id))
+      .collect::<Vec<_>>();
+
+    let mut assigned = vec![];
+    match manual_chunks {
+      ManualChunks::Map(map) => {
+        let mut names = map.keys().collect::<Vec<_>>();
+        names.sort();
+        for name in names {
+          for module in &candidates {
+            if map[name].iter().any(|pattern| self.is_module_matched(module, pattern)) {
+              assigned.push((module.id, name.clone()));
+            }
+          }
+        }
+      }
+      ManualChunks::Fn(manual_chunks_fn) => {
+        for module in &candidates {
+          let id = module.resource_id.expect_file().to_string();
+          if let Some(name) = manual_chunks_fn(id, self.module_info(module)).await? {
+            assigned.push((module.id, name));
+          }
+        }
+      }
+    }
+
+    // Modules assigned explicitly take precedence over dependencies pulled into manual chunks
+    for (module_id, name) in &assigned {
+      module_to_manual_chunk.entry(*module_id).or_insert_with(|| name.clone());
+    }
+
+    for (module_id, name) in assigned {
+      let mut stack = vec![module_id];
+      while let Some(module_id) = stack.pop() {
+        modules[module_id]
+          .import_records
+          .iter()
+          .filter(|rec| rec.kind != ImportKind::DynamicImport)
+          .filter_map(|rec| rec.resolved_module.as_normal())
+          .for_each(|importee_id| {
+            let importee = &modules[importee_id];
+            if !importee.is_included
+              || importee_id == runtime_id
+              || entry_modules.contains(&importee_id)
+              || module_to_manual_chunk.contains_key(&importee_id)
+            {
+              return;
+            }
+            module_to_manual_chunk.insert(importee_id, name.clone());
+            stack.push(importee_id);
+          });
+      }
+    }
+
+    Ok(module_to_manual_chunk)
+  }
+
+  fn is_module_matched(&self, module: &NormalModule, pattern: &str) -> bool {
+    let id = module.resource_id.expect_file().as_str();
+    if Path::new(id) == self.input_options.cwd.join(pattern).normalize() {
+      return true;
+    }
+    // Treat the pattern as a package name
+    id.replace('\\', "/").contains(&format!("/node_modules/{pattern}/"))
+  }
+
+  fn module_info(&self, module: &NormalModule) -> ModuleInfo {
+    let module_table = &self.link_output.module_table;
+    let ids_of = |is_dynamic: bool| {
+      module
+        .import_records
+        .iter()
+        .filter(|rec| (rec.kind == ImportKind::DynamicImport) == is_dynamic)
+        .map(|rec| match rec.resolved_module {
+          ModuleId::Normal(id) => module_table.normal_modules[id].resource_id.expect_file(),
+          ModuleId::External(id) => module_table.external_modules[id].resource_id.expect_file(),
+        })
+        .map(|path| path.as_str().to_string())
+        .collect()
+    };
+    ModuleInfo {
+      id: module.resource_id.expect_file().to_string(),
+      is_entry: module.is_user_defined_entry,
+      imported_ids: ids_of(false),
+      dynamically_imported_ids: ids_of(true),
+    }
+  }
+}
@@ -0,0 +1,146 @@
+use crate::chunk::{Chunk, ChunksVec};
+use index_vec::{index_vec, IndexVec};
+use rolldown_common::{ChunkId, ChunkKind, ImportKind, ModuleId, NormalModuleId};
+
+use super::BundleStage;
+
+impl<'a> BundleStage<'a> {
+  /// Merges common chunks smaller than `experimental_min_chunk_size` into other common chunks or entry chunks. The
+  /// merged chunk is loaded by entries of both sides, so merging is only done if code that an entry didn't load before
+  /// is free of side effects.
+  pub fn merge_small_chunks(
+    &self,
+    chunks: ChunksVec,
+    module_to_chunk: &mut IndexVec<NormalModuleId, Option<ChunkId>>,
+  ) -> ChunksVec {
+    let min_chunk_size = self.output_options.experimental_min_chunk_size;
+    if min_chunk_size == 0 {
+      return chunks;
+    }
+
+    let modules = &self.link_output.module_table.normal_modules;
+    let runtime_id = self.link_output.runtime.id();
+    let mut sizes = chunks
+      .iter()
+      .map(|chunk| chunk.modules.iter().map(|id| modules[*id].source.len()).sum::<usize>())
+      .collect::<IndexVec<ChunkId, _>>();
+    let is_pure_module = self.pure_modules();
+    let mut is_pure = chunks
+      .iter()
+      .map(|chunk| chunk.modules.iter().all(|id| is_pure_module[*id]))
+      .collect::<IndexVec<ChunkId, _>>();
+    // Named chunks are requested by users explicitly, and the runtime chunk stays standalone in tests
+    let is_mergeable = |chunk: &Chunk| {
+      matches!(chunk.kind, ChunkKind::Common)
+        && chunk.name.is_none()
+        && !(self.has_standalone_runtime_chunk() && chunk.modules.contains(&runtime_id))
+    };
+
+    // Entry chunks are loaded anyway, so they could take small chunks but never be merged into others
+    let is_merge_target =
+      |chunk: &Chunk| matches!(chunk.kind, ChunkKind::EntryPoint { .. }) || is_mergeable(chunk);
+
+    let mut small_chunks = chunks
+      .iter_enumerated()
+      .filter(|(id, chunk)| is_mergeable(chunk) && sizes[*id] < min_chunk_size)
+      .map(|(id, _)| id)
+      .collect::<Vec<_>>();
+    small_chunks.sort_by_key(|id| (sizes[*id], *id));
+
+    let mut chunks = chunks.into_iter().map(Some).collect::<IndexVec<ChunkId, _>>();
+    for small_chunk_id in small_chunks {
+      let Some(small_chunk) = &chunks[small_chunk_id] else {
+        continue;
+      };
+      // Prefer the chunk loaded by the most similar set of entries
+      let target = chunks
+        .iter_enumerated()
+        .filter(|(id, _)| *id != small_chunk_id)
+        .filter_map(|(id, chunk)| chunk.as_ref().map(|chunk| (id, chunk)))
+        .filter(|(_, chunk)| is_merge_target(chunk))
+        .filter(|(id, chunk)| {
+          // Entries that only loaded one side are going to execute the other side too
+          (chunk.bits.is_subset_of(&small_chunk.bits) || is_pure[small_chunk_id])
+            && (small_chunk.bits.is_subset_of(&chunk.bits) || is_pure[*id])
+        })
+        .min_by_key(|(id, chunk)| {
+          let mut bits = chunk.bits.clone();
+          bits.union(&small_chunk.bits);
+          let distance =
+            2 * bits.count_ones() - chunk.bits.count_ones() - small_chunk.bits.count_ones();
+          (distance, sizes[*id], *id)
+        })
+        .map(|(id, _)| id);
+      let Some(target_id) = target else {
+        continue;
+      };
+
+      let small_chunk = chunks[small_chunk_id].take().expect("should exist");
+      let target = chunks[target_id].as_mut().expect("should exist");
+      target.bits.union(&small_chunk.bits);
+      target.modules.extend(small_chunk.modules);
+      sizes[target_id] += sizes[small_chunk_id];
+      is_pure[target_id] = is_pure[target_id] && is_pure[small_chunk_id];
+    }
+
+    // Remove merged chunks and update ids of the rest
+    let mut merged_chunks = ChunksVec::with_capacity(chunks.len());
+    for chunk in chunks.into_iter().flatten() {
+      let chunk_id = merged_chunks.next_idx();
+      chunk.modules.iter().for_each(|module_id| module_to_chunk[*module_id] = Some(chunk_id));
+      merged_chunks.push(chunk);
+    }
+    merged_chunks
+  }
+
+  /// Whether executing each module is free of side effects. Static imports aren't side effects by themselves if
+  /// imported modules are free of side effects too.
+  fn pure_modules(&self) -> IndexVec<NormalModuleId, bool> {
+    let modules = &self.link_output.module_table.normal_modules;
+    // Modules imported by statements whose only side effect is importing them
+    let mut imported_modules = index_vec![vec![]; modules.len()];
+    let mut is_pure = modules
+      .iter()
+      .map(|module| {
+        // The facade statement of an entry is marked as a side effect only to include its exports
+        module
+          .stmt_infos
+          .iter()
+          .filter(|stmt_info| {
+            stmt_info.stmt_idx.is_some() && stmt_info.is_included && stmt_info.side_effect
+          })
+          .all(|stmt_info| {
+            let importees = stmt_info
+              .import_records
+              .iter()
+              .map(|rec_id| &module.import_records[*rec_id])
+              .map(|rec| match rec.resolved_module {
+                ModuleId::Normal(importee) if rec.kind == ImportKind::Import => Some(importee),
+                _ => None,
+              })
+              .collect::<Option<Vec<_>>>();
+            match importees {
+              Some(importees) if !importees.is_empty() => {
+                imported_modules[module.id].extend(importees);
+                true
+              }
+              _ => false,
+            }
+          })
+      })
+      .collect::<IndexVec<NormalModuleId, _>>();
+
+    // Side effects of imported modules propagate to importers until nothing changes, which handles cycles
+    let mut changed = true;
+    while changed {
+      changed = false;
+      for (module_id, importees) in imported_modules.iter_enumerated() {
+        if is_pure[module_id] && importees.iter().any(|importee| !is_pure[*importee]) {
+          is_pure[module_id] = false;
+          changed = true;
+        }
+      }
+    }
+    is_pure
+  }
+}
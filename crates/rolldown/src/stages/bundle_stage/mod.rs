@@ -2,20 +2,40 @@ use crate::{
   chunk_graph::ChunkGraph,
   error::BatchedResult,
   finalizer::FinalizerContext,
-  options::{
-    file_name_template::FileNameRenderOptions, normalized_input_options::NormalizedInputOptions,
-    normalized_output_options::NormalizedOutputOptions, output_options::SourceMapType,
-  },
   stages::link_stage::LinkStageOutput,
-  utils::{finalize_normal_module, is_in_rust_test_mode, render_chunks::render_chunks},
+  utils::{
+    finalize_normal_module,
+    hash_placeholder::{generate_hash_placeholder, min_hash_length, MAX_HASH_LENGTH},
+    is_in_rust_test_mode,
+    render_chunk_addons::render_chunk_addons,
+    render_chunks::render_chunks,
+  },
+  OutputFormat,
+};
+use std::path::Path;
+
+use chunk_names::chunk_ids_by_naming_priority;
+use finalize_chunk_hashes::finalize_chunk_hashes;
+use index_vec::IndexVec;
+use rolldown_common::{
+  ChunkId, ChunkKind, FileNameRenderOptions, NormalizedInputOptions, NormalizedOutputOptions,
+  Output, OutputAsset, OutputChunk, SourceMapType,
 };
-use rolldown_common::{ChunkKind, Output, OutputAsset, OutputChunk};
 use rolldown_error::BuildError;
 use rolldown_plugin::SharedPluginDriver;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
+mod advanced_chunks;
+mod chunk_names;
 mod code_splitting;
 mod compute_cross_chunk_links;
+mod emitted_assets;
+mod emitted_chunks;
+mod external_import_paths;
+mod finalize_chunk_hashes;
+mod manual_chunks;
+mod merge_small_chunks;
+mod preserve_modules;
 
 pub struct BundleStage<'a> {
   link_output: &'a mut LinkStageOutput,
@@ -38,18 +58,78 @@ impl<'a> BundleStage<'a> {
   pub async fn bundle(&mut self) -> BatchedResult<Vec<Output>> {
     use rayon::prelude::*;
     tracing::info!("Start bundle stage");
-    let mut chunk_graph = self.generate_chunks();
+    if self.output_options.inline_dynamic_imports && self.link_output.entries.len() > 1 {
+      return Err(BuildError::inline_dynamic_imports_with_multiple_entries().into());
+    }
+    if self.output_options.preserve_modules {
+      let unsupported_option = if self.output_options.inline_dynamic_imports {
+        Some("output.inlineDynamicImports")
+      } else if self.output_options.manual_chunks.is_some() {
+        Some("output.manualChunks")
+      } else if self.output_options.advanced_chunks.is_some() {
+        Some("output.advancedChunks")
+      } else {
+        None
+      };
+      if let Some(option) = unsupported_option {
+        return Err(BuildError::unsupported_option_with_preserve_modules(option).into());
+      }
+    }
+    let module_to_manual_chunk = self.assign_manual_chunks().await?;
+    let matched_advanced_chunk_groups =
+      self.match_advanced_chunk_groups(&module_to_manual_chunk).await?;
+    let mut chunk_graph =
+      self.generate_chunks(&module_to_manual_chunk, &matched_advanced_chunk_groups)?;
+
+    if chunk_graph.chunks.len() > 1 && !self.output_options.format.supports_code_splitting() {
+      return Err(
+        BuildError::unsupported_code_splitting_format(self.output_options.format.to_string())
+          .into(),
+      );
+    }
+
+    if chunk_graph.chunks.len() > 1 && self.output_options.file.is_some() {
+      return Err(BuildError::multiple_chunks_with_file_option().into());
+    }
 
-    self.generate_chunk_filenames(&mut chunk_graph);
+    let chunk_by_placeholder = self.generate_chunk_filenames(&mut chunk_graph).await?;
     tracing::info!("generate_chunk_filenames");
 
+    let modules = &self.link_output.module_table.normal_modules;
+    self.set_emitted_chunk_file_names(chunk_graph.chunks.iter().filter_map(|chunk| {
+      let ChunkKind::EntryPoint { module, .. } = chunk.kind else {
+        return None;
+      };
+      Some((modules[module].resource_id.expect_file().as_str(), chunk.file_name.as_deref()?))
+    }));
+
+    self.compute_external_import_paths(&mut chunk_graph).await?;
+
     self.compute_cross_chunk_links(&mut chunk_graph);
     tracing::info!("compute_cross_chunk_links");
 
+    // File names of chunks are used to generate readable bindings for `require` calls in CJS output
+    let chunk_file_names = chunk_graph
+      .chunks
+      .iter()
+      .map(|chunk| chunk.file_name.clone().expect("file name should be generated"))
+      .collect::<IndexVec<ChunkId, _>>();
     chunk_graph.chunks.iter_mut().par_bridge().for_each(|chunk| {
-      chunk.de_conflict(self.link_output);
+      chunk.de_conflict(self.link_output, self.output_options, &chunk_file_names);
     });
 
+    let exported_names_by_symbol_vec = chunk_graph
+      .chunks
+      .iter()
+      .map(|chunk| {
+        if matches!(self.output_options.format, OutputFormat::System) {
+          chunk.get_exported_names_by_symbol(self.link_output)
+        } else {
+          FxHashMap::default()
+        }
+      })
+      .collect::<IndexVec<ChunkId, _>>();
+
     self
       .link_output
       .ast_table
@@ -70,28 +150,39 @@ impl<'a> BundleStage<'a> {
             linking_info,
             module,
             modules: &self.link_output.module_table.normal_modules,
+            external_modules: &self.link_output.module_table.external_modules,
             linking_infos: &self.link_output.metas,
             runtime: &self.link_output.runtime,
             chunk_graph: &chunk_graph,
+            output_options: self.output_options,
+            exported_names_by_symbol: &exported_names_by_symbol_vec[chunk_id],
           },
           ast,
         );
       });
     tracing::info!("finalizing modules");
 
-    let chunks = chunk_graph.chunks.iter().map(|c| {
+    let mut chunks = Vec::with_capacity(chunk_graph.chunks.len());
+    for c in &chunk_graph.chunks {
+      let rendered_chunk = c.get_rendered_chunk_info(self.link_output);
+      let addons =
+        render_chunk_addons(self.plugin_driver, self.output_options, &rendered_chunk).await?;
       let ret =
-        c.render(self.input_options, self.link_output, &chunk_graph, self.output_options).unwrap();
-      (
-        ret.code,
-        ret.map,
-        c.get_rendered_chunk_info(self.link_output, self.output_options, ret.rendered_modules),
-      )
-    });
+        c.render(self.input_options, self.link_output, &chunk_graph, self.output_options, &addons)?;
+      self.link_output.warnings.extend(ret.warnings);
+      chunks.push((ret.code, ret.map, rendered_chunk));
+    }
 
     let mut assets = vec![];
 
-    render_chunks(self.plugin_driver, chunks).await?.into_iter().try_for_each(
+    let (mut chunks, warnings) = render_chunks(self.plugin_driver, chunks.into_iter()).await?;
+    self.link_output.warnings.extend(warnings);
+    finalize_chunk_hashes(&chunk_by_placeholder, &mut chunks);
+    self.set_emitted_chunk_file_names(chunks.iter().filter_map(|(_, _, rendered_chunk)| {
+      Some((rendered_chunk.facade_module_id.as_deref()?, rendered_chunk.file_name.as_str()))
+    }));
+
+    chunks.into_iter().try_for_each(
       |(mut content, map, rendered_chunk)| -> Result<(), BuildError> {
         if let Some(mut map) = map {
           map.set_file(Some(rendered_chunk.file_name.clone()));
@@ -104,8 +195,9 @@ impl<'a> BundleStage<'a> {
               };
               let map_file_name = format!("{}.map", rendered_chunk.file_name);
               assets.push(Output::Asset(Box::new(OutputAsset {
+                name: None,
                 file_name: map_file_name.clone(),
-                source: map,
+                source: map.into(),
               })));
               content.push_str(&format!("\n//# sourceMappingURL={map_file_name}"));
             }
@@ -133,47 +225,70 @@ impl<'a> BundleStage<'a> {
 
     tracing::info!("rendered chunks");
 
+    let emitted_assets = self.generate_emitted_assets(&assets).await?;
+    assets.extend(emitted_assets);
+
     Ok(assets)
   }
 
-  fn generate_chunk_filenames(&self, chunk_graph: &mut ChunkGraph) {
-    let mut used_chunk_names = FxHashSet::default();
-    chunk_graph.chunks.iter_mut().for_each(|chunk| {
-      let runtime_id = self.link_output.runtime.id();
+  /// In test mode, the runtime module is put into a standalone chunk to keep snapshots clean. This is not possible
+  /// for formats that don't support code splitting, or builds that must produce a single chunk.
+  fn has_standalone_runtime_chunk(&self) -> bool {
+    is_in_rust_test_mode()
+      && self.output_options.format.supports_code_splitting()
+      && self.output_options.file.is_none()
+      && !self.output_options.inline_dynamic_imports
+  }
+
+  /// File names with `[hash]` patterns are rendered with placeholders, since the content hash is only known after
+  /// rendering. Returns the chunk each placeholder belongs to.
+  async fn generate_chunk_filenames(
+    &self,
+    chunk_graph: &mut ChunkGraph,
+  ) -> Result<FxHashMap<String, ChunkId>, BuildError> {
+    let mut chunk_by_placeholder = FxHashMap::default();
+
+    if let Some(file) = &self.output_options.file {
+      // There is only one chunk, which is named after the `file` option.
+      let file_name = Path::new(file).file_name().map(|name| name.to_string_lossy().to_string());
+      chunk_graph.chunks.iter_mut().for_each(|chunk| chunk.file_name = file_name.clone());
+      return Ok(chunk_by_placeholder);
+    }
 
+    let chunk_names = self.generate_chunk_names(chunk_graph).await?;
+    let chunk_count = chunk_graph.chunks.len();
+    let mut used_file_names = FxHashSet::default();
+    for chunk_id in chunk_ids_by_naming_priority(chunk_graph) {
+      let chunk = &mut chunk_graph.chunks[chunk_id];
       let file_name_tmp = chunk.file_name_template(self.output_options);
-      let chunk_name =
-        if is_in_rust_test_mode() && chunk.modules.first().copied() == Some(runtime_id) {
-          "$runtime$".to_string()
-        } else {
-          chunk.name.clone().unwrap_or_else(|| {
-            let module_id =
-              if let ChunkKind::EntryPoint { module: entry_module_id, is_user_defined, .. } =
-                &chunk.kind
-              {
-                debug_assert!(
-                  !*is_user_defined,
-                  "User-defined entry point should always have a name"
-                );
-                *entry_module_id
-              } else {
-                // TODO: we currently use the first executed module to calculate the chunk name for common chunks
-                // This is not perfect, should investigate more to find a better solution
-                chunk.modules.first().copied().unwrap()
-              };
-            let module = &self.link_output.module_table.normal_modules[module_id];
-            module.resource_id.expect_file().unique(&self.input_options.cwd)
-          })
-        };
 
-      let mut chunk_name = chunk_name;
-      while used_chunk_names.contains(&chunk_name) {
-        chunk_name = format!("{}-{}", chunk_name, used_chunk_names.len());
+      for len in file_name_tmp.hash_lengths() {
+        if len > MAX_HASH_LENGTH {
+          return Err(BuildError::hash_too_long(len, MAX_HASH_LENGTH));
+        }
+        let min_len = min_hash_length(chunk_count);
+        if len < min_len {
+          return Err(BuildError::hash_too_short(len, min_len, chunk_count));
+        }
+        chunk_by_placeholder.insert(generate_hash_placeholder(chunk_id.index(), len), chunk_id);
       }
-      used_chunk_names.insert(chunk_name.clone());
 
-      chunk.file_name =
-        Some(file_name_tmp.render(&FileNameRenderOptions { name: Some(&chunk_name) }));
-    });
+      let hash_placeholder = |len: usize| generate_hash_placeholder(chunk_id.index(), len);
+      let file_name = self.render_unique_chunk_file_name(
+        chunk,
+        &chunk_names[chunk_id],
+        file_name_tmp,
+        |name| {
+          file_name_tmp.render(&FileNameRenderOptions {
+            name: Some(name),
+            hash: Some(&hash_placeholder),
+            ..Default::default()
+          })
+        },
+        &mut used_file_names,
+      );
+      chunk.file_name = Some(file_name);
+    }
+    Ok(chunk_by_placeholder)
   }
 }
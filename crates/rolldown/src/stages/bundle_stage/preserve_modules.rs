@@ -0,0 +1,53 @@
+use std::path::{Component, Path, PathBuf};
+
+use rolldown_common::NormalModule;
+use sugar_path::SugarPath;
+
+use super::BundleStage;
+
+impl<'a> BundleStage<'a> {
+  /// The directory that names of preserved modules are relative to. Defaults to the common directory of entry modules.
+  pub fn preserve_modules_root(&self) -> PathBuf {
+    let cwd = &self.input_options.cwd;
+    if let Some(root) = &self.output_options.preserve_modules_root {
+      return cwd.join(root).normalize().into_owned();
+    }
+    let modules = &self.link_output.module_table.normal_modules;
+    self
+      .link_output
+      .entries
+      .iter()
+      .filter_map(|entry| Path::new(modules[entry.id].resource_id.expect_file().as_str()).parent())
+      .map(Path::to_path_buf)
+      .reduce(|common, dir| {
+        common
+          .components()
+          .zip(dir.components())
+          .take_while(|(a, b)| a == b)
+          .map(|(a, _)| a)
+          .collect()
+      })
+      .unwrap_or_else(|| cwd.clone())
+  }
+}
+
+/// Returns the chunk name of a preserved module, like `components/button` for `<root>/components/button.js`.
+/// Modules outside of the root get `_` for each `..` in their relative paths.
+pub fn preserved_module_chunk_name(module: &NormalModule, root: &Path) -> String {
+  let path = Path::new(module.resource_id.expect_file().as_str());
+  if !path.is_absolute() {
+    // Virtual modules, such as the runtime
+    return path.to_string_lossy().to_string();
+  }
+  let mut relative = path.relative(root);
+  relative.set_extension("");
+  relative
+    .components()
+    .filter_map(|component| match component {
+      Component::Normal(segment) => Some(segment.to_string_lossy()),
+      Component::ParentDir => Some("_".into()),
+      _ => None,
+    })
+    .collect::<Vec<_>>()
+    .join("/")
+}
@@ -3,13 +3,13 @@ use std::{ptr::addr_of, sync::Mutex};
 use index_vec::IndexVec;
 use rayon::iter::{ParallelBridge, ParallelIterator};
 use rolldown_common::{
-  EntryPoint, ExportsKind, ImportKind, ModuleId, NormalModule, NormalModuleId, StmtInfo, WrapKind,
+  EntryPoint, EntryPointKind, ExportsKind, ImportKind, ModuleId, NormalModule, NormalModuleId,
+  NormalizedInputOptions, NormalizedOutputOptions, StmtInfo, WrapKind,
 };
 use rolldown_error::BuildError;
 use rolldown_oxc_utils::OxcProgram;
 
 use crate::{
-  options::normalized_input_options::NormalizedInputOptions,
   runtime::RuntimeModuleBrief,
   types::{
     linking_metadata::{LinkingMetadata, LinkingMetadataVec},
@@ -50,12 +50,14 @@ pub struct LinkStage<'a> {
   pub warnings: Vec<BuildError>,
   pub ast_table: IndexVec<NormalModuleId, OxcProgram>,
   pub input_options: &'a NormalizedInputOptions,
+  pub output_options: &'a NormalizedOutputOptions,
 }
 
 impl<'a> LinkStage<'a> {
   pub fn new(
     scan_stage_output: ScanStageOutput,
     input_options: &'a NormalizedInputOptions,
+    output_options: &'a NormalizedOutputOptions,
   ) -> Self {
     Self {
       sorted_modules: Vec::new(),
@@ -72,6 +74,7 @@ impl<'a> LinkStage<'a> {
       warnings: scan_stage_output.warnings,
       ast_table: scan_stage_output.ast_table,
       input_options,
+      output_options,
     }
   }
 
@@ -114,6 +117,10 @@ impl<'a> LinkStage<'a> {
   pub fn link(mut self) -> LinkStageOutput {
     tracing::info!("Start link stage");
     self.sort_modules();
+    if self.output_options.inline_dynamic_imports {
+      // Dynamically imported modules are still sorted as entries, so they are executed after the static ones
+      self.entries.retain(|entry| !matches!(entry.kind, EntryPointKind::DynamicImport));
+    }
 
     self.determine_module_exports_kind();
     self.wrap_modules();
@@ -195,7 +202,18 @@ impl<'a> LinkStage<'a> {
               }
             }
           },
-          ImportKind::DynamicImport => {}
+          ImportKind::DynamicImport => {
+            // Inlined dynamic imports are resolved to the namespace object of the importee
+            if self.output_options.inline_dynamic_imports
+              && matches!(importee.exports_kind, ExportsKind::None)
+            {
+              // SAFETY: If `importee` and `importer` are different, so this is safe. If they are the same, then behaviors are still expected.
+              unsafe {
+                let importee_mut = addr_of!(*importee).cast_mut();
+                (*importee_mut).exports_kind = ExportsKind::Esm;
+              }
+            }
+          }
         }
       });
 
@@ -276,7 +294,28 @@ impl<'a> LinkStage<'a> {
                 stmt_info.referenced_symbols.push(importee.namespace_symbol);
               }
             },
-            ImportKind::DynamicImport => {}
+            ImportKind::DynamicImport => {
+              if !self.output_options.inline_dynamic_imports {
+                return;
+              }
+              let importee = &self.module_table.normal_modules[importee_id];
+              match importee_linking_info.wrap_kind {
+                WrapKind::None => {
+                  // something like `Promise.resolve().then(() => foo_exports)`
+                  stmt_info.referenced_symbols.push(importee.namespace_symbol);
+                }
+                WrapKind::Cjs => {
+                  // something like `Promise.resolve().then(() => __toESM(require_foo()))`
+                  stmt_info.referenced_symbols.push(importee_linking_info.wrapper_ref.unwrap());
+                  stmt_info.referenced_symbols.push(self.runtime.resolve_symbol("__toESM"));
+                }
+                WrapKind::Esm => {
+                  // something like `Promise.resolve().then(() => (init_foo(), foo_exports))`
+                  stmt_info.referenced_symbols.push(importee_linking_info.wrapper_ref.unwrap());
+                  stmt_info.referenced_symbols.push(importee.namespace_symbol);
+                }
+              }
+            }
           }
         });
       });
@@ -1,7 +1,9 @@
 use std::sync::Arc;
 
 use index_vec::IndexVec;
-use rolldown_common::{EntryPoint, ImportKind, IntoBatchedResult, NormalModuleId};
+use rolldown_common::{
+  EntryPoint, ImportKind, IntoBatchedResult, NormalModuleId, SharedNormalizedInputOptions,
+};
 use rolldown_error::BuildError;
 use rolldown_fs::FileSystem;
 use rolldown_oxc_utils::OxcProgram;
@@ -11,7 +13,6 @@ use rolldown_utils::block_on_spawn_all;
 use crate::{
   error::BatchedResult,
   module_loader::{module_loader::ModuleLoaderOutput, ModuleLoader},
-  options::normalized_input_options::SharedNormalizedInputOptions,
   runtime::RuntimeModuleBrief,
   types::{
     module_table::ModuleTable, resolved_request_info::ResolvedRequestInfo, symbols::Symbols,
@@ -88,6 +89,7 @@ impl<Fs: FileSystem + Default + 'static> ScanStage<Fs> {
           specifier,
           None,
           HookResolveIdExtraOptions { is_entry: true, kind: ImportKind::Import },
+          None,
           false,
         )
         .await
@@ -1,11 +1,8 @@
-use rolldown_common::SymbolRef;
+use rolldown_common::{NormalizedInputOptions, SymbolRef};
 use rolldown_rstr::Rstr;
 use rustc_hash::FxHashMap;
 
-use crate::{
-  chunk_graph::ChunkGraph, options::normalized_input_options::NormalizedInputOptions,
-  stages::link_stage::LinkStageOutput,
-};
+use crate::{chunk_graph::ChunkGraph, stages::link_stage::LinkStageOutput};
 
 pub struct ModuleRenderContext<'a> {
   pub input_options: &'a NormalizedInputOptions,
@@ -1,10 +1,10 @@
 use std::sync::Arc;
 
 use index_vec::IndexVec;
-use oxc::{semantic::SymbolId, span::Span};
+use oxc::semantic::SymbolId;
 use rolldown_common::{
-  AstScope, ExportsKind, ImportRecord, ImportRecordId, LocalExport, ModuleType, NamedImport,
-  NormalModule, NormalModuleId, ResourceId, StmtInfos, SymbolRef,
+  AstScope, ExportsKind, ImportRecord, ImportRecordId, ImportRecordKey, LocalExport, ModuleType,
+  NamedImport, NormalModule, NormalModuleId, ResourceId, StmtInfos, SymbolRef,
 };
 use rolldown_rstr::Rstr;
 use rustc_hash::FxHashMap;
@@ -19,7 +19,7 @@ pub struct NormalModuleBuilder {
   pub named_exports: Option<FxHashMap<Rstr, LocalExport>>,
   pub stmt_infos: Option<StmtInfos>,
   pub import_records: Option<IndexVec<ImportRecordId, ImportRecord>>,
-  pub imports: Option<FxHashMap<Span, ImportRecordId>>,
+  pub imports: Option<FxHashMap<ImportRecordKey, ImportRecordId>>,
   pub star_exports: Option<Vec<ImportRecordId>>,
   pub scope: Option<AstScope>,
   pub default_export_ref: Option<SymbolRef>,
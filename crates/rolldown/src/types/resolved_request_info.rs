@@ -1,6 +1,6 @@
 use rolldown_common::{ModuleType, ResolvedPath};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ResolvedRequestInfo {
   pub path: ResolvedPath,
   pub module_type: ModuleType,
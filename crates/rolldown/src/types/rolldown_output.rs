@@ -4,4 +4,6 @@ use rolldown_error::BuildError;
 pub struct RolldownOutput {
   pub warnings: Vec<BuildError>,
   pub assets: Vec<Output>,
+  /// Files added by plugins via `PluginContext::add_watch_file`.
+  pub watch_files: Vec<String>,
 }
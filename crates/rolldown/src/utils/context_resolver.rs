@@ -0,0 +1,37 @@
+use rolldown_common::{FilePath, SharedNormalizedInputOptions};
+use rolldown_error::BuildError;
+use rolldown_fs::FileSystem;
+use rolldown_plugin::{ContextResolver, HookResolveIdExtraOptions, PluginDriver, ResolvedId};
+
+use crate::{utils::resolve_id::resolve_id_with_external, SharedResolver};
+
+/// Backs `PluginContext::resolve` with the resolver and the `external` option of the bundler.
+pub struct BundlerContextResolver<T: FileSystem + Default> {
+  pub input_options: SharedNormalizedInputOptions,
+  pub resolver: SharedResolver<T>,
+}
+
+#[async_trait::async_trait]
+impl<T: FileSystem + Default> ContextResolver for BundlerContextResolver<T> {
+  async fn resolve(
+    &self,
+    plugin_driver: &PluginDriver,
+    specifier: &str,
+    importer: Option<&str>,
+    options: HookResolveIdExtraOptions,
+    skipped_plugin: Option<usize>,
+  ) -> Result<ResolvedId, BuildError> {
+    let importer = importer.map(FilePath::new);
+    let info = resolve_id_with_external(
+      &self.input_options,
+      &self.resolver,
+      plugin_driver,
+      specifier,
+      importer.as_ref(),
+      options,
+      skipped_plugin,
+    )
+    .await?;
+    Ok(ResolvedId { id: info.path.path.to_string(), external: info.is_external })
+  }
+}
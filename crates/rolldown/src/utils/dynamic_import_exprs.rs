@@ -0,0 +1,59 @@
+use oxc::{
+  allocator::Allocator,
+  ast::{ast, Visit, VisitMut},
+  span::{GetSpan, Span},
+};
+use rolldown_oxc_utils::AstSnippet;
+use rustc_hash::FxHashMap;
+
+/// Collect dynamic imports with arguments that aren't string literals, such as `import(`./${name}.js`)`. Returns
+/// the spans of the import expressions along with the spans of their arguments.
+pub fn collect_dynamic_import_exprs(program: &ast::Program<'_>) -> Vec<(Span, Span)> {
+  #[derive(Default)]
+  struct Collector {
+    spans: Vec<(Span, Span)>,
+  }
+
+  impl<'ast> Visit<'ast> for Collector {
+    fn visit_import_expression(&mut self, expr: &ast::ImportExpression<'ast>) {
+      if !matches!(expr.source, ast::Expression::StringLiteral(_)) {
+        self.spans.push((expr.span, expr.source.span()));
+      }
+      self.visit_expression(&expr.source);
+      for arg in &expr.arguments {
+        self.visit_expression(arg);
+      }
+    }
+  }
+
+  let mut collector = Collector::default();
+  collector.visit_program(program);
+  collector.spans
+}
+
+/// Replace arguments of dynamic imports with string literals of the ids they are resolved to, by spans of the
+/// import expressions. Then they are scanned and bundled just like `import('./foo.js')`.
+pub fn rewrite_dynamic_import_exprs<'ast>(
+  program: &mut ast::Program<'ast>,
+  alloc: &'ast Allocator,
+  resolved_ids: &FxHashMap<Span, String>,
+) {
+  struct Rewriter<'me, 'ast> {
+    snippet: AstSnippet<'ast>,
+    resolved_ids: &'me FxHashMap<Span, String>,
+  }
+
+  impl<'me, 'ast> VisitMut<'ast> for Rewriter<'me, 'ast> {
+    fn visit_import_expression(&mut self, expr: &mut ast::ImportExpression<'ast>) {
+      if let Some(id) = self.resolved_ids.get(&expr.span) {
+        expr.source = self.snippet.string_literal_expr(id);
+      }
+      self.visit_expression(&mut expr.source);
+      for arg in expr.arguments.iter_mut() {
+        self.visit_expression(arg);
+      }
+    }
+  }
+
+  Rewriter { snippet: AstSnippet::new(alloc), resolved_ids }.visit_program(program);
+}
@@ -0,0 +1,464 @@
+use std::path::{Path, PathBuf};
+
+use glob::Pattern;
+use oxc::{
+  allocator::{self, Allocator},
+  ast::{ast, Visit, VisitMut},
+  span::{GetSpan, Span, SPAN},
+  syntax::operator::BinaryOperator,
+};
+use rolldown_fs::FileSystem;
+use rolldown_oxc_utils::{AstSnippet, Dummy, IntoIn};
+use sugar_path::SugarPath;
+
+/// Name of the parameter of the generated switch helper.
+const PATH_PARAM: &str = "path";
+
+/// Expand `import()` and `require()` with non-literal specifiers, such as `import(`./locales/${lang}.js`)` and
+/// `require('./pages/' + name + '.js')`, into a switch helper over the files matching the implied glob. Enabled by
+/// `InputOptions.dynamic_import_vars`.
+///
+/// ```js
+/// ((path) => {
+///   switch (path) {
+///     case './locales/en.js': return import('./locales/en.js');
+///     default: return Promise.reject(new Error('Unknown variable dynamic import: ' + path));
+///   }
+/// })(`./locales/${lang}.js`)
+/// ```
+///
+/// Every match becomes an ordinary import record once the program is scanned. Like
+/// `@rollup/plugin-dynamic-import-vars`, specifiers are expanded only if they start with `./` or `../` and end with a
+/// literal file extension, and imports of sibling files, like `./${name}.js` or `../${name}.js`, need a file name
+/// pattern such as `./page-${name}.js`. Variables only match within a path segment. The extension has to be one of
+/// JavaScript, since every match is loaded as a JavaScript module and rolldown has no loader for others, like JSON.
+/// Other specifiers and those with no matching files are left as they are.
+pub fn expand_dynamic_import_vars<'ast>(
+  program: &mut ast::Program<'ast>,
+  alloc: &'ast Allocator,
+  fs: &dyn FileSystem,
+  importer: &Path,
+) {
+  let Some(dir) = importer.parent() else {
+    return;
+  };
+  let expand_require = !binds_require(program);
+  let mut expander =
+    Expander { snippet: AstSnippet::new(alloc), fs, dir, importer, expand_require };
+  expander.visit_program(program);
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+  Import,
+  Require,
+}
+
+struct Expander<'me, 'ast> {
+  snippet: AstSnippet<'ast>,
+  fs: &'me dyn FileSystem,
+  dir: &'me Path,
+  importer: &'me Path,
+  /// `require` calls are only expanded if the module doesn't declare its own `require`.
+  expand_require: bool,
+}
+
+impl<'me, 'ast> VisitMut<'ast> for Expander<'me, 'ast> {
+  fn visit_expression(&mut self, expr: &mut ast::Expression<'ast>) {
+    self.try_expand(expr);
+    self.visit_expression_match(expr);
+  }
+}
+
+impl<'me, 'ast> Expander<'me, 'ast> {
+  fn try_expand(&self, expr: &mut ast::Expression<'ast>) {
+    let (kind, specifier) = match expr {
+      ast::Expression::ImportExpression(import_expr) if import_expr.arguments.is_empty() => {
+        (Kind::Import, &mut import_expr.source)
+      }
+      ast::Expression::CallExpression(call_expr) if self.expand_require => {
+        let ast::CallExpression { callee, arguments, .. } = &mut **call_expr;
+        match (&*callee, arguments.as_mut_slice()) {
+          (ast::Expression::Identifier(ident), [ast::Argument::Expression(arg)])
+            if ident.name == "require" =>
+          {
+            (Kind::Require, arg)
+          }
+          _ => return,
+        }
+      }
+      _ => return,
+    };
+    if matches!(specifier, ast::Expression::StringLiteral(_)) {
+      return;
+    }
+    let Some(glob) = SpecifierGlob::from_expr(specifier) else {
+      return;
+    };
+    let matches = glob.find_matches(self.fs, self.dir, self.importer);
+    if matches.is_empty() {
+      return;
+    }
+    let specifier = std::mem::replace(specifier, self.snippet.string_literal_expr(""));
+    *expr = self.switch_helper_call_expr(kind, expr.span(), &matches, specifier);
+  }
+
+  /// `((path) => { switch (path) { ... } })([specifier])`
+  ///
+  /// Generated `import()`s and `require()`s take the span of the original expression. Their import records are
+  /// told apart by their specifiers, see `ImportRecordKey`.
+  fn switch_helper_call_expr(
+    &self,
+    kind: Kind,
+    span: Span,
+    matches: &[String],
+    specifier: ast::Expression<'ast>,
+  ) -> ast::Expression<'ast> {
+    let alloc = self.snippet.alloc;
+    let mut cases = allocator::Vec::new_in(alloc);
+    for matched in matches {
+      let source = self.snippet.string_literal_expr(matched);
+      let load = match kind {
+        Kind::Import => ast::Expression::ImportExpression(
+          ast::ImportExpression { span, source, arguments: allocator::Vec::new_in(alloc) }
+            .into_in(alloc),
+        ),
+        Kind::Require => {
+          let mut call_expr = self.snippet.call_expr("require");
+          call_expr.span = span;
+          call_expr.arguments.push(ast::Argument::Expression(source));
+          ast::Expression::CallExpression(call_expr.into_in(alloc))
+        }
+      };
+      let mut consequent = allocator::Vec::new_in(alloc);
+      consequent.push(self.return_stmt(load));
+      cases.push(ast::SwitchCase {
+        span: SPAN,
+        test: Some(self.snippet.string_literal_expr(matched)),
+        consequent,
+      });
+    }
+
+    // new Error('Unknown variable dynamic import: ' + path)
+    let message = match kind {
+      Kind::Import => "Unknown variable dynamic import: ",
+      Kind::Require => "Unknown variable dynamic require: ",
+    };
+    let mut error_args = allocator::Vec::new_in(alloc);
+    error_args.push(ast::Argument::Expression(ast::Expression::BinaryExpression(
+      ast::BinaryExpression {
+        span: SPAN,
+        left: self.snippet.string_literal_expr(message),
+        operator: BinaryOperator::Addition,
+        right: self.snippet.id_ref_expr(PATH_PARAM, SPAN),
+      }
+      .into_in(alloc),
+    )));
+    let error = ast::Expression::NewExpression(
+      ast::NewExpression {
+        span: SPAN,
+        callee: self.snippet.id_ref_expr("Error", SPAN),
+        arguments: error_args,
+        type_parameters: None,
+      }
+      .into_in(alloc),
+    );
+    let default_stmt = match kind {
+      Kind::Import => self.return_stmt(self.snippet.callee_call_expr_with_arg_expr(
+        self.snippet.static_member_expr_expr(self.snippet.id_ref_expr("Promise", SPAN), "reject"),
+        error,
+      )),
+      Kind::Require => ast::Statement::ThrowStatement(
+        ast::ThrowStatement { span: SPAN, argument: error }.into_in(alloc),
+      ),
+    };
+    let mut consequent = allocator::Vec::new_in(alloc);
+    consequent.push(default_stmt);
+    cases.push(ast::SwitchCase { span: SPAN, test: None, consequent });
+
+    // (path) => { switch (path) { ... } }
+    let mut statements = allocator::Vec::new_in(alloc);
+    statements.push(ast::Statement::SwitchStatement(
+      ast::SwitchStatement {
+        span: SPAN,
+        discriminant: self.snippet.id_ref_expr(PATH_PARAM, SPAN),
+        cases,
+      }
+      .into_in(alloc),
+    ));
+    let mut arrow_expr = ast::ArrowFunctionExpression {
+      body: ast::FunctionBody { statements, ..Dummy::dummy(alloc) }.into_in(alloc),
+      ..Dummy::dummy(alloc)
+    };
+    arrow_expr.params.kind = ast::FormalParameterKind::ArrowFormalParameters;
+    arrow_expr.params.items.push(ast::FormalParameter {
+      pattern: ast::BindingPattern {
+        kind: ast::BindingPatternKind::BindingIdentifier(
+          self.snippet.id(PATH_PARAM, SPAN).into_in(alloc),
+        ),
+        ..Dummy::dummy(alloc)
+      },
+      ..Dummy::dummy(alloc)
+    });
+    let callee = ast::Expression::ParenthesizedExpression(
+      ast::ParenthesizedExpression {
+        expression: ast::Expression::ArrowFunctionExpression(arrow_expr.into_in(alloc)),
+        ..Dummy::dummy(alloc)
+      }
+      .into_in(alloc),
+    );
+    self.snippet.callee_call_expr_with_arg_expr(callee, specifier)
+  }
+
+  fn return_stmt(&self, argument: ast::Expression<'ast>) -> ast::Statement<'ast> {
+    ast::Statement::ReturnStatement(
+      ast::ReturnStatement { span: SPAN, argument: Some(argument) }.into_in(self.snippet.alloc),
+    )
+  }
+}
+
+enum GlobPart {
+  Literal(String),
+  Wildcard,
+}
+
+/// The glob implied by a non-literal specifier, such as `./locales/*.js` for `` `./locales/${lang}.js` ``.
+struct SpecifierGlob {
+  /// Parts of each path segment
+  segments: Vec<Vec<GlobPart>>,
+}
+
+impl SpecifierGlob {
+  fn from_expr(expr: &ast::Expression) -> Option<Self> {
+    let mut parts = vec![];
+    collect_glob_parts(expr, &mut parts)?;
+
+    let mut segments = vec![vec![]];
+    for part in parts {
+      match part {
+        GlobPart::Literal(literal) => {
+          let mut pieces = literal.split('/');
+          if let Some(first) = pieces.next().filter(|first| !first.is_empty()) {
+            segments.last_mut()?.push(GlobPart::Literal(first.to_string()));
+          }
+          for piece in pieces {
+            segments.push(vec![]);
+            if !piece.is_empty() {
+              segments.last_mut()?.push(GlobPart::Literal(piece.to_string()));
+            }
+          }
+        }
+        // Adjacent variables are the same as a single one
+        GlobPart::Wildcard => {
+          let segment = segments.last_mut()?;
+          if !matches!(segment.last(), Some(GlobPart::Wildcard)) {
+            segment.push(GlobPart::Wildcard);
+          }
+        }
+      }
+    }
+
+    let glob = Self { segments };
+    let is_relative = matches!(glob.literal_segment(0), Some("." | ".."));
+    (is_relative && glob.has_js_extension() && !glob.is_whole_dir()).then_some(glob)
+  }
+
+  /// Whether the last segment ends with a literal JavaScript file extension, like `${name}.js`.
+  fn has_js_extension(&self) -> bool {
+    match self.segments.last().and_then(|segment| segment.last()) {
+      Some(GlobPart::Literal(literal)) => literal
+        .rsplit_once('.')
+        .is_some_and(|(_, extension)| matches!(extension, "js" | "mjs" | "cjs" | "jsx")),
+      _ => false,
+    }
+  }
+
+  /// Whether the glob starts with a variable right after `./` or `../`, like `./${name}.js`, which would match
+  /// every file of the directory.
+  fn is_whole_dir(&self) -> bool {
+    let dirs = (0..self.segments.len())
+      .take_while(|index| matches!(self.literal_segment(*index), Some("." | "..")))
+      .count();
+    dirs + 1 == self.segments.len()
+      && matches!(self.segments[dirs].first(), Some(GlobPart::Wildcard))
+  }
+
+  fn literal_segment(&self, index: usize) -> Option<&str> {
+    match self.segments.get(index)?.as_slice() {
+      [GlobPart::Literal(literal)] => Some(literal),
+      _ => None,
+    }
+  }
+
+  /// Specifiers of files matching the glob relative to `dir`, sorted. `importer` itself is excluded. Paths are
+  /// normalized as they're joined, since file systems like `MemoryFileSystem` look up paths as they are.
+  fn find_matches(&self, fs: &dyn FileSystem, dir: &Path, importer: &Path) -> Vec<String> {
+    // Paths matching the segments so far, along with their specifiers
+    let mut candidates: Vec<(PathBuf, String)> = vec![(dir.to_path_buf(), String::new())];
+    for (index, segment) in self.segments.iter().enumerate() {
+      let is_first = index == 0;
+      let is_last = index == self.segments.len() - 1;
+      let join = |specifier: &str, name: &str| {
+        if is_first {
+          name.to_string()
+        } else {
+          format!("{specifier}/{name}")
+        }
+      };
+      candidates =
+        if let Some(name) = self.literal_segment(index).or(segment.is_empty().then_some("")) {
+          candidates
+            .into_iter()
+            .map(|(path, specifier)| {
+              (path.join(name).normalize().into_owned(), join(&specifier, name))
+            })
+            .collect()
+        } else {
+          let Some(pattern) = segment_pattern(segment) else {
+            return vec![];
+          };
+          let pattern = &pattern;
+          candidates
+            .into_iter()
+            .flat_map(|(path, specifier)| {
+              fs.read_dir(&path).unwrap_or_default().into_iter().filter_map(move |entry| {
+                let name = entry.file_name()?.to_str()?.to_string();
+                pattern.matches(&name).then(|| (entry, format!("{specifier}/{name}")))
+              })
+            })
+            .collect()
+        };
+      if is_last {
+        candidates.retain(|(path, _)| fs.is_file(path) && path != importer);
+      }
+    }
+    let mut matches = candidates.into_iter().map(|(_, specifier)| specifier).collect::<Vec<_>>();
+    matches.sort_unstable();
+    matches
+  }
+}
+
+fn segment_pattern(segment: &[GlobPart]) -> Option<Pattern> {
+  let pattern = segment
+    .iter()
+    .map(|part| match part {
+      GlobPart::Literal(literal) => Pattern::escape(literal),
+      GlobPart::Wildcard => "*".to_string(),
+    })
+    .collect::<String>();
+  Pattern::new(&pattern).ok()
+}
+
+fn collect_glob_parts(expr: &ast::Expression, parts: &mut Vec<GlobPart>) -> Option<()> {
+  match expr {
+    ast::Expression::StringLiteral(lit) => parts.push(GlobPart::Literal(lit.value.to_string())),
+    ast::Expression::TemplateLiteral(template) => {
+      for (index, quasi) in template.quasis.iter().enumerate() {
+        parts.push(GlobPart::Literal(quasi.value.cooked.as_ref()?.to_string()));
+        if index < template.expressions.len() {
+          parts.push(GlobPart::Wildcard);
+        }
+      }
+    }
+    ast::Expression::BinaryExpression(binary) if binary.operator == BinaryOperator::Addition => {
+      collect_glob_parts(&binary.left, parts)?;
+      collect_glob_parts(&binary.right, parts)?;
+    }
+    ast::Expression::ParenthesizedExpression(paren) => {
+      collect_glob_parts(&paren.expression, parts)?;
+    }
+    _ => parts.push(GlobPart::Wildcard),
+  }
+  Some(())
+}
+
+/// Whether the program declares a binding named `require`, which isn't the CommonJS one.
+fn binds_require(program: &ast::Program<'_>) -> bool {
+  #[derive(Default)]
+  struct Finder {
+    found: bool,
+  }
+
+  impl<'ast> Visit<'ast> for Finder {
+    fn visit_binding_identifier(&mut self, ident: &ast::BindingIdentifier) {
+      self.found |= ident.name == "require";
+    }
+  }
+
+  let mut finder = Finder::default();
+  finder.visit_program(program);
+  finder.found
+}
+
+#[cfg(test)]
+mod test {
+  use oxc::{ast::ast, span::SourceType};
+  use rolldown_oxc_utils::OxcCompiler;
+
+  use super::{GlobPart, SpecifierGlob};
+
+  /// The glob implied by the specifier expression `code`, like `./locales/*.js`.
+  fn glob(code: &str) -> Option<String> {
+    let program = OxcCompiler::parse(code, SourceType::default());
+    let Some(ast::Statement::ExpressionStatement(stmt)) = program.program().body.first() else {
+      panic!("{code} should be an expression");
+    };
+    let glob = SpecifierGlob::from_expr(&stmt.expression)?;
+    let segments = glob.segments.iter().map(|segment| {
+      segment
+        .iter()
+        .map(|part| match part {
+          GlobPart::Literal(literal) => literal.as_str(),
+          GlobPart::Wildcard => "*",
+        })
+        .collect::<String>()
+    });
+    Some(segments.collect::<Vec<_>>().join("/"))
+  }
+
+  #[test]
+  fn specifiers_must_be_relative() {
+    assert_eq!(glob("`./locales/${lang}.js`").as_deref(), Some("./locales/*.js"));
+    assert_eq!(glob("'../pages/' + name + '.js'").as_deref(), Some("../pages/*.js"));
+    assert_eq!(glob("`locales/${lang}.js`"), None);
+    assert_eq!(glob("`/locales/${lang}.js`"), None);
+    assert_eq!(glob("`${dir}/index.js`"), None);
+  }
+
+  #[test]
+  fn adjacent_variables_are_a_single_wildcard() {
+    assert_eq!(glob("`./locales/${lang}${region}.js`").as_deref(), Some("./locales/*.js"));
+    assert_eq!(glob("'./locales/' + lang + region + '.js'").as_deref(), Some("./locales/*.js"));
+    assert_eq!(glob("`./${lang}-${region}/index.js`").as_deref(), Some("./*-*/index.js"));
+    assert_eq!(glob("`./${lang}${region}.js`"), None);
+  }
+
+  #[test]
+  fn specifiers_must_end_with_a_file_extension() {
+    assert_eq!(glob("`./locales/${lang}`"), None);
+    assert_eq!(glob("`./locales/${lang}.`"), None);
+    assert_eq!(glob("`./locales/${lang}.${ext}`"), None);
+    assert_eq!(glob("`./locales/${lang}/index`"), None);
+    assert_eq!(glob("`./locales/${lang}/index.js`").as_deref(), Some("./locales/*/index.js"));
+  }
+
+  #[test]
+  fn specifiers_must_end_with_a_javascript_extension() {
+    assert_eq!(glob("`./locales/${lang}.json`"), None);
+    assert_eq!(glob("`./styles/${theme}.css`"), None);
+    assert_eq!(glob("`./locales/${lang}.mjs`").as_deref(), Some("./locales/*.mjs"));
+    assert_eq!(glob("`./pages/${name}.jsx`").as_deref(), Some("./pages/*.jsx"));
+  }
+
+  #[test]
+  fn files_next_to_the_importer_or_its_parents_need_a_file_name_pattern() {
+    assert_eq!(glob("`./${name}.js`"), None);
+    assert_eq!(glob("`../${name}`"), None);
+    assert_eq!(glob("`../${name}.js`"), None);
+    assert_eq!(glob("`../../${name}.js`"), None);
+    assert_eq!(glob("`./page-${name}.js`").as_deref(), Some("./page-*.js"));
+    assert_eq!(glob("`../page-${name}.js`").as_deref(), Some("../page-*.js"));
+    assert_eq!(glob("`../pages/${name}.js`").as_deref(), Some("../pages/*.js"));
+    assert_eq!(glob("`../../${dir}/${name}.js`").as_deref(), Some("../../*/*.js"));
+  }
+}
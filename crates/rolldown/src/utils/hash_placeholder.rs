@@ -0,0 +1,70 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use rustc_hash::FxHashMap;
+
+// Placeholders look like `!~{001}~`. They are unlikely to appear in real code and are valid in file names.
+const PLACEHOLDER_PREFIX: &str = "!~{";
+const PLACEHOLDER_SUFFIX: &str = "}~";
+const PLACEHOLDER_CHARS: &[u8] =
+  b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_$";
+
+static PLACEHOLDER_REGEX: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"!~\{[0-9a-zA-Z_$]+\}~").expect("Init PLACEHOLDER_REGEX failed"));
+
+/// The url-safe base64 encoded xxh3-128 hash has 22 characters.
+pub const MAX_HASH_LENGTH: usize = 22;
+
+fn to_base64(mut index: usize) -> String {
+  let mut digits = vec![];
+  loop {
+    digits.push(PLACEHOLDER_CHARS[index % PLACEHOLDER_CHARS.len()]);
+    index /= PLACEHOLDER_CHARS.len();
+    if index == 0 {
+      break;
+    }
+  }
+  digits.reverse();
+  String::from_utf8(digits).expect("placeholder chars are ascii")
+}
+
+/// The minimal hash length that is able to hold placeholders for `count` chunks.
+pub fn min_hash_length(count: usize) -> usize {
+  PLACEHOLDER_PREFIX.len() + to_base64(count.saturating_sub(1)).len() + PLACEHOLDER_SUFFIX.len()
+}
+
+/// The placeholder has exactly `len` characters, so replacing it with the real hash won't break the sourcemap.
+/// Callers need to make sure `len` is not less than `min_hash_length`.
+pub fn generate_hash_placeholder(index: usize, len: usize) -> String {
+  let digits = to_base64(index);
+  let padding = len - PLACEHOLDER_PREFIX.len() - PLACEHOLDER_SUFFIX.len() - digits.len();
+  format!("{PLACEHOLDER_PREFIX}{}{digits}{PLACEHOLDER_SUFFIX}", "0".repeat(padding))
+}
+
+pub fn find_hash_placeholders(code: &str) -> impl Iterator<Item = &str> {
+  PLACEHOLDER_REGEX.find_iter(code).map(|m| m.as_str())
+}
+
+pub fn replace_hash_placeholders(
+  code: &str,
+  hash_by_placeholder: &FxHashMap<String, String>,
+) -> String {
+  PLACEHOLDER_REGEX
+    .replace_all(code, |caps: &Captures| {
+      let placeholder = &caps[0];
+      hash_by_placeholder.get(placeholder).map_or_else(|| placeholder.to_string(), Clone::clone)
+    })
+    .into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn placeholder_has_the_requested_length() {
+    assert_eq!(generate_hash_placeholder(0, 8), "!~{000}~");
+    assert_eq!(generate_hash_placeholder(65, 8), "!~{011}~");
+    assert_eq!(min_hash_length(1), 6);
+    assert_eq!(min_hash_length(65), 7);
+  }
+}
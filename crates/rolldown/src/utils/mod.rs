@@ -4,9 +4,14 @@ use rolldown_oxc_utils::{AstSnippet, OxcProgram};
 
 use super::finalizer::{Finalizer, FinalizerContext};
 
+pub mod context_resolver;
+pub mod dynamic_import_exprs;
+pub mod dynamic_import_vars;
+pub mod hash_placeholder;
 pub mod load_source;
 pub mod normalize_options;
 pub mod renamer;
+pub mod render_chunk_addons;
 pub mod render_chunks;
 pub mod render_normal_module;
 pub mod resolve_id;
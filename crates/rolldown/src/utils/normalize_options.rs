@@ -1,10 +1,7 @@
+use rolldown_common::{NormalizedInputOptions, NormalizedOutputOptions, SourceMapType};
+use rolldown_error::BuildError;
 use rolldown_resolver::EnforceExtension;
 
-use crate::options::{
-  normalized_input_options::NormalizedInputOptions,
-  normalized_output_options::NormalizedOutputOptions, output_options::SourceMapType,
-};
-
 #[allow(clippy::struct_field_names)]
 pub struct NormalizeOptionsReturn {
   pub input_options: NormalizedInputOptions,
@@ -15,7 +12,7 @@ pub struct NormalizeOptionsReturn {
 pub fn normalize_options(
   mut raw_input: crate::InputOptions,
   raw_output: crate::OutputOptions,
-) -> NormalizeOptionsReturn {
+) -> Result<NormalizeOptionsReturn, BuildError> {
   let raw_resolve = std::mem::take(&mut raw_input.resolve).unwrap_or_default();
 
   // So far we align the default behavior with esbuild that target browser platform;
@@ -67,21 +64,47 @@ pub fn normalize_options(
       .cwd
       .unwrap_or_else(|| std::env::current_dir().expect("Failed to get current dir")),
     external: raw_input.external.unwrap_or_default(),
+    dynamic_import_vars: raw_input.dynamic_import_vars.unwrap_or(false),
+    make_absolute_externals_relative: raw_input.make_absolute_externals_relative.unwrap_or(false),
     treeshake: raw_input.treeshake.unwrap_or(true),
   };
 
   // Normalize output options
 
+  if raw_output.dir.is_some() && raw_output.file.is_some() {
+    return Err(BuildError::dir_with_file_option());
+  }
+
   let output_options = NormalizedOutputOptions {
     entry_file_names: raw_output.entry_file_names.unwrap_or_else(|| "[name].js".to_string()).into(),
     chunk_file_names: raw_output
       .chunk_file_names
       .unwrap_or_else(|| "[name]-[hash].js".to_string())
       .into(),
-    dir: "dist".to_string(),
+    asset_file_names: raw_output
+      .asset_file_names
+      .unwrap_or_else(|| "assets/[name]-[hash][extname]".to_string())
+      .into(),
+    sanitize_file_name: raw_output.sanitize_file_name.unwrap_or_default(),
+    dir: raw_output.dir.unwrap_or_else(|| "dist".to_string()),
+    file: raw_output.file,
     format: raw_output.format.unwrap_or(crate::OutputFormat::Esm),
+    exports: raw_output.exports.unwrap_or(crate::OutputExports::Auto),
     sourcemap: raw_output.sourcemap.unwrap_or(SourceMapType::Hidden),
+    name: raw_output.name,
+    globals: raw_output.globals.unwrap_or_default(),
+    paths: raw_output.paths,
+    banner: raw_output.banner,
+    footer: raw_output.footer,
+    intro: raw_output.intro,
+    outro: raw_output.outro,
+    manual_chunks: raw_output.manual_chunks,
+    advanced_chunks: raw_output.advanced_chunks,
+    experimental_min_chunk_size: raw_output.experimental_min_chunk_size.unwrap_or(0),
+    inline_dynamic_imports: raw_output.inline_dynamic_imports.unwrap_or(false),
+    preserve_modules: raw_output.preserve_modules.unwrap_or(false),
+    preserve_modules_root: raw_output.preserve_modules_root,
   };
 
-  NormalizeOptionsReturn { input_options, output_options, resolve_options }
+  Ok(NormalizeOptionsReturn { input_options, output_options, resolve_options })
 }
@@ -27,6 +27,19 @@ impl<'name> Renamer<'name> {
     self.used_canonical_names.insert(name);
   }
 
+  /// Create a conflict-free top-level name that doesn't belong to any symbol.
+  pub fn create_conflictless_top_level_name(&mut self, hint: &str) -> Rstr {
+    let hint: Rstr = hint.into();
+    let mut count = 0;
+    let mut candidate_name = hint.clone();
+    while self.used_canonical_names.contains(&candidate_name) {
+      count += 1;
+      candidate_name = format!("{hint}${count}").into();
+    }
+    self.used_canonical_names.insert(Cow::Owned(candidate_name.clone()));
+    candidate_name
+  }
+
   pub fn add_top_level_symbol(&mut self, symbol_ref: SymbolRef) {
     let canonical_ref = self.symbols.par_canonical_ref_for(symbol_ref);
     let original_name: Cow<'_, Rstr> =
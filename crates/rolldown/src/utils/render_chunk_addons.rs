@@ -0,0 +1,46 @@
+use rolldown_common::{AddonOutputOption, RenderedChunk};
+use rolldown_error::BuildError;
+use rolldown_plugin::SharedPluginDriver;
+
+use rolldown_common::NormalizedOutputOptions;
+
+/// `banner` and `footer` are placed outside of the format wrapper, while `intro` and `outro` are placed inside.
+#[derive(Debug, Default)]
+pub struct ChunkAddons {
+  pub banner: Option<String>,
+  pub footer: Option<String>,
+  pub intro: Option<String>,
+  pub outro: Option<String>,
+}
+
+pub async fn render_chunk_addons(
+  plugin_driver: &SharedPluginDriver,
+  output_options: &NormalizedOutputOptions,
+  chunk: &RenderedChunk,
+) -> Result<ChunkAddons, BuildError> {
+  Ok(ChunkAddons {
+    banner: join_addons(output_options.banner.as_ref(), plugin_driver.banner(chunk).await?, chunk)
+      .await?,
+    footer: join_addons(output_options.footer.as_ref(), plugin_driver.footer(chunk).await?, chunk)
+      .await?,
+    intro: join_addons(output_options.intro.as_ref(), plugin_driver.intro(chunk).await?, chunk)
+      .await?,
+    outro: join_addons(output_options.outro.as_ref(), plugin_driver.outro(chunk).await?, chunk)
+      .await?,
+  })
+}
+
+/// The addon from options goes first, followed by the ones from plugins. Empty addons are ignored.
+async fn join_addons(
+  option: Option<&AddonOutputOption>,
+  from_plugins: Vec<String>,
+  chunk: &RenderedChunk,
+) -> Result<Option<String>, BuildError> {
+  let mut addons = vec![];
+  if let Some(option) = option {
+    addons.extend(option.call(chunk).await?);
+  }
+  addons.extend(from_plugins);
+  addons.retain(|addon| !addon.is_empty());
+  Ok((!addons.is_empty()).then(|| addons.join("\n")))
+}
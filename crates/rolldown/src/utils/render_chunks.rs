@@ -1,25 +1,41 @@
 use rolldown_common::{IntoBatchedResult, RenderedChunk};
+use rolldown_error::BuildError;
 use rolldown_plugin::{HookRenderChunkArgs, SharedPluginDriver};
-use rolldown_sourcemap::SourceMap;
+use rolldown_sourcemap::{collapse_sourcemaps, SourceMap};
 use rolldown_utils::block_on_spawn_all;
 
 use crate::error::BatchedErrors;
 
+/// Call `render_chunk` hooks on rendered chunks. Returns the chunks, along with warnings about plugins that break
+/// sourcemaps.
 pub async fn render_chunks<'a>(
   plugin_driver: &SharedPluginDriver,
   chunks: impl Iterator<Item = (String, Option<SourceMap>, RenderedChunk)>,
-) -> Result<Vec<(String, Option<SourceMap>, RenderedChunk)>, BatchedErrors> {
-  // TODO support `render_chunk` hook return map
+) -> Result<(Vec<(String, Option<SourceMap>, RenderedChunk)>, Vec<BuildError>), BatchedErrors> {
   let result = block_on_spawn_all(chunks.map(|(content, map, rendered_chunk)| async move {
     tracing::info!("render_chunks");
-    match plugin_driver
+    let (content, sourcemap_chain, plugins_without_map) = plugin_driver
       .render_chunk(HookRenderChunkArgs { code: content, chunk: &rendered_chunk })
-      .await
-    {
-      Ok(value) => Ok((value, map, rendered_chunk)),
-      Err(e) => Err(e),
-    }
+      .await?;
+    // Maps returned by `render_chunk` map the code rendered by previous plugins, so they are collapsed onto the map of the chunk.
+    // Without a map of the chunk, sourcemaps are disabled and returned maps are ignored.
+    let warnings = if map.is_some() {
+      plugins_without_map
+        .into_iter()
+        .map(|plugin| BuildError::sourcemap_broken(plugin, rendered_chunk.file_name.as_str()))
+        .collect()
+    } else {
+      vec![]
+    };
+    let map = match map {
+      Some(map) if !sourcemap_chain.is_empty() => {
+        collapse_sourcemaps(std::iter::once(&map).chain(&sourcemap_chain).collect())?
+      }
+      map => map,
+    };
+    Ok(((content, map, rendered_chunk), warnings))
   }));
 
-  result.into_batched_result()
+  let (chunks, warnings): (Vec<_>, Vec<Vec<_>>) = result.into_batched_result()?.into_iter().unzip();
+  Ok((chunks, warnings.into_iter().flatten().collect()))
 }
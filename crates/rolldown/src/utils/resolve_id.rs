@@ -1,9 +1,12 @@
 use once_cell::sync::Lazy;
 use regex::Regex;
-use rolldown_common::{FilePath, ModuleType};
+use rolldown_common::{FilePath, ModuleType, NormalizedInputOptions};
 use rolldown_error::BuildError;
 use rolldown_fs::FileSystem;
-use rolldown_plugin::{HookResolveIdArgs, HookResolveIdExtraOptions, SharedPluginDriver};
+use rolldown_plugin::{
+  DynamicImportSpecifier, HookResolveDynamicImportArgs, HookResolveIdArgs,
+  HookResolveIdExtraOptions, PluginDriver,
+};
 use rolldown_resolver::Resolver;
 
 use crate::types::resolved_request_info::ResolvedRequestInfo;
@@ -16,19 +19,23 @@ static DATA_URL_REGEX: Lazy<Regex> =
 #[allow(clippy::no_effect_underscore_binding)]
 pub async fn resolve_id<T: FileSystem + Default>(
   resolver: &Resolver<T>,
-  plugin_driver: &SharedPluginDriver,
+  plugin_driver: &PluginDriver,
   request: &str,
   importer: Option<&FilePath>,
   options: HookResolveIdExtraOptions,
+  skipped_plugin: Option<usize>,
   _preserve_symlinks: bool,
 ) -> Result<ResolvedRequestInfo, BuildError> {
   // Run plugin resolve_id first, if it is None use internal resolver as fallback
   if let Some(r) = plugin_driver
-    .resolve_id(&HookResolveIdArgs {
-      importer: importer.map(std::convert::AsRef::as_ref),
-      source: request,
-      options,
-    })
+    .resolve_id(
+      &HookResolveIdArgs {
+        importer: importer.map(std::convert::AsRef::as_ref),
+        source: request,
+        options,
+      },
+      skipped_plugin,
+    )
     .await?
   {
     return Ok(ResolvedRequestInfo {
@@ -57,3 +64,65 @@ pub async fn resolve_id<T: FileSystem + Default>(
     is_external: false,
   })
 }
+
+/// Resolve an import and check `InputOptions.external` with both the unresolved and the resolved id.
+pub async fn resolve_id_with_external<T: FileSystem + Default>(
+  input_options: &NormalizedInputOptions,
+  resolver: &Resolver<T>,
+  plugin_driver: &PluginDriver,
+  request: &str,
+  importer: Option<&FilePath>,
+  options: HookResolveIdExtraOptions,
+  skipped_plugin: Option<usize>,
+) -> Result<ResolvedRequestInfo, BuildError> {
+  let importer_id = importer.map(|importer| importer.as_str().to_string());
+  // Check external with unresolved path
+  if input_options.external.call(request.to_string(), importer_id.clone(), false).await? {
+    return Ok(ResolvedRequestInfo {
+      path: request.to_string().into(),
+      module_type: ModuleType::Unknown,
+      is_external: true,
+    });
+  }
+
+  let mut info =
+    resolve_id(resolver, plugin_driver, request, importer, options, skipped_plugin, false).await?;
+
+  if !info.is_external {
+    // Check external with resolved path
+    info.is_external =
+      input_options.external.call(info.path.path.to_string(), importer_id, true).await?;
+  }
+  Ok(info)
+}
+
+/// Resolve a dynamic import with the `resolve_dynamic_import` hooks of plugins. Returns `None` if no plugin
+/// resolves it. `InputOptions.external` is checked with the resolved id.
+pub async fn resolve_dynamic_import(
+  input_options: &NormalizedInputOptions,
+  plugin_driver: &PluginDriver,
+  specifier: DynamicImportSpecifier<'_>,
+  importer: &FilePath,
+) -> Result<Option<ResolvedRequestInfo>, BuildError> {
+  let Some(r) = plugin_driver
+    .resolve_dynamic_import(&HookResolveDynamicImportArgs {
+      importer: importer.as_str(),
+      specifier,
+    })
+    .await?
+  else {
+    return Ok(None);
+  };
+  let mut info = ResolvedRequestInfo {
+    path: r.id.into(),
+    module_type: ModuleType::Unknown,
+    is_external: matches!(r.external, Some(true)),
+  };
+  if !info.is_external {
+    info.is_external = input_options
+      .external
+      .call(info.path.path.to_string(), Some(importer.as_str().to_string()), true)
+      .await?;
+  }
+  Ok(Some(info))
+}
@@ -1,4 +1,4 @@
-use rolldown_common::ResolvedPath;
+use rolldown_common::{ModuleType, ResolvedPath};
 use rolldown_plugin::{HookTransformArgs, PluginDriver};
 use rolldown_sourcemap::SourceMap;
 
@@ -7,11 +7,13 @@ use crate::error::BatchedErrors;
 pub async fn transform_source(
   plugin_driver: &PluginDriver,
   resolved_path: &ResolvedPath,
+  module_type: ModuleType,
   source: String,
   sourcemap_chain: &mut Vec<SourceMap>,
 ) -> Result<String, BatchedErrors> {
-  let (code, map_chain) =
-    plugin_driver.transform(&HookTransformArgs { id: &resolved_path.path, code: &source }).await?;
+  let (code, map_chain) = plugin_driver
+    .transform(&HookTransformArgs { id: &resolved_path.path, code: &source, module_type })
+    .await?;
 
   sourcemap_chain.extend(map_chain);
 
@@ -74,7 +74,7 @@ impl Case {
         [
           Cow::Owned(format!("## {}\n", asset.file_name())),
           "```js".into(),
-          Cow::Borrowed(asset.content().trim()),
+          String::from_utf8_lossy(asset.content_as_bytes()).trim().to_string().into(),
           "```".into(),
         ]
       })
@@ -4,14 +4,51 @@ use std::{
   process::Command,
 };
 
-use rolldown::{Bundler, External, InputOptions, OutputOptions, RolldownOutput};
+use rolldown::{Bundler, External, InputOptions, OutputFormat, OutputOptions, RolldownOutput};
 use rolldown_error::BuildError;
 use rolldown_testing::TestConfig;
+use sugar_path::SugarPath;
 
 fn default_test_input_item() -> rolldown_testing::InputItem {
   rolldown_testing::InputItem { name: "main".to_string(), import: "./main.js".to_string() }
 }
 
+/// Relative paths are resolved against the fixture, so that they match resolved ids of externals.
+fn normalize_external(fixture_path: &Path, external: Vec<String>) -> External {
+  External::ArrayString(
+    external
+      .into_iter()
+      .map(|id| {
+        if id.starts_with('.') {
+          fixture_path.join(id).normalize().to_string_lossy().to_string()
+        } else {
+          id
+        }
+      })
+      .collect(),
+  )
+}
+
+fn parse_output_format(format: &str) -> OutputFormat {
+  match format {
+    "esm" => OutputFormat::Esm,
+    "cjs" => OutputFormat::Cjs,
+    "iife" => OutputFormat::Iife,
+    "umd" => OutputFormat::Umd,
+    "system" => OutputFormat::System,
+    format => panic!("Unknown output format: {format}"),
+  }
+}
+
+/// Only ESM output needs the `.mjs` extension to be executed as ES module by node.
+fn output_extension(format: &str) -> &'static str {
+  if format == "esm" {
+    "mjs"
+  } else {
+    "js"
+  }
+}
+
 pub struct Fixture {
   fixture_path: PathBuf,
 }
@@ -54,11 +91,14 @@ impl Fixture {
       .input
       .unwrap_or_else(|| vec![default_test_input_item()])
       .iter()
-      .map(|item| format!("{}.mjs", item.name))
+      .map(|item| format!("{}.{}", item.name, output_extension(&test_config.output.format)))
       .map(|name| dist_folder.join(name))
       .collect::<Vec<_>>();
 
     let mut command = Command::new("node");
+    // SystemJS output can't be executed by node directly. The test script is responsible for loading it.
+    let compiled_entries =
+      if test_config.output.format == "system" { vec![] } else { compiled_entries };
     compiled_entries.iter().for_each(|entry| {
       command.arg("--import");
       if cfg!(target_os = "windows") {
@@ -97,6 +137,8 @@ impl Fixture {
       test_config.input.input = Some(vec![default_test_input_item()]);
     }
 
+    let extension = output_extension(&test_config.output.format);
+
     let mut bundler = Bundler::new(
       InputOptions {
         input: test_config
@@ -110,7 +152,15 @@ impl Fixture {
           })
           .unwrap(),
         cwd: Some(fixture_path.to_path_buf()),
-        external: Some(test_config.input.external.map(External::ArrayString).unwrap_or_default()),
+        external: Some(
+          test_config
+            .input
+            .external
+            .map(|external| normalize_external(fixture_path, external))
+            .unwrap_or_default(),
+        ),
+        dynamic_import_vars: test_config.input.dynamic_import_vars,
+        make_absolute_externals_relative: test_config.input.make_absolute_externals_relative,
         treeshake: Some(test_config.input.treeshake.unwrap_or(true)),
         resolve: test_config.input.resolve.map(|value| rolldown::ResolveOptions {
           alias: value.alias.map(|alias| alias.into_iter().collect::<Vec<_>>()),
@@ -125,11 +175,53 @@ impl Fixture {
         }),
       },
       OutputOptions {
-        entry_file_names: "[name].mjs".to_string().into(),
-        chunk_file_names: "[name].mjs".to_string().into(),
+        entry_file_names: Some(
+          test_config.output.entry_file_names.unwrap_or_else(|| format!("[name].{extension}")),
+        ),
+        chunk_file_names: Some(
+          test_config.output.chunk_file_names.unwrap_or_else(|| format!("[name].{extension}")),
+        ),
+        sanitize_file_name: test_config.output.sanitize_file_name.map(Into::into),
+        format: Some(parse_output_format(&test_config.output.format)),
+        exports: Some(test_config.output.export_mode.into()),
+        dir: test_config.output.dir,
+        file: test_config.output.file,
+        name: test_config.output.name,
+        globals: test_config.output.globals,
+        paths: test_config.output.paths.map(rolldown::PathsOutputOption::Map),
+        banner: test_config.output.banner.map(Into::into),
+        footer: test_config.output.footer.map(Into::into),
+        intro: test_config.output.intro.map(Into::into),
+        outro: test_config.output.outro.map(Into::into),
+        manual_chunks: test_config.output.manual_chunks.map(rolldown::ManualChunks::Map),
+        advanced_chunks: test_config.output.advanced_chunks.map(|options| {
+          rolldown::AdvancedChunksOptions {
+            min_size: options.min_size,
+            max_size: options.max_size,
+            min_share_count: options.min_share_count,
+            groups: options
+              .groups
+              .into_iter()
+              .map(|group| rolldown::MatchGroup {
+                name: group.name,
+                test: group
+                  .test
+                  .map(|test| rolldown::MatchGroupTest::Regex(regex::Regex::new(&test).unwrap())),
+                priority: group.priority,
+                min_size: group.min_size,
+                max_size: group.max_size,
+                min_share_count: group.min_share_count,
+              })
+              .collect(),
+          }
+        }),
+        experimental_min_chunk_size: test_config.output.experimental_min_chunk_size,
+        inline_dynamic_imports: test_config.output.inline_dynamic_imports,
+        preserve_modules: test_config.output.preserve_modules,
+        preserve_modules_root: test_config.output.preserve_modules_root,
         ..Default::default()
       },
-    );
+    )?;
 
     if fixture_path.join("dist").is_dir() {
       std::fs::remove_dir_all(fixture_path.join("dist")).unwrap();
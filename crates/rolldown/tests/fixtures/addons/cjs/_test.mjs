@@ -0,0 +1,7 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const { value } = require('./dist/main.js')
+
+assert.strictEqual(value, 'intro')
@@ -0,0 +1,3 @@
+import { value } from './foo.js'
+
+export { value }
@@ -0,0 +1 @@
+export const value = typeof intro === 'undefined' ? 'foo' : intro
@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import { a } from './dist/a.mjs'
+import { b } from './dist/b.mjs'
+import { c } from './dist/c.mjs'
+
+assert.strictEqual(a, 'aball')
+assert.strictEqual(b, 'abbcall')
+assert.strictEqual(c, 'bcall')
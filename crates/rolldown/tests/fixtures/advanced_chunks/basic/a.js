@@ -0,0 +1,4 @@
+import { ab } from './shared_ab.js'
+import { all } from './shared_all.js'
+
+export const a = ab + all
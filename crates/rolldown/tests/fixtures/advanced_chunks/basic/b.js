@@ -0,0 +1,5 @@
+import { ab } from './shared_ab.js'
+import { bc } from './shared_bc.js'
+import { all } from './shared_all.js'
+
+export const b = ab + bc + all
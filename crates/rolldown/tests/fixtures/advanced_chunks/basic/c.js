@@ -0,0 +1,4 @@
+import { bc } from './shared_bc.js'
+import { all } from './shared_all.js'
+
+export const c = bc + all
@@ -0,0 +1 @@
+export const bc = 'bc'
@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import { main } from './dist/main.mjs'
+
+assert.deepStrictEqual(main, [
+  'a module with a fairly long string',
+  'another module with a fairly long string',
+  1,
+])
@@ -0,0 +1 @@
+export const lib1 = 'a module with a fairly long string'
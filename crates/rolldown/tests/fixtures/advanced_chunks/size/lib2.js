@@ -0,0 +1 @@
+export const lib2 = 'another module with a fairly long string'
@@ -0,0 +1,5 @@
+import { lib1 } from './lib1.js'
+import { lib2 } from './lib2.js'
+import { tiny } from './tiny.js'
+
+export const main = [lib1, lib2, tiny]
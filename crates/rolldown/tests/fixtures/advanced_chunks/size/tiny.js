@@ -0,0 +1 @@
+export const tiny = 1
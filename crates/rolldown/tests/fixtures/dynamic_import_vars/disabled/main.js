@@ -0,0 +1 @@
+export const loadLocale = (lang) => import(`./locales/${lang}.js`)
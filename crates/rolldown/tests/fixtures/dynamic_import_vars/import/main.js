@@ -0,0 +1,7 @@
+import assert from 'node:assert'
+
+const loadLocale = (lang) => import(`./locales/${lang}.js`)
+
+assert.strictEqual((await loadLocale('en')).default, 'Hello')
+assert.strictEqual((await loadLocale('fr')).default, 'Bonjour')
+await assert.rejects(loadLocale('de'), /Unknown variable dynamic import: \.\/locales\/de\.js/)
@@ -0,0 +1,3 @@
+// Rolldown can't load JSON modules, so this is left as it is
+export const loadLocale = (lang) => import(`./locales/${lang}.json`)
+export const loadMessages = (lang) => import(`./messages/${lang}.js`)
@@ -0,0 +1,7 @@
+import assert from 'node:assert'
+
+const loadPage = (name) => require('./pages/' + name + '.js')
+
+assert.strictEqual(loadPage('home').title, 'Home')
+assert.strictEqual(loadPage('about').title, 'About')
+assert.throws(() => loadPage('missing'), /Unknown variable dynamic require: \.\/pages\/missing\.js/)
@@ -0,0 +1 @@
+module.exports = { title: 'Home' }
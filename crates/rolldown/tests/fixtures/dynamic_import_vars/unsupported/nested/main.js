@@ -0,0 +1,6 @@
+// Without a file extension
+export const loadPage = (name) => import(`../pages/${name}`)
+// Files next to the importer or in the parent directory need a file name pattern
+export const loadSibling = (name) => import(`./${name}.js`)
+export const loadParent = (name) => import(`../${name}.js`)
+export const loadParentWithoutExtension = (name) => import(`../${name}`)
@@ -0,0 +1,3 @@
+import { value } from './dep.js'
+
+export const main = value
@@ -0,0 +1,9 @@
+import assert from 'node:assert'
+import { a } from './dist/a.mjs'
+import { b } from './dist/b.mjs'
+import { c } from './dist/c.mjs'
+
+assert.strictEqual(a, 'ababc')
+assert.strictEqual(b, 'ababcbc')
+assert.strictEqual(c, 'abcbc')
+assert.strictEqual(globalThis.loadedBc, true)
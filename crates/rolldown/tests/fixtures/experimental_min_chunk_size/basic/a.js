@@ -0,0 +1,4 @@
+import { ab } from './shared_ab.js'
+import { abc } from './shared_abc.js'
+
+export const a = ab + abc
@@ -0,0 +1,5 @@
+import { ab } from './shared_ab.js'
+import { abc } from './shared_abc.js'
+import { bc } from './shared_bc.js'
+
+export const b = ab + abc + bc
@@ -0,0 +1,4 @@
+import { abc } from './shared_abc.js'
+import { bc } from './shared_bc.js'
+
+export const c = abc + bc
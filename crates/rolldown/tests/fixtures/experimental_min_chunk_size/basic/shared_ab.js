@@ -0,0 +1 @@
+export const ab = 'ab'
@@ -0,0 +1 @@
+export const abc = 'abc'
@@ -0,0 +1,3 @@
+globalThis.loadedBc = true
+
+export const bc = 'bc'
@@ -0,0 +1,10 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import path from 'node:path'
+import { a } from './dist/a.mjs'
+import { b } from './dist/b.mjs'
+
+assert.strictEqual(a(), 'shareda')
+assert.strictEqual(b(), 'sharedb')
+// The shared chunk is merged into an entry chunk
+assert.deepStrictEqual(fs.readdirSync(path.join(import.meta.dirname, 'dist')).sort(), ['a.mjs', 'b.mjs'])
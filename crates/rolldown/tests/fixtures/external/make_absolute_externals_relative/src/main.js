@@ -0,0 +1,4 @@
+import assert from 'node:assert'
+import { value } from '../sibling/index.js'
+
+assert.strictEqual(value, 'sibling')
@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import { join } from 'path-alias'
+
+assert.strictEqual(join('a', 'b'), 'a/b')
+
+import('path-alias').then((path) => {
+  assert.strictEqual(path.join, join)
+})
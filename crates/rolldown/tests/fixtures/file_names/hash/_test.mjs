@@ -0,0 +1,7 @@
+import assert from 'node:assert'
+import { main, lazy } from './dist/main.mjs'
+import { other } from './dist/other.mjs'
+
+assert.strictEqual(main, 'shared-main')
+assert.strictEqual(other, 'shared-other')
+assert.strictEqual((await lazy).lazy, 'shared-lazy')
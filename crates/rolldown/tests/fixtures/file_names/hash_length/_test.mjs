@@ -0,0 +1,5 @@
+import assert from 'node:assert'
+import { main, lazy } from './dist/main.mjs'
+
+assert.strictEqual(main, 'shared-main')
+assert.strictEqual((await lazy).lazy, 'shared-lazy')
@@ -0,0 +1,5 @@
+import { shared } from './shared.js'
+
+export const main = shared + '-main'
+
+export const lazy = import('./lazy.js')
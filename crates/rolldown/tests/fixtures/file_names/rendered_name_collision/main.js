@@ -0,0 +1,4 @@
+import assert from 'node:assert'
+import { shared } from './shared.js'
+
+assert.strictEqual(shared, 'shared')
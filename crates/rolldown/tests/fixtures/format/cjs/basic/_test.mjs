@@ -0,0 +1,11 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+import path from 'node:path'
+
+const lib = createRequire(import.meta.url)('./dist/main.js')
+assert.strictEqual(lib.__esModule, true)
+assert.strictEqual(lib.joined, path.join('a', 'b'))
+assert.strictEqual(lib.sep, path.sep)
+assert.strictEqual(lib.value, 0)
+lib.increase()
+assert.strictEqual(lib.value, 1)
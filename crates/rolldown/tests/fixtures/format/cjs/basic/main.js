@@ -0,0 +1,6 @@
+import path, { join } from 'node:path'
+import { value, increase } from './counter.js'
+
+export const joined = join('a', 'b')
+export const sep = path.sep
+export { value, increase }
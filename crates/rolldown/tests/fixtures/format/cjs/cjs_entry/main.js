@@ -0,0 +1,3 @@
+const { foo } = require('./foo.js')
+
+module.exports = { foo }
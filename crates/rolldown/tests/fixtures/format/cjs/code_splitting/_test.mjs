@@ -0,0 +1,13 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+
+const require = createRequire(import.meta.url)
+const main = require('./dist/main.js')
+const other = require('./dist/other.js')
+
+// Bindings imported from the shared chunk are live
+assert.strictEqual(main.getCount(), 0)
+assert.strictEqual(other.count, 0)
+main.increase()
+assert.strictEqual(main.getCount(), 1)
+assert.strictEqual(other.count, 1)
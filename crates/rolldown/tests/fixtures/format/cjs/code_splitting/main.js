@@ -0,0 +1,7 @@
+import { count, increase } from './shared.js'
+
+export function getCount() {
+  return count
+}
+
+export { increase }
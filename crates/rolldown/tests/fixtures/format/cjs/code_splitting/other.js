@@ -0,0 +1 @@
+export { count } from './shared.js'
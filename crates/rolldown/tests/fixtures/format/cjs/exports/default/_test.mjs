@@ -0,0 +1,5 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+
+const greet = createRequire(import.meta.url)('./dist/main.js')
+assert.strictEqual(greet(), 'hello')
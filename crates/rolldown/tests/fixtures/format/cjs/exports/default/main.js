@@ -0,0 +1 @@
+export default function greet() { return 'hello' }
@@ -0,0 +1,2 @@
+export const foo = 'foo'
+export default 'main'
@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import { createRequire } from 'node:module'
+
+const lib = createRequire(import.meta.url)('./dist/main.js')
+assert.deepStrictEqual(lib, {})
+assert.strictEqual(globalThis.value, 'none')
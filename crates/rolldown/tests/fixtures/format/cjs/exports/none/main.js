@@ -0,0 +1 @@
+globalThis.value = 'none'
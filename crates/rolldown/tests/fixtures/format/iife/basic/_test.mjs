@@ -0,0 +1,14 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import path from 'node:path'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(path.join(import.meta.dirname, 'dist/main.js'), 'utf-8')
+const context = vm.createContext({})
+vm.runInContext(code, context)
+
+const { myLib } = context
+assert.strictEqual(myLib.default, 'main')
+assert.strictEqual(myLib.value, 0)
+myLib.increase()
+assert.strictEqual(myLib.value, 1)
@@ -0,0 +1,4 @@
+import { value, increase } from './counter.js'
+
+export { value, increase }
+export default 'main'
@@ -0,0 +1,13 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import path from 'node:path'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(path.join(import.meta.dirname, 'dist/main.js'), 'utf-8')
+const logs = []
+const context = vm.createContext({ console: { log: (...args) => logs.push(args) }, Math, JSON })
+vm.runInContext(code, context)
+
+assert.strictEqual(context.myLib.result, 2)
+assert.strictEqual(context.myLib.json, '{"result":2}')
+assert.deepStrictEqual(logs, [['{"result":2}']])
@@ -0,0 +1,7 @@
+import { log } from 'logger'
+import * as mathNs from 'math'
+import defaultJson from 'json'
+
+export const result = mathNs.max(1, 2)
+export const json = defaultJson.stringify({ result })
+log(json)
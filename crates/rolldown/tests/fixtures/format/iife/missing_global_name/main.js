@@ -0,0 +1,3 @@
+import { foo } from 'external-foo'
+
+console.log(foo)
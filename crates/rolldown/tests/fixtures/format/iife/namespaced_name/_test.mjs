@@ -0,0 +1,11 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import path from 'node:path'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(path.join(import.meta.dirname, 'dist/main.js'), 'utf-8')
+const context = vm.createContext({ a: { existing: true } })
+vm.runInContext(code, context)
+
+assert.strictEqual(context.a.existing, true)
+assert.strictEqual(context.a.b.c.foo, 'foo')
@@ -0,0 +1,50 @@
+import fs from 'node:fs'
+import path from 'node:path'
+
+// A minimal SystemJS loader, which is just enough to execute the output in tests
+export function createLoader(dist, externals = {}) {
+  const records = new Map()
+
+  function load(id) {
+    if (id in externals) {
+      return { namespace: externals[id], setters: [] }
+    }
+    const file = path.join(dist, id)
+    if (records.has(file)) {
+      return records.get(file)
+    }
+    const record = { namespace: Object.create(null), setters: [] }
+    records.set(file, record)
+
+    let registration
+    const System = {
+      register: (deps, declare) => {
+        registration = { deps, declare }
+      },
+    }
+    new Function('System', fs.readFileSync(file, 'utf-8'))(System)
+
+    const _export = (name, value) => {
+      if (typeof name === 'object') {
+        Object.assign(record.namespace, name)
+      } else {
+        record.namespace[name] = value
+      }
+      record.setters.forEach((setter) => setter(record.namespace))
+      return value
+    }
+    const _module = { import: async (dep) => load(dep).namespace }
+    const { setters, execute } = registration.declare(_export, _module)
+    registration.deps.forEach((dep, i) => {
+      const dependency = load(dep)
+      if (setters[i]) {
+        dependency.setters.push(setters[i])
+        setters[i](dependency.namespace)
+      }
+    })
+    execute()
+    return record
+  }
+
+  return (id) => load(id).namespace
+}
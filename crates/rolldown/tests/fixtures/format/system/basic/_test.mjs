@@ -0,0 +1,13 @@
+import assert from 'node:assert'
+import path from 'node:path'
+import { createLoader } from '../_system_loader.mjs'
+
+const load = createLoader(path.join(import.meta.dirname, 'dist'), {
+  'node:path': await import('node:path'),
+})
+const lib = load('main.js')
+assert.strictEqual(lib.joined, path.join('a', 'b'))
+assert.strictEqual(lib.sep, path.sep)
+assert.strictEqual(lib.value, 0)
+lib.increase()
+assert.strictEqual(lib.value, 1)
@@ -0,0 +1,10 @@
+import assert from 'node:assert'
+import path from 'node:path'
+import { createLoader } from '../_system_loader.mjs'
+
+const load = createLoader(path.join(import.meta.dirname, 'dist'))
+const main = load('main.js')
+
+// Chunks importing each other can call hoisted functions before they are executed
+assert.strictEqual(main.getFactor(), 2)
+assert.strictEqual(main.result, 42)
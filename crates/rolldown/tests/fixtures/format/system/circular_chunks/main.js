@@ -0,0 +1,7 @@
+import { double } from './util.js'
+
+export function getFactor() {
+  return 2
+}
+
+export const result = double(21)
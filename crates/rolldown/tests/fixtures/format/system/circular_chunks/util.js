@@ -0,0 +1,8 @@
+import { getFactor } from './main.js'
+
+// `main.js` isn't executed yet, but its hoisted functions are available
+export const factor = getFactor()
+
+export function double(n) {
+  return n * factor
+}
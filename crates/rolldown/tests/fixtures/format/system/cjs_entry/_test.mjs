@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import path from 'node:path'
+import { createLoader } from '../_system_loader.mjs'
+
+const load = createLoader(path.join(import.meta.dirname, 'dist'))
+assert.deepStrictEqual(load('main.js').default, { foo: 'foo' })
@@ -0,0 +1,14 @@
+import assert from 'node:assert'
+import path from 'node:path'
+import { createLoader } from '../_system_loader.mjs'
+
+const load = createLoader(path.join(import.meta.dirname, 'dist'))
+const main = load('main.js')
+const other = load('other.js')
+
+// Bindings imported from the shared chunk are updated by setters
+assert.strictEqual(main.getCount(), 0)
+assert.strictEqual(other.count, 0)
+main.increase()
+assert.strictEqual(main.getCount(), 1)
+assert.strictEqual(other.count, 1)
@@ -0,0 +1,19 @@
+import assert from 'node:assert'
+import path from 'node:path'
+import { createLoader } from '../_system_loader.mjs'
+
+const load = createLoader(path.join(import.meta.dirname, 'dist'))
+const main = load('main.js')
+
+// Bindings assigned by destructuring are updated in the namespace
+main.swap()
+assert.strictEqual(main.a, 2)
+assert.strictEqual(main.b, 1)
+assert.strictEqual(main.first, 2)
+
+const values = { a: 3, c: 4 }
+assert.strictEqual(main.reset(values), values)
+assert.strictEqual(main.a, 3)
+assert.strictEqual(main.b, 0)
+assert.strictEqual(main.first, 3)
+assert.deepStrictEqual(main.rest, { c: 4 })
@@ -0,0 +1,13 @@
+export let a = 1
+export let b = 2
+export let rest
+
+export function swap() {
+  [a, b] = [b, a]
+}
+
+export function reset(values) {
+  return ({ a, b = 0, ...rest } = values)
+}
+
+export { a as first }
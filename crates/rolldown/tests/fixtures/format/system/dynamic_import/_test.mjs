@@ -0,0 +1,7 @@
+import assert from 'node:assert'
+import path from 'node:path'
+import { createLoader } from '../_system_loader.mjs'
+
+const load = createLoader(path.join(import.meta.dirname, 'dist'))
+const lazy = await load('main.js').load()
+assert.strictEqual(lazy.value, 'lazy')
@@ -0,0 +1,31 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import { createRequire } from 'node:module'
+import path from 'node:path'
+import vm from 'node:vm'
+
+const file = path.join(import.meta.dirname, 'dist/main.js')
+const code = fs.readFileSync(file, 'utf-8')
+
+// CommonJS
+const cjsLib = createRequire(import.meta.url)(file)
+assert.strictEqual(cjsLib.joined, path.join('a', 'b'))
+assert.strictEqual(cjsLib.value, 0)
+cjsLib.increase()
+assert.strictEqual(cjsLib.value, 1)
+
+// AMD
+let amdLib
+const define = (deps, factory) => {
+  assert.deepStrictEqual([...deps], ['exports', 'node:path'])
+  amdLib = {}
+  factory(amdLib, path)
+}
+define.amd = true
+vm.runInContext(code, vm.createContext({ define }))
+assert.strictEqual(amdLib.joined, path.join('a', 'b'))
+
+// Browser global
+const context = vm.createContext({ path })
+vm.runInContext(code, context)
+assert.strictEqual(context.myLib.joined, path.join('a', 'b'))
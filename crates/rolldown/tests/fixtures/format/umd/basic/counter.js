@@ -0,0 +1,5 @@
+export let value = 0
+
+export function increase() {
+  value++
+}
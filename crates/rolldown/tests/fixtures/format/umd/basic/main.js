@@ -0,0 +1,5 @@
+import { join } from 'node:path'
+import { value, increase } from './counter.js'
+
+export const joined = join('a', 'b')
+export { value, increase }
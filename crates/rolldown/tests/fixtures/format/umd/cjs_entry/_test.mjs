@@ -0,0 +1,13 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import { createRequire } from 'node:module'
+import path from 'node:path'
+import vm from 'node:vm'
+
+const file = path.join(import.meta.dirname, 'dist/main.js')
+
+assert.deepStrictEqual(createRequire(import.meta.url)(file), { foo: 'foo' })
+
+const context = vm.createContext({})
+vm.runInContext(fs.readFileSync(file, 'utf-8'), context)
+assert.strictEqual(context.myLib.foo, 'foo')
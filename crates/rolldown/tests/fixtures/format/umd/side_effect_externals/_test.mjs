@@ -0,0 +1,39 @@
+import assert from 'node:assert'
+import fs from 'node:fs'
+import path from 'node:path'
+import vm from 'node:vm'
+
+const code = fs.readFileSync(path.join(import.meta.dirname, 'dist/main.js'), 'utf-8')
+
+// CommonJS
+const required = []
+const cjsModule = { exports: {} }
+vm.runInContext(
+  code,
+  vm.createContext({
+    module: cjsModule,
+    exports: cjsModule.exports,
+    require: (id) => {
+      required.push(id)
+      return id === 'node:path' ? path : {}
+    },
+  }),
+)
+assert.deepStrictEqual(required, ['node:util', 'node:path', 'node:os'])
+assert.strictEqual(cjsModule.exports.joined, path.join('a', 'b'))
+
+// AMD
+let amdLib
+const define = (deps, factory) => {
+  assert.deepStrictEqual([...deps], ['exports', 'node:util', 'node:path', 'node:os'])
+  amdLib = {}
+  factory(amdLib, {}, path, {})
+}
+define.amd = true
+vm.runInContext(code, vm.createContext({ define }))
+assert.strictEqual(amdLib.joined, path.join('a', 'b'))
+
+// Browser global
+const context = vm.createContext({ util: {}, path })
+vm.runInContext(code, context)
+assert.strictEqual(context.myLib.joined, path.join('a', 'b'))
@@ -0,0 +1,5 @@
+import 'node:util'
+import { join } from 'node:path'
+import 'node:os'
+
+export const joined = join('a', 'b')
@@ -0,0 +1,8 @@
+import assert from 'node:assert'
+import { lazy, cjs, wrapped, required } from './dist/main.mjs'
+
+assert.strictEqual((await lazy).value, 'lazy')
+assert.strictEqual((await cjs).value, 'cjs')
+assert.strictEqual((await cjs).default.value, 'cjs')
+assert.strictEqual((await wrapped).value, 'wrapped')
+assert.strictEqual(required.value, 'wrapped')
@@ -0,0 +1,4 @@
+export const lazy = import('./lazy.js')
+export const cjs = import('./cjs.js')
+export const wrapped = import('./wrapped.js')
+export const required = require('./wrapped.js')
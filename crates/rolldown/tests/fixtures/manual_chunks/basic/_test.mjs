@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import { main } from './dist/main.mjs'
+import { other } from './dist/other.mjs'
+
+assert.strictEqual(main, 3)
+assert.strictEqual(other, 5)
@@ -0,0 +1,4 @@
+import { add } from 'lib'
+import { util } from './util.js'
+
+export const main = add(util, 1)
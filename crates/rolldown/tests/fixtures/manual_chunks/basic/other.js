@@ -0,0 +1,3 @@
+import { add } from 'lib'
+
+export const other = add(2, 3)
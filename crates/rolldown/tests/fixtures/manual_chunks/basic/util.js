@@ -0,0 +1,3 @@
+import { dep } from './dep.js'
+
+export const util = dep + 1
@@ -0,0 +1,6 @@
+import assert from 'node:assert'
+import { main } from './dist/main.mjs'
+import { button } from './dist/components/button.mjs'
+
+assert.strictEqual(main, '<<button>>')
+assert.strictEqual(button, '<button>')
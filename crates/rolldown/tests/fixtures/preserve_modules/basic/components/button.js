@@ -0,0 +1,3 @@
+import { format } from '../utils/format.js'
+
+export const button = format('button')
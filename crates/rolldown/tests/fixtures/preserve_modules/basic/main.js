@@ -0,0 +1,4 @@
+import { button } from './components/button.js'
+import { format } from './utils/format.js'
+
+export const main = format(button)
@@ -0,0 +1,4 @@
+import assert from 'node:assert'
+import { main } from './dist/src/main.mjs'
+
+assert.strictEqual(main, 'main-shared')
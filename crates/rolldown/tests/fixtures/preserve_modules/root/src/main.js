@@ -0,0 +1,3 @@
+import { shared } from '../shared/shared.js'
+
+export const main = `main-${shared}`
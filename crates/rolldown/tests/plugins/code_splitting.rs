@@ -0,0 +1,26 @@
+use rolldown::OutputOptions;
+
+use crate::{bundler_with_options, chunk_code};
+
+// Fixture tests run in test mode, which never imports the runtime chunk for side effects. This test doesn't, so it
+// covers the behavior of real builds.
+#[tokio::test(flavor = "multi_thread")]
+async fn entries_import_shared_chunks_for_side_effects() {
+  let mut bundler = bundler_with_options(
+    &[
+      ("/main.js", "import './shared.js'\nimport('./lazy.js')"),
+      ("/lazy.js", "import './shared.js'\nconsole.log('lazy')"),
+      ("/shared.js", "globalThis.shared = true"),
+    ],
+    OutputOptions { chunk_file_names: Some("[name].js".to_string()), ..Default::default() },
+    vec![],
+  );
+  let output = bundler.generate().await.unwrap();
+
+  for chunk in ["main.js", "lazy_js.js"] {
+    assert!(
+      chunk_code(&output.assets, chunk).contains("import \"./shared_js.js\";"),
+      "{chunk} should import the shared chunk"
+    );
+  }
+}
@@ -0,0 +1,34 @@
+use rolldown::{InputItem, InputOptions, OutputOptions};
+
+use crate::{bundler_builder, chunk_code};
+
+// Fixtures bundle from the OS file system, which resolves `.` and `..` in paths on its own. `MemoryFileSystem`,
+// which the wasm binding also uses, doesn't.
+#[tokio::test(flavor = "multi_thread")]
+async fn variables_match_files_in_memory() {
+  let mut bundler = bundler_builder(
+    &[
+      ("/src/main.js", "export const loadPage = (name) => import(`../pages/${name}.js`)"),
+      ("/pages/home.js", "console.log('home')"),
+      ("/pages/about.js", "console.log('about')"),
+      ("/pages/readme.md", "# Pages"),
+    ],
+    OutputOptions { chunk_file_names: Some("[name].js".to_string()), ..Default::default() },
+    vec![],
+  )
+  .with_input_options(InputOptions {
+    input: vec![InputItem { name: Some("main".to_string()), import: "./src/main.js".to_string() }],
+    cwd: Some("/".into()),
+    dynamic_import_vars: Some(true),
+    ..Default::default()
+  })
+  .build()
+  .unwrap();
+  let output = bundler.generate().await.unwrap();
+
+  assert_eq!(output.assets.len(), 3);
+  let main = chunk_code(&output.assets, "main.js");
+  assert!(main.contains("case '../pages/about.js':"), "{main}");
+  assert!(main.contains("case '../pages/home.js':"), "{main}");
+  assert!(chunk_code(&output.assets, "pages_home_js.js").contains("console.log('home')"));
+}
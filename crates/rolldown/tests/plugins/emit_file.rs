@@ -0,0 +1,296 @@
+use std::{
+  borrow::Cow,
+  sync::{Arc, Mutex},
+};
+
+use rolldown::OutputOptions;
+use rolldown_common::{AssetSource, EmittedAsset, EmittedChunk, EmittedFile, ModuleInfo, Output};
+use rolldown_plugin::{BoxPlugin, HookNoopReturn, Plugin, PluginContext};
+
+use crate::{assets, bundler, bundler_with_options, chunk_code, error_codes};
+
+/// Emits the files once `/main.js` is parsed, when its imports are known already, and records their file names in
+/// `generate_bundle`.
+#[derive(Debug, Default)]
+struct EmitFiles {
+  files: Vec<EmittedFile>,
+  reference_ids: Mutex<Vec<String>>,
+  file_names: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl Plugin for EmitFiles {
+  fn name(&self) -> Cow<'static, str> {
+    "emit-files".into()
+  }
+
+  async fn module_parsed(&self, ctx: &PluginContext, module_info: &ModuleInfo) -> HookNoopReturn {
+    if module_info.id == "/main.js" {
+      let mut reference_ids = self.reference_ids.lock().unwrap();
+      for file in &self.files {
+        reference_ids.push(ctx.emit_file(file.clone())?);
+      }
+    }
+    Ok(())
+  }
+
+  async fn generate_bundle(
+    &self,
+    ctx: &PluginContext,
+    _bundle: &Vec<Output>,
+    _is_write: bool,
+  ) -> HookNoopReturn {
+    let mut file_names = self.file_names.lock().unwrap();
+    for reference_id in self.reference_ids.lock().unwrap().iter() {
+      file_names.push(ctx.get_file_name(reference_id)?);
+    }
+    Ok(())
+  }
+}
+
+fn named_asset(name: &str, source: impl Into<AssetSource>) -> EmittedFile {
+  EmittedAsset { name: Some(name.to_string()), file_name: None, source: source.into() }.into()
+}
+
+fn asset_with_file_name(file_name: &str, source: impl Into<AssetSource>) -> EmittedFile {
+  EmittedAsset { name: None, file_name: Some(file_name.to_string()), source: source.into() }.into()
+}
+
+fn chunk(id: &str, implicitly_loaded_after: &[&str]) -> EmittedFile {
+  EmittedChunk {
+    id: id.to_string(),
+    name: Some("emitted".to_string()),
+    implicitly_loaded_after: implicitly_loaded_after.iter().map(ToString::to_string).collect(),
+  }
+  .into()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn assets_are_named_by_asset_file_names() {
+  let file_names = Arc::new(Mutex::new(vec![]));
+  let plugin = EmitFiles {
+    files: vec![named_asset("style.css", "body {}".to_string())],
+    file_names: Arc::clone(&file_names),
+    ..Default::default()
+  };
+  let mut bundler = bundler_with_options(
+    &[("/main.js", "console.log('main')")],
+    OutputOptions {
+      asset_file_names: Some("static/[name].[ext]".to_string()),
+      ..Default::default()
+    },
+    vec![Box::new(plugin)],
+  );
+  let output = bundler.generate().await.unwrap();
+
+  let assets = assets(&output.assets);
+  assert_eq!(assets.len(), 1);
+  assert_eq!(assets[0].file_name, "static/style.css");
+  assert_eq!(assets[0].name.as_deref(), Some("style.css"));
+  assert_eq!(*file_names.lock().unwrap(), ["static/style.css"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn assets_with_the_same_content_are_written_once() {
+  let file_names = Arc::new(Mutex::new(vec![]));
+  let plugin = EmitFiles {
+    files: vec![
+      named_asset("a.txt", "same".to_string()),
+      named_asset("b.txt", "same".to_string()),
+      named_asset("c.txt", "other".to_string()),
+    ],
+    file_names: Arc::clone(&file_names),
+    ..Default::default()
+  };
+  let mut bundler = bundler(&[("/main.js", "console.log('main')")], vec![Box::new(plugin)]);
+  let output = bundler.generate().await.unwrap();
+
+  let assets = assets(&output.assets);
+  assert_eq!(assets.len(), 2);
+  let file_names = file_names.lock().unwrap();
+  assert_eq!(file_names[0], file_names[1]);
+  assert_ne!(file_names[0], file_names[2]);
+  assert!(file_names[0].starts_with("assets/a-"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn binary_assets_keep_their_bytes() {
+  let bytes = vec![0x89, b'P', b'N', b'G', 0xff, 0x00];
+  let plugin =
+    EmitFiles { files: vec![named_asset("logo.png", bytes.clone())], ..Default::default() };
+  let mut bundler = bundler(&[("/main.js", "console.log('main')")], vec![Box::new(plugin)]);
+  let output = bundler.generate().await.unwrap();
+
+  let assets = assets(&output.assets);
+  assert_eq!(assets.len(), 1);
+  assert!(matches!(&assets[0].source, AssetSource::Buffer(source) if *source == bytes));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn the_same_asset_can_be_emitted_twice_with_an_explicit_file_name() {
+  let file_names = Arc::new(Mutex::new(vec![]));
+  let plugin = EmitFiles {
+    files: vec![
+      asset_with_file_name("robots.txt", "User-agent: *".to_string()),
+      asset_with_file_name("robots.txt", "User-agent: *".to_string()),
+    ],
+    file_names: Arc::clone(&file_names),
+    ..Default::default()
+  };
+  let mut bundler = bundler(&[("/main.js", "console.log('main')")], vec![Box::new(plugin)]);
+  let output = bundler.generate().await.unwrap();
+
+  assert_eq!(assets(&output.assets).len(), 1);
+  assert_eq!(*file_names.lock().unwrap(), ["robots.txt", "robots.txt"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn explicit_file_names_cannot_overwrite_other_outputs() {
+  for (existing, file_name) in [
+    (asset_with_file_name("robots.txt", "User-agent: *".to_string()), "robots.txt"),
+    (asset_with_file_name("robots.txt", "User-agent: *".to_string()), "ROBOTS.txt"),
+    (named_asset("unused.txt", "unused".to_string()), "main.js"),
+  ] {
+    let plugin = EmitFiles {
+      files: vec![existing, asset_with_file_name(file_name, "Disallow: /".to_string())],
+      ..Default::default()
+    };
+    let mut bundler = bundler(&[("/main.js", "console.log('main')")], vec![Box::new(plugin)]);
+    assert_eq!(
+      error_codes(bundler.generate().await),
+      ["FILE_NAME_CONFLICT"],
+      "{file_name} should conflict"
+    );
+  }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn emitted_chunks_are_extra_entries() {
+  let file_names = Arc::new(Mutex::new(vec![]));
+  let plugin = EmitFiles {
+    files: vec![chunk("./worker.js", &[])],
+    file_names: Arc::clone(&file_names),
+    ..Default::default()
+  };
+  let mut bundler = bundler(
+    &[("/main.js", "console.log('main')"), ("/worker.js", "console.log('worker')")],
+    vec![Box::new(plugin)],
+  );
+  let output = bundler.generate().await.unwrap();
+
+  assert!(chunk_code(&output.assets, "emitted.js").contains("console.log('worker')"));
+  assert_eq!(*file_names.lock().unwrap(), ["emitted.js"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn emitted_chunks_are_not_dynamic_entries() {
+  let plugin = EmitFiles { files: vec![chunk("./lazy.js", &[])], ..Default::default() };
+  let mut bundler = bundler(
+    &[("/main.js", "import('./lazy.js')"), ("/lazy.js", "console.log('lazy')")],
+    vec![Box::new(plugin)],
+  );
+  let output = bundler.generate().await.unwrap();
+
+  assert_eq!(output.assets.len(), 2);
+  assert!(chunk_code(&output.assets, "main.js").contains("import('./emitted.js')"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn modules_loaded_before_emitted_chunks_stay_in_their_chunks() {
+  let plugin =
+    EmitFiles { files: vec![chunk("./worker.js", &["./main.js"])], ..Default::default() };
+  let mut bundler = bundler(
+    &[
+      ("/main.js", "import './shared.js'"),
+      ("/worker.js", "import './shared.js'"),
+      ("/shared.js", "console.log('shared')"),
+    ],
+    vec![Box::new(plugin)],
+  );
+  let output = bundler.generate().await.unwrap();
+
+  assert_eq!(output.assets.len(), 2);
+  assert!(chunk_code(&output.assets, "main.js").contains("console.log('shared')"));
+  assert!(!chunk_code(&output.assets, "emitted.js").contains("console.log('shared')"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn modules_loaded_before_emitted_chunks_must_be_entries() {
+  let plugin = EmitFiles { files: vec![chunk("./worker.js", &["./dep.js"])], ..Default::default() };
+  let mut bundler = bundler(
+    &[("/main.js", "import './dep.js'"), ("/dep.js", ""), ("/worker.js", "")],
+    vec![Box::new(plugin)],
+  );
+  assert_eq!(error_codes(bundler.generate().await), ["MISSING_IMPLICIT_DEPENDANT"]);
+}
+
+/// Emits the files in `generate_bundle`, after the other outputs are generated.
+#[derive(Debug)]
+struct EmitFilesInGenerateBundle {
+  files: Vec<EmittedFile>,
+}
+
+#[async_trait::async_trait]
+impl Plugin for EmitFilesInGenerateBundle {
+  fn name(&self) -> Cow<'static, str> {
+    "emit-files-in-generate-bundle".into()
+  }
+
+  async fn generate_bundle(
+    &self,
+    ctx: &PluginContext,
+    _bundle: &Vec<Output>,
+    _is_write: bool,
+  ) -> HookNoopReturn {
+    for file in &self.files {
+      ctx.emit_file(file.clone())?;
+    }
+    Ok(())
+  }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn assets_emitted_in_generate_bundle_are_part_of_the_bundle() {
+  let plugins: Vec<BoxPlugin> = vec![
+    Box::new(EmitFiles {
+      files: vec![named_asset("a.txt", "same".to_string())],
+      ..Default::default()
+    }),
+    Box::new(EmitFilesInGenerateBundle {
+      files: vec![
+        asset_with_file_name("manifest.json", "{}".to_string()),
+        named_asset("b.txt", "same".to_string()),
+      ],
+    }),
+  ];
+  let mut bundler = bundler(&[("/main.js", "console.log('main')")], plugins);
+  let output = bundler.generate().await.unwrap();
+
+  let assets = assets(&output.assets);
+  assert_eq!(assets.len(), 2);
+  assert!(assets[0].file_name.starts_with("assets/a-"));
+  assert_eq!(assets[1].file_name, "manifest.json");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn chunks_cannot_be_emitted_after_modules_finish_loading() {
+  let plugin = EmitFilesInGenerateBundle { files: vec![chunk("./worker.js", &[])] };
+  let mut bundler = bundler(
+    &[("/main.js", "console.log('main')"), ("/worker.js", "console.log('worker')")],
+    vec![Box::new(plugin)],
+  );
+  assert_eq!(error_codes(bundler.generate().await), ["INVALID_ROLLUP_PHASE"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn chunks_can_be_emitted_again_in_the_next_build() {
+  let plugin = EmitFiles { files: vec![chunk("./worker.js", &[])], ..Default::default() };
+  let mut bundler = bundler(
+    &[("/main.js", "console.log('main')"), ("/worker.js", "console.log('worker')")],
+    vec![Box::new(plugin)],
+  );
+  bundler.generate().await.unwrap();
+  let output = bundler.generate().await.unwrap();
+
+  assert!(chunk_code(&output.assets, "emitted.js").contains("console.log('worker')"));
+}
@@ -0,0 +1,144 @@
+use std::{
+  borrow::Cow,
+  sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+  },
+};
+
+use rolldown::{OutputFormat, OutputOptions};
+use rolldown_common::{BatchedErrors, Output};
+use rolldown_plugin::{
+  HookNoopReturn, HookRenderErrorArgs, HookRenderStartArgs, Plugin, PluginContext,
+};
+
+use crate::{bundler, bundler_with_options};
+
+/// Fails in `generate_bundle`, and in `render_error` if `fail_render_error` is set.
+#[derive(Debug, Default)]
+struct FailRender {
+  fail_render_error: bool,
+  render_errors: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl Plugin for FailRender {
+  fn name(&self) -> Cow<'static, str> {
+    "fail-render".into()
+  }
+
+  async fn generate_bundle(
+    &self,
+    ctx: &PluginContext,
+    _bundle: &Vec<Output>,
+    _is_write: bool,
+  ) -> HookNoopReturn {
+    Err(ctx.error("generate_bundle failed"))
+  }
+
+  async fn render_error(&self, ctx: &PluginContext, args: &HookRenderErrorArgs) -> HookNoopReturn {
+    self.render_errors.lock().unwrap().push(args.error.clone());
+    if self.fail_render_error {
+      return Err(ctx.error("render_error failed"));
+    }
+    Ok(())
+  }
+}
+
+fn error_messages(errors: BatchedErrors) -> Vec<String> {
+  errors.into_iter().map(|error| error.to_string()).collect()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn render_error_receives_the_render_error() {
+  let render_errors = Arc::new(Mutex::new(vec![]));
+  let plugin = FailRender { fail_render_error: false, render_errors: Arc::clone(&render_errors) };
+  let mut bundler = bundler(&[("/main.js", "")], vec![Box::new(plugin)]);
+  let Err(errors) = bundler.generate().await else { panic!("the build should fail") };
+
+  let messages = error_messages(errors);
+  assert_eq!(messages.len(), 1);
+  assert!(messages[0].contains("generate_bundle failed"));
+  assert_eq!(*render_errors.lock().unwrap(), messages);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn render_error_failures_are_reported_along_with_the_render_error() {
+  let plugin = FailRender { fail_render_error: true, ..Default::default() };
+  let mut bundler = bundler(&[("/main.js", "")], vec![Box::new(plugin)]);
+  let Err(errors) = bundler.generate().await else { panic!("the build should fail") };
+
+  let messages = error_messages(errors);
+  assert_eq!(messages.len(), 2);
+  assert!(messages[0].contains("generate_bundle failed"));
+  assert!(messages[1].contains("render_error failed"));
+}
+
+#[derive(Debug, Default)]
+struct CountClose {
+  calls: Arc<AtomicUsize>,
+}
+
+#[async_trait::async_trait]
+impl Plugin for CountClose {
+  fn name(&self) -> Cow<'static, str> {
+    "count-close".into()
+  }
+
+  async fn close_bundle(&self, _ctx: &PluginContext) -> HookNoopReturn {
+    self.calls.fetch_add(1, Ordering::SeqCst);
+    Ok(())
+  }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn close_bundle_is_called_once_by_close() {
+  let calls = Arc::new(AtomicUsize::new(0));
+  let mut bundler =
+    bundler(&[("/main.js", "")], vec![Box::new(CountClose { calls: Arc::clone(&calls) })]);
+  bundler.generate().await.unwrap();
+  assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+  bundler.close().await.unwrap();
+  bundler.close().await.unwrap();
+  assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+  // Dropping the bundler doesn't call it
+  drop(bundler);
+  assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+/// Records the output format and the cwd that `render_start` receives.
+#[derive(Debug, Default)]
+struct RecordRenderStart {
+  options: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+#[async_trait::async_trait]
+impl Plugin for RecordRenderStart {
+  fn name(&self) -> Cow<'static, str> {
+    "record-render-start".into()
+  }
+
+  async fn render_start(&self, _ctx: &PluginContext, args: &HookRenderStartArgs) -> HookNoopReturn {
+    self.options.lock().unwrap().push((
+      args.output_options.format.to_string(),
+      args.input_options.cwd.to_string_lossy().to_string(),
+    ));
+    Ok(())
+  }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn render_start_receives_the_normalized_options() {
+  let options = Arc::new(Mutex::new(vec![]));
+  let plugin = RecordRenderStart { options: Arc::clone(&options) };
+  let mut bundler = bundler_with_options(
+    &[("/main.js", "")],
+    OutputOptions { format: Some(OutputFormat::Cjs), ..Default::default() },
+    vec![Box::new(plugin)],
+  );
+  bundler.generate().await.unwrap();
+
+  assert_eq!(*options.lock().unwrap(), [("cjs".to_string(), "/".to_string())]);
+}
@@ -0,0 +1,77 @@
+//! Tests that bundle in memory, for what fixtures can't cover: plugin hooks and `PluginContext`, since fixtures
+//! don't have plugins, and the output of real builds, since fixtures always run in test mode.
+
+mod code_splitting;
+mod dynamic_import_vars;
+mod emit_file;
+mod lifecycle;
+mod options;
+mod plugin_context;
+mod render_chunk;
+mod resolve_dynamic_import;
+
+use rolldown::{Bundler, BundlerBuilder, InputItem, InputOptions, OutputOptions, RolldownOutput};
+use rolldown_common::{BatchedErrors, Output, OutputAsset};
+use rolldown_fs::MemoryFileSystem;
+use rolldown_plugin::BoxPlugin;
+
+/// Create a bundler of the given files in memory, with `/main.js` as the entry.
+pub fn bundler(files: &[(&str, &str)], plugins: Vec<BoxPlugin>) -> Bundler<MemoryFileSystem> {
+  bundler_with_options(files, OutputOptions::default(), plugins)
+}
+
+pub fn bundler_with_options(
+  files: &[(&str, &str)],
+  output_options: OutputOptions,
+  plugins: Vec<BoxPlugin>,
+) -> Bundler<MemoryFileSystem> {
+  bundler_builder(files, output_options, plugins).build().unwrap()
+}
+
+pub fn bundler_builder(
+  files: &[(&str, &str)],
+  output_options: OutputOptions,
+  plugins: Vec<BoxPlugin>,
+) -> BundlerBuilder<MemoryFileSystem> {
+  let mut fs = MemoryFileSystem::default();
+  for (path, content) in files {
+    fs.add_file(path.as_ref(), content);
+  }
+  BundlerBuilder::<MemoryFileSystem>::default()
+    .with_input_options(InputOptions {
+      input: vec![InputItem { name: Some("main".to_string()), import: "./main.js".to_string() }],
+      cwd: Some("/".into()),
+      ..Default::default()
+    })
+    .with_output_options(output_options)
+    .with_file_system(fs)
+    .with_plugins(plugins)
+}
+
+pub fn chunk_code<'a>(outputs: &'a [Output], file_name: &str) -> &'a str {
+  outputs
+    .iter()
+    .find_map(|output| match output {
+      Output::Chunk(chunk) if chunk.file_name == file_name => Some(chunk.code.as_str()),
+      _ => None,
+    })
+    .unwrap_or_else(|| panic!("chunk {file_name} should be generated"))
+}
+
+pub fn assets(outputs: &[Output]) -> Vec<&OutputAsset> {
+  outputs
+    .iter()
+    .filter_map(|output| match output {
+      Output::Asset(asset) => Some(asset.as_ref()),
+      Output::Chunk(_) => None,
+    })
+    .collect()
+}
+
+/// The codes of the errors a build failed with.
+pub fn error_codes(result: Result<RolldownOutput, BatchedErrors>) -> Vec<&'static str> {
+  match result {
+    Ok(_) => panic!("the build should fail"),
+    Err(errors) => errors.into_iter().map(|error| error.code()).collect(),
+  }
+}
@@ -0,0 +1,66 @@
+use std::borrow::Cow;
+
+use rolldown::{External, InputItem, InputOptions, OutputOptions};
+use rolldown_error::BuildError;
+use rolldown_plugin::{HookNoopReturn, Plugin};
+
+use crate::{bundler, bundler_builder, chunk_code};
+
+/// Adds `./worker.js` as an entry, makes `node:fs` external and names entries `[name].mjs`.
+#[derive(Debug)]
+struct AdjustOptions;
+
+impl Plugin for AdjustOptions {
+  fn name(&self) -> Cow<'static, str> {
+    "adjust-options".into()
+  }
+
+  fn options(&self, options: &mut InputOptions) -> HookNoopReturn {
+    options
+      .input
+      .push(InputItem { name: Some("worker".to_string()), import: "./worker.js".to_string() });
+    options.external = Some(External::ArrayString(vec!["node:fs".to_string()]));
+    Ok(())
+  }
+
+  fn output_options(&self, options: &mut OutputOptions) -> HookNoopReturn {
+    options.entry_file_names = Some("[name].mjs".to_string());
+    Ok(())
+  }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn options_hooks_adjust_options() {
+  let mut bundler = bundler(
+    &[("/main.js", "import 'node:fs'"), ("/worker.js", "console.log('worker')")],
+    vec![Box::new(AdjustOptions)],
+  );
+  let output = bundler.generate().await.unwrap();
+
+  assert!(chunk_code(&output.assets, "main.mjs").contains("import \"node:fs\""));
+  assert!(chunk_code(&output.assets, "worker.mjs").contains("console.log('worker')"));
+}
+
+#[derive(Debug)]
+struct RejectOptions;
+
+impl Plugin for RejectOptions {
+  fn name(&self) -> Cow<'static, str> {
+    "reject-options".into()
+  }
+
+  fn options(&self, _options: &mut InputOptions) -> HookNoopReturn {
+    Err(BuildError::plugin_error("reject-options", "invalid options"))
+  }
+}
+
+#[test]
+fn options_hooks_fail_the_build() {
+  let result =
+    bundler_builder(&[("/main.js", "")], OutputOptions::default(), vec![Box::new(RejectOptions)])
+      .build();
+  let Err(errors) = result else { panic!("the build should fail") };
+  let errors = Vec::from(errors);
+  assert_eq!(errors.len(), 1);
+  assert_eq!(errors[0].code(), "PLUGIN_ERROR");
+}
@@ -0,0 +1,174 @@
+use std::{
+  borrow::Cow,
+  sync::{Arc, Mutex},
+};
+
+use rolldown_common::ModuleInfo;
+use rolldown_plugin::{
+  HookBuildEndArgs, HookNoopReturn, HookResolveIdArgs, HookResolveIdOutput, HookResolveIdReturn,
+  Plugin, PluginContext, PluginContextResolveOptions,
+};
+
+use crate::{bundler, error_codes};
+
+/// Redirects `./dep.js` to `/redirected.js`, and resolves `./dep.js` from `/main.js` in `build_start`.
+#[derive(Debug, Default)]
+struct Redirect {
+  resolved_ids: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl Plugin for Redirect {
+  fn name(&self) -> Cow<'static, str> {
+    "redirect".into()
+  }
+
+  async fn build_start(&self, ctx: &PluginContext) -> HookNoopReturn {
+    for skip_self in [true, false] {
+      let options = PluginContextResolveOptions { skip_self, ..Default::default() };
+      let resolved_id = ctx.resolve("./dep.js", Some("/main.js"), Some(options)).await?;
+      self.resolved_ids.lock().unwrap().push(resolved_id.id);
+    }
+    Ok(())
+  }
+
+  async fn resolve_id(
+    &self,
+    _ctx: &PluginContext,
+    args: &HookResolveIdArgs,
+  ) -> HookResolveIdReturn {
+    Ok(
+      (args.source == "./dep.js")
+        .then(|| HookResolveIdOutput { id: "/redirected.js".to_string(), external: None }),
+    )
+  }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn resolve_skips_the_plugin_itself() {
+  let resolved_ids = Arc::new(Mutex::new(vec![]));
+  let plugin = Redirect { resolved_ids: Arc::clone(&resolved_ids) };
+  let mut bundler = bundler(
+    &[
+      ("/main.js", "import './dep.js'"),
+      ("/dep.js", "console.log('dep')"),
+      ("/redirected.js", "console.log('redirected')"),
+    ],
+    vec![Box::new(plugin)],
+  );
+  bundler.generate().await.unwrap();
+
+  assert_eq!(*resolved_ids.lock().unwrap(), ["/dep.js", "/redirected.js"]);
+}
+
+/// Loads `id` when `/main.js` is parsed.
+#[derive(Debug)]
+struct LoadOnParsed {
+  id: &'static str,
+  result: Arc<Mutex<Option<Result<ModuleInfo, String>>>>,
+}
+
+#[async_trait::async_trait]
+impl Plugin for LoadOnParsed {
+  fn name(&self) -> Cow<'static, str> {
+    "load-on-parsed".into()
+  }
+
+  async fn module_parsed(&self, ctx: &PluginContext, module_info: &ModuleInfo) -> HookNoopReturn {
+    if module_info.id == "/main.js" {
+      let result = ctx.load(self.id).await.map_err(|err| err.to_string());
+      *self.result.lock().unwrap() = Some(result);
+    }
+    Ok(())
+  }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn load_waits_for_the_module_to_be_parsed() {
+  let result = Arc::new(Mutex::new(None));
+  let plugin = LoadOnParsed { id: "/extra.js", result: Arc::clone(&result) };
+  let mut bundler = bundler(
+    &[
+      ("/main.js", "console.log('main')"),
+      ("/extra.js", "import './dep.js'"),
+      ("/dep.js", "console.log('dep')"),
+    ],
+    vec![Box::new(plugin)],
+  );
+  bundler.generate().await.unwrap();
+
+  let module_info = result.lock().unwrap().take().unwrap().unwrap();
+  assert_eq!(module_info.id, "/extra.js");
+  assert!(!module_info.is_entry);
+  assert_eq!(module_info.imported_ids, ["/dep.js"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn load_fails_if_the_module_fails() {
+  let result = Arc::new(Mutex::new(None));
+  let plugin = LoadOnParsed { id: "/missing.js", result: Arc::clone(&result) };
+  let mut bundler = bundler(&[("/main.js", "console.log('main')")], vec![Box::new(plugin)]);
+  assert!(!error_codes(bundler.generate().await).is_empty());
+
+  let error = result.lock().unwrap().take().unwrap().unwrap_err();
+  assert!(error.contains("Could not load \"/missing.js\""), "{error}");
+}
+
+/// Records infos of modules in `build_end`, and adds a watch file and a warning in `build_start`.
+#[derive(Debug, Default)]
+struct Inspect {
+  module_infos: Arc<Mutex<Vec<Option<ModuleInfo>>>>,
+}
+
+#[async_trait::async_trait]
+impl Plugin for Inspect {
+  fn name(&self) -> Cow<'static, str> {
+    "inspect".into()
+  }
+
+  async fn build_start(&self, ctx: &PluginContext) -> HookNoopReturn {
+    ctx.add_watch_file("/config.json");
+    ctx.add_watch_file("/config.json");
+    ctx.warn("careful");
+    Ok(())
+  }
+
+  async fn build_end(
+    &self,
+    ctx: &PluginContext,
+    _args: Option<&HookBuildEndArgs>,
+  ) -> HookNoopReturn {
+    let mut module_infos = self.module_infos.lock().unwrap();
+    module_infos.extend(["/main.js", "/missing.js"].map(|id| ctx.get_module_info(id)));
+    Ok(())
+  }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn get_module_info_returns_loaded_modules() {
+  let module_infos = Arc::new(Mutex::new(vec![]));
+  let plugin = Inspect { module_infos: Arc::clone(&module_infos) };
+  let mut bundler = bundler(
+    &[("/main.js", "import './dep.js'\nimport('./lazy.js')"), ("/dep.js", ""), ("/lazy.js", "")],
+    vec![Box::new(plugin)],
+  );
+  bundler.generate().await.unwrap();
+
+  let module_infos = module_infos.lock().unwrap();
+  let main = module_infos[0].as_ref().unwrap();
+  assert!(main.is_entry);
+  assert_eq!(main.imported_ids, ["/dep.js"]);
+  assert_eq!(main.dynamically_imported_ids, ["/lazy.js"]);
+  assert!(module_infos[1].is_none());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn watch_files_and_warnings_are_reported() {
+  let mut bundler = bundler(&[("/main.js", "")], vec![Box::<Inspect>::default()]);
+  let output = bundler.generate().await.unwrap();
+
+  assert_eq!(output.watch_files, ["/config.json"]);
+  assert_eq!(output.warnings.len(), 1);
+  assert_eq!(output.warnings[0].code(), "PLUGIN_WARNING");
+  assert!(output.warnings[0].to_string().contains("careful"));
+}
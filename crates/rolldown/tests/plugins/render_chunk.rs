@@ -0,0 +1,117 @@
+use std::borrow::Cow;
+
+use rolldown::{OutputOptions, SourceMapType};
+use rolldown_plugin::{
+  HookRenderChunkArgs, HookRenderChunkOutput, HookRenderChunkReturn, Plugin, PluginContext,
+};
+use rolldown_sourcemap::{SourceMap, SourceMapBuilder};
+
+use crate::{assets, bundler_with_options, chunk_code};
+
+/// Prepends a line to chunks, optionally with a map of the change.
+#[derive(Debug)]
+struct Prepend {
+  with_map: bool,
+}
+
+#[async_trait::async_trait]
+impl Plugin for Prepend {
+  fn name(&self) -> Cow<'static, str> {
+    "prepend".into()
+  }
+
+  async fn render_chunk(
+    &self,
+    _ctx: &PluginContext,
+    args: &HookRenderChunkArgs,
+  ) -> HookRenderChunkReturn {
+    let map = self.with_map.then(|| {
+      let mut builder = SourceMapBuilder::new(None);
+      for line in 0..u32::try_from(args.code.lines().count()).unwrap() {
+        builder.add(line + 1, 0, line, 0, Some(args.chunk.file_name.as_str()), None);
+      }
+      builder.into_sourcemap()
+    });
+    Ok(Some(HookRenderChunkOutput { code: format!("/* prepended */\n{}", args.code), map }))
+  }
+}
+
+fn output_options(sourcemap: Option<SourceMapType>) -> OutputOptions {
+  OutputOptions { sourcemap, ..Default::default() }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn maps_of_render_chunk_are_collapsed_onto_the_chunk() {
+  let mut bundler = bundler_with_options(
+    &[("/main.js", "console.log('a')\n\nconsole.log('main')\nconsole.log('b')")],
+    output_options(Some(SourceMapType::File)),
+    vec![Box::new(Prepend { with_map: true }), Box::new(Prepend { with_map: true })],
+  );
+  let output = bundler.generate().await.unwrap();
+  assert!(output.warnings.is_empty());
+
+  let code = chunk_code(&output.assets, "main.js");
+  let (line, column) = code
+    .lines()
+    .enumerate()
+    .find_map(|(line, content)| Some((line, content.find("console.log('main')")?)))
+    .unwrap();
+  assert!(code.starts_with("/* prepended */\n/* prepended */\n"));
+
+  let map_asset = assets(&output.assets).into_iter().find(|asset| asset.file_name == "main.js.map");
+  let map = SourceMap::from_slice(map_asset.unwrap().source.as_bytes()).unwrap();
+  let token = map.lookup_token(line.try_into().unwrap(), column.try_into().unwrap()).unwrap();
+  assert!(token.get_source().unwrap().ends_with("main.js"));
+  assert_eq!(token.get_src_line(), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn changing_code_without_a_map_breaks_sourcemaps() {
+  let mut bundler = bundler_with_options(
+    &[("/main.js", "console.log('main')")],
+    output_options(Some(SourceMapType::File)),
+    vec![Box::new(Prepend { with_map: false })],
+  );
+  let output = bundler.generate().await.unwrap();
+
+  assert_eq!(output.warnings.len(), 1);
+  assert_eq!(output.warnings[0].code(), "SOURCEMAP_BROKEN");
+  assert!(output.warnings[0].to_string().contains("prepend"));
+
+  // Nothing to break without sourcemaps
+  let mut bundler = bundler_with_options(
+    &[("/main.js", "console.log('main')")],
+    output_options(None),
+    vec![Box::new(Prepend { with_map: false })],
+  );
+  assert!(bundler.generate().await.unwrap().warnings.is_empty());
+}
+
+/// Returns the code as it is, which doesn't need a map.
+#[derive(Debug)]
+struct Noop;
+
+#[async_trait::async_trait]
+impl Plugin for Noop {
+  fn name(&self) -> Cow<'static, str> {
+    "noop".into()
+  }
+
+  async fn render_chunk(
+    &self,
+    _ctx: &PluginContext,
+    args: &HookRenderChunkArgs,
+  ) -> HookRenderChunkReturn {
+    Ok(Some(HookRenderChunkOutput { code: args.code.clone(), map: None }))
+  }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn unchanged_code_does_not_need_a_map() {
+  let mut bundler = bundler_with_options(
+    &[("/main.js", "console.log('main')")],
+    output_options(Some(SourceMapType::File)),
+    vec![Box::new(Noop)],
+  );
+  assert!(bundler.generate().await.unwrap().warnings.is_empty());
+}
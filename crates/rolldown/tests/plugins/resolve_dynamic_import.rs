@@ -0,0 +1,153 @@
+use std::{
+  borrow::Cow,
+  sync::{Arc, Mutex},
+};
+
+use rolldown_common::Output;
+use rolldown_plugin::{
+  DynamicImportSpecifier, HookResolveDynamicImportArgs, HookResolveIdOutput, HookResolveIdReturn,
+  Plugin, PluginContext,
+};
+
+use crate::{bundler, chunk_code};
+
+/// Resolves non-literal specifiers by their source text, and records every specifier it's called with.
+#[derive(Debug, Default)]
+struct ResolveBySource {
+  /// Source texts of specifiers with the ids and `external` flags they resolve to.
+  resolved: Vec<(&'static str, &'static str, Option<bool>)>,
+  specifiers: Arc<Mutex<Vec<String>>>,
+}
+
+#[async_trait::async_trait]
+impl Plugin for ResolveBySource {
+  fn name(&self) -> Cow<'static, str> {
+    "resolve-by-source".into()
+  }
+
+  async fn resolve_dynamic_import(
+    &self,
+    _ctx: &PluginContext,
+    args: &HookResolveDynamicImportArgs,
+  ) -> HookResolveIdReturn {
+    let source_text = match args.specifier {
+      DynamicImportSpecifier::Str(specifier) => specifier,
+      DynamicImportSpecifier::Expression { source_text, .. } => source_text,
+    };
+    self.specifiers.lock().unwrap().push(source_text.to_string());
+    Ok(
+      self.resolved.iter().find(|(source, ..)| *source == source_text).map(|(_, id, external)| {
+        HookResolveIdOutput { id: (*id).to_string(), external: *external }
+      }),
+    )
+  }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn template_and_variable_imports_are_bundled_if_resolved() {
+  let specifiers = Arc::new(Mutex::new(vec![]));
+  let plugin = ResolveBySource {
+    resolved: vec![
+      ("`./locales/${lang}.js`", "/locales/en.js", None),
+      ("page", "/pages/home.js", None),
+    ],
+    specifiers: Arc::clone(&specifiers),
+  };
+  let mut bundler = bundler(
+    &[
+      (
+        "/main.js",
+        "const lang = 'en', page = './home.js'\nimport(`./locales/${lang}.js`)\nimport(page)",
+      ),
+      ("/locales/en.js", "console.log('en')"),
+      ("/pages/home.js", "console.log('home')"),
+    ],
+    vec![Box::new(plugin)],
+  );
+  let output = bundler.generate().await.unwrap();
+
+  assert_eq!(output.assets.len(), 3);
+  let main = chunk_code(&output.assets, "main.js");
+  for (module_code, chunk_prefix) in
+    [("console.log('en')", "locales_en_js-"), ("console.log('home')", "pages_home_js-")]
+  {
+    let chunk = output
+      .assets
+      .iter()
+      .find_map(|output| match output {
+        Output::Chunk(chunk) if chunk.code.contains(module_code) => Some(chunk),
+        _ => None,
+      })
+      .unwrap_or_else(|| panic!("{module_code} should be bundled"));
+    assert!(chunk.file_name.starts_with(chunk_prefix), "{}", chunk.file_name);
+    assert!(main.contains(&format!("import('./{}')", chunk.file_name)), "{main}");
+  }
+
+  let mut specifiers = specifiers.lock().unwrap().clone();
+  specifiers.sort();
+  assert_eq!(specifiers, ["`./locales/${lang}.js`", "page"]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn resolved_imports_can_be_external() {
+  let plugin = ResolveBySource {
+    resolved: vec![
+      ("`./locales/${lang}.js`", "https://cdn.example.com/en.js", Some(true)),
+      ("./lazy.js", "lazy-external", Some(true)),
+    ],
+    ..Default::default()
+  };
+  let mut bundler = bundler(
+    &[
+      ("/main.js", "const lang = 'en'\nimport(`./locales/${lang}.js`)\nimport('./lazy.js')"),
+      ("/lazy.js", "console.log('lazy')"),
+    ],
+    vec![Box::new(plugin)],
+  );
+  let output = bundler.generate().await.unwrap();
+
+  assert_eq!(output.assets.len(), 1);
+  let main = chunk_code(&output.assets, "main.js");
+  assert!(main.contains("import('https://cdn.example.com/en.js')"), "{main}");
+  assert!(main.contains("import('lazy-external')"), "{main}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn unresolved_variable_imports_are_left_as_they_are() {
+  let mut bundler = bundler(
+    &[("/main.js", "const page = './home.js'\nimport(page)"), ("/home.js", "")],
+    vec![Box::<ResolveBySource>::default()],
+  );
+  let output = bundler.generate().await.unwrap();
+
+  assert_eq!(output.assets.len(), 1);
+  assert!(chunk_code(&output.assets, "main.js").contains("import(page)"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn literal_imports_of_resolved_ids_are_resolved_on_their_own() {
+  let specifiers = Arc::new(Mutex::new(vec![]));
+  let plugin = ResolveBySource {
+    resolved: vec![
+      ("`./locales/${lang}.js`", "/locales/en.js", None),
+      ("/locales/en.js", "https://cdn.example.com/en.js", Some(true)),
+    ],
+    specifiers: Arc::clone(&specifiers),
+  };
+  let mut bundler = bundler(
+    &[
+      ("/main.js", "const lang = 'en'\nimport(`./locales/${lang}.js`)\nimport('/locales/en.js')"),
+      ("/locales/en.js", "console.log('en')"),
+    ],
+    vec![Box::new(plugin)],
+  );
+  let output = bundler.generate().await.unwrap();
+
+  assert_eq!(output.assets.len(), 2);
+  let main = chunk_code(&output.assets, "main.js");
+  assert!(main.contains("import('./locales_en_js-"), "{main}");
+  assert!(main.contains("import('https://cdn.example.com/en.js')"), "{main}");
+  let mut specifiers = specifiers.lock().unwrap().clone();
+  specifiers.sort();
+  assert_eq!(specifiers, ["/locales/en.js", "`./locales/${lang}.js`"]);
+}
@@ -42,6 +42,11 @@ impl Bundler {
   pub async fn scan(&self) -> napi::Result<()> {
     self.scan_impl().await
   }
+
+  #[napi]
+  pub async fn close(&self) -> napi::Result<()> {
+    self.close_impl().await
+  }
 }
 
 impl Bundler {
@@ -53,13 +58,14 @@ impl Bundler {
     NAPI_ENV.set(&env, || {
       let ret = normalize_binding_options(input_opts, output_opts)?;
 
-      Ok(Self {
-        inner: Mutex::new(NativeBundler::with_plugins(
-          ret.input_options,
-          ret.output_options,
-          ret.plugins,
-        )),
-      })
+      let inner = NativeBundler::with_plugins(ret.input_options, ret.output_options, ret.plugins)
+        .map_err(|err| {
+        // TODO: better handing errors
+        eprintln!("{err:?}");
+        napi::Error::from_reason("Invalid options")
+      })?;
+
+      Ok(Self { inner: Mutex::new(inner) })
     })
   }
 
@@ -81,6 +87,24 @@ impl Bundler {
     Ok(())
   }
 
+  #[instrument(skip_all)]
+  #[allow(clippy::significant_drop_tightening)]
+  pub async fn close_impl(&self) -> napi::Result<()> {
+    let mut bundler_core = self.inner.try_lock().map_err(|_| {
+      napi::Error::from_reason("Failed to lock the bundler. Is another operation in progress?")
+    })?;
+
+    let result = bundler_core.close().await;
+
+    if let Err(err) = result {
+      // TODO: better handing errors
+      eprintln!("{err:?}");
+      return Err(napi::Error::from_reason("Close failed"));
+    }
+
+    Ok(())
+  }
+
   #[instrument(skip_all)]
   #[allow(clippy::significant_drop_tightening)]
   pub async fn write_impl(&self) -> napi::Result<BindingOutputs> {
@@ -36,8 +36,9 @@ pub struct BindingInputOptions {
     ts_type = "undefined | ((source: string, importer: string | undefined, isResolved: boolean) => boolean)"
   )]
   pub external: Option<JsFunction>,
+  pub dynamic_import_vars: Option<bool>,
   pub input: Vec<BindingInputItem>,
-  // makeAbsoluteExternalsRelative?: boolean | 'ifRelativeSource';
+  pub make_absolute_externals_relative: Option<bool>,
   // /** @deprecated Use the "manualChunks" output option instead. */
   // manualChunks?: ManualChunksOption;
   // maxParallelFileOps?: number;
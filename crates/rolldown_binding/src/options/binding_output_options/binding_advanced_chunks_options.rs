@@ -0,0 +1,64 @@
+use serde::Deserialize;
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BindingAdvancedChunksOptions {
+  pub min_size: Option<u32>,
+  pub max_size: Option<u32>,
+  pub min_share_count: Option<u32>,
+  pub groups: Option<Vec<BindingMatchGroup>>,
+}
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BindingMatchGroup {
+  pub name: String,
+  /// Source of a regular expression that is tested against module ids
+  pub test: Option<String>,
+  pub priority: Option<u32>,
+  pub min_size: Option<u32>,
+  pub max_size: Option<u32>,
+  pub min_share_count: Option<u32>,
+}
+
+impl TryFrom<BindingAdvancedChunksOptions> for rolldown::AdvancedChunksOptions {
+  type Error = napi::Error;
+
+  fn try_from(value: BindingAdvancedChunksOptions) -> Result<Self, Self::Error> {
+    Ok(Self {
+      min_size: value.min_size.map(|v| v as usize),
+      max_size: value.max_size.map(|v| v as usize),
+      min_share_count: value.min_share_count,
+      groups: value
+        .groups
+        .unwrap_or_default()
+        .into_iter()
+        .map(TryInto::try_into)
+        .collect::<Result<Vec<_>, _>>()?,
+    })
+  }
+}
+
+impl TryFrom<BindingMatchGroup> for rolldown::MatchGroup {
+  type Error = napi::Error;
+
+  fn try_from(value: BindingMatchGroup) -> Result<Self, Self::Error> {
+    let test = value
+      .test
+      .map(|test| regex::Regex::new(&test).map(rolldown::MatchGroupTest::Regex))
+      .transpose()
+      .map_err(|e| {
+        napi::Error::from_reason(format!("Invalid test of group {:?}: {e}", value.name))
+      })?;
+    Ok(Self {
+      name: value.name,
+      test,
+      priority: value.priority,
+      min_size: value.min_size.map(|v| v as usize),
+      max_size: value.max_size.map(|v| v as usize),
+      min_share_count: value.min_share_count,
+    })
+  }
+}
@@ -1,11 +1,26 @@
+use std::collections::HashMap;
+
+use derivative::Derivative;
+use napi::{Either, JsFunction};
 use napi_derive::napi;
 use serde::Deserialize;
 
-use super::plugin::PluginOptions;
+use crate::utils::JsCallback;
+
+use self::binding_advanced_chunks_options::BindingAdvancedChunksOptions;
+
+use super::plugin::{PluginOptions, RenderedChunk};
+
+mod binding_advanced_chunks_options;
+
+pub type AddonOutputFn = JsCallback<(RenderedChunk,), Option<String>>;
+pub type PathsOutputFn = JsCallback<(String,), String>;
+pub type SanitizeFileNameFn = JsCallback<(String,), String>;
 
 #[napi(object)]
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Derivative)]
 #[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
 pub struct BindingOutputOptions {
   // --- Options Rolldown doesn't need to be supported
   // /** @deprecated Use the "renderDynamicImport" plugin hook instead. */
@@ -14,8 +29,11 @@ pub struct BindingOutputOptions {
   pub chunk_file_names: Option<String>,
 
   // amd: NormalizedAmdOptions;
-  // assetFileNames: string | ((chunkInfo: PreRenderedAsset) => string);
-  // banner: () => string | Promise<string>;
+  pub asset_file_names: Option<String>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(chunk: RenderedChunk) => Promise<undefined | string>")]
+  pub banner: Option<JsFunction>,
   // chunkFileNames: string | ((chunkInfo: PreRenderedChunk) => string);
   // compact: boolean;
   pub dir: Option<String>,
@@ -23,31 +41,50 @@ pub struct BindingOutputOptions {
   // esModule: boolean;
   #[napi(ts_type = "'default' | 'named' | 'none' | 'auto'")]
   pub exports: Option<String>,
+  pub experimental_min_chunk_size: Option<u32>,
   // extend: boolean;
   // externalLiveBindings: boolean;
-  // footer: () => string | Promise<string>;
-  #[napi(ts_type = "'esm' | 'cjs'")]
+  pub file: Option<String>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(chunk: RenderedChunk) => Promise<undefined | string>")]
+  pub footer: Option<JsFunction>,
+  #[napi(ts_type = "'esm' | 'cjs' | 'iife' | 'umd' | 'system'")]
   pub format: Option<String>,
   // freeze: boolean;
   // generatedCode: NormalizedGeneratedCodeOptions;
-  // globals: GlobalsOption;
+  pub globals: Option<HashMap<String, String>>,
   // hoistTransitiveImports: boolean;
   // indent: true | string;
-  // inlineDynamicImports: boolean;
+  pub inline_dynamic_imports: Option<bool>,
   // interop: GetInterop;
-  // intro: () => string | Promise<string>;
-  // manualChunks: ManualChunksOption;
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(chunk: RenderedChunk) => Promise<undefined | string>")]
+  pub intro: Option<JsFunction>,
+  // TODO: support the function form of `manualChunks`
+  pub manual_chunks: Option<HashMap<String, Vec<String>>>,
+  pub advanced_chunks: Option<BindingAdvancedChunksOptions>,
   // minifyInternalExports: boolean;
-  // name: string | undefined;
+  pub name: Option<String>,
   // namespaceToStringTag: boolean;
   // noConflict: boolean;
-  // outro: () => string | Promise<string>;
-  // paths: OptionsPaths;
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(chunk: RenderedChunk) => Promise<undefined | string>")]
+  pub outro: Option<JsFunction>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(id: string) => string")]
+  pub paths: Option<JsFunction>,
   pub plugins: Vec<PluginOptions>,
   // preferConst: boolean;
-  // preserveModules: boolean;
-  // preserveModulesRoot: string | undefined;
-  // sanitizeFileName: (fileName: string) => string;
+  pub preserve_modules: Option<bool>,
+  pub preserve_modules_root: Option<String>,
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "boolean | ((name: string) => string)")]
+  pub sanitize_file_name: Option<Either<bool, JsFunction>>,
   #[napi(ts_type = "'file' | 'inline' | 'hidden'")]
   pub sourcemap: Option<String>,
   // sourcemapExcludeSources: boolean;
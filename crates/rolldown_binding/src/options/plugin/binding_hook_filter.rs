@@ -0,0 +1,108 @@
+use derivative::Derivative;
+use rolldown_common::ModuleType;
+use rolldown_plugin::{FilterPattern, HookFilter, PatternFilter};
+use serde::Deserialize;
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct BindingFilterPattern {
+  /// A glob when filtering ids, or a substring when filtering code
+  pub value: Option<String>,
+  /// Source of a regular expression
+  pub regex: Option<String>,
+}
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct BindingPatternFilter {
+  pub include: Option<Vec<BindingFilterPattern>>,
+  pub exclude: Option<Vec<BindingFilterPattern>>,
+}
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct BindingHookFilter {
+  pub id: Option<BindingPatternFilter>,
+  pub code: Option<BindingPatternFilter>,
+  #[napi(
+    ts_type = "Array<'unknown' | 'cjs' | 'cjs-package-json' | 'esm-mjs' | 'esm-package-json'>"
+  )]
+  pub module_type: Option<Vec<String>>,
+}
+
+impl TryFrom<BindingHookFilter> for HookFilter {
+  type Error = napi::Error;
+
+  fn try_from(value: BindingHookFilter) -> Result<Self, Self::Error> {
+    Ok(Self {
+      id: value.id.map(|filter| normalize_pattern_filter(filter, PatternKind::Id)).transpose()?,
+      code: value
+        .code
+        .map(|filter| normalize_pattern_filter(filter, PatternKind::Code))
+        .transpose()?,
+      module_type: value
+        .module_type
+        .map(|module_types| module_types.iter().map(|ty| normalize_module_type(ty)).collect())
+        .transpose()?,
+    })
+  }
+}
+
+#[derive(Clone, Copy)]
+enum PatternKind {
+  Id,
+  Code,
+}
+
+fn normalize_pattern_filter(
+  filter: BindingPatternFilter,
+  kind: PatternKind,
+) -> napi::Result<PatternFilter> {
+  let normalize_patterns = |patterns: Option<Vec<BindingFilterPattern>>| {
+    patterns
+      .unwrap_or_default()
+      .into_iter()
+      .map(|pattern| normalize_pattern(pattern, kind))
+      .collect::<napi::Result<Vec<_>>>()
+  };
+  Ok(PatternFilter {
+    include: normalize_patterns(filter.include)?,
+    exclude: normalize_patterns(filter.exclude)?,
+  })
+}
+
+fn normalize_pattern(
+  pattern: BindingFilterPattern,
+  kind: PatternKind,
+) -> napi::Result<FilterPattern> {
+  match (pattern.value, pattern.regex, kind) {
+    (_, Some(regex), _) => regex::Regex::new(&regex).map(FilterPattern::Regex).map_err(|e| {
+      napi::Error::from_reason(format!("Invalid regex {regex:?} of hook filter: {e}"))
+    }),
+    (Some(glob), None, PatternKind::Id) => FilterPattern::glob(&glob)
+      .map_err(|e| napi::Error::from_reason(format!("Invalid glob {glob:?} of hook filter: {e}"))),
+    (Some(substring), None, PatternKind::Code) => Ok(FilterPattern::Contains(substring)),
+    (None, None, _) => {
+      Err(napi::Error::from_reason("A pattern of hook filter should have a value or a regex"))
+    }
+  }
+}
+
+fn normalize_module_type(module_type: &str) -> napi::Result<ModuleType> {
+  match module_type {
+    "unknown" => Ok(ModuleType::Unknown),
+    "cjs" => Ok(ModuleType::CJS),
+    "cjs-package-json" => Ok(ModuleType::CjsPackageJson),
+    "esm-mjs" => Ok(ModuleType::EsmMjs),
+    "esm-package-json" => Ok(ModuleType::EsmPackageJson),
+    _ => {
+      Err(napi::Error::from_reason(format!("Unknown module type {module_type:?} of hook filter")))
+    }
+  }
+}
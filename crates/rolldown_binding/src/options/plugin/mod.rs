@@ -1,5 +1,7 @@
+mod binding_hook_filter;
 mod plugin;
 mod plugin_adapter;
 
+pub use binding_hook_filter::*;
 pub use plugin::*;
 pub use plugin_adapter::*;
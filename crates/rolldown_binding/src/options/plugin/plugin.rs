@@ -5,6 +5,7 @@ use napi::JsFunction;
 use rolldown_error::BuildError;
 use serde::Deserialize;
 
+use super::BindingHookFilter;
 use crate::types::binding_rendered_module::BindingRenderedModule;
 
 #[napi_derive::napi(object)]
@@ -19,6 +20,8 @@ pub struct PluginOptions {
   #[napi(ts_type = "() => Promise<void>")]
   pub build_start: Option<JsFunction>,
 
+  pub build_start_meta: Option<BindingPluginHookMeta>,
+
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(
@@ -26,21 +29,65 @@ pub struct PluginOptions {
   )]
   pub resolve_id: Option<JsFunction>,
 
+  pub resolve_id_meta: Option<BindingPluginHookMeta>,
+
+  pub resolve_id_filter: Option<BindingHookFilter>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(
+    ts_type = "(specifier: BindingDynamicImportSpecifier, importer: string) => Promise<undefined | ResolveIdResult>"
+  )]
+  pub resolve_dynamic_import: Option<JsFunction>,
+
+  pub resolve_dynamic_import_meta: Option<BindingPluginHookMeta>,
+
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(ts_type = "(id: string) => Promise<undefined | SourceResult>")]
   pub load: Option<JsFunction>,
 
+  pub load_meta: Option<BindingPluginHookMeta>,
+
+  pub load_filter: Option<BindingHookFilter>,
+
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(ts_type = "(id: string, code: string) => Promise<undefined | SourceResult>")]
   pub transform: Option<JsFunction>,
 
+  pub transform_meta: Option<BindingPluginHookMeta>,
+
+  pub transform_filter: Option<BindingHookFilter>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(moduleInfo: BindingModuleInfo) => Promise<void>")]
+  pub module_parsed: Option<JsFunction>,
+
+  pub module_parsed_meta: Option<BindingPluginHookMeta>,
+
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(ts_type = "(error: string) => Promise<void>")]
   pub build_end: Option<JsFunction>,
 
+  pub build_end_meta: Option<BindingPluginHookMeta>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(id: string, event: 'create' | 'update' | 'delete') => Promise<void>")]
+  pub watch_change: Option<JsFunction>,
+
+  pub watch_change_meta: Option<BindingPluginHookMeta>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(outputOptions: BindingNormalizedOutputOptions) => Promise<void>")]
+  pub render_start: Option<JsFunction>,
+
+  pub render_start_meta: Option<BindingPluginHookMeta>,
+
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(
@@ -48,15 +95,64 @@ pub struct PluginOptions {
   )]
   pub render_chunk: Option<JsFunction>,
 
+  pub render_chunk_meta: Option<BindingPluginHookMeta>,
+
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(ts_type = "(bundle: Outputs, isWrite: boolean) => Promise<void>")]
   pub generate_bundle: Option<JsFunction>,
 
+  pub generate_bundle_meta: Option<BindingPluginHookMeta>,
+
   #[derivative(Debug = "ignore")]
   #[serde(skip_deserializing)]
   #[napi(ts_type = "(bundle: Outputs) => Promise<void>")]
   pub write_bundle: Option<JsFunction>,
+
+  pub write_bundle_meta: Option<BindingPluginHookMeta>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "(error: string) => Promise<void>")]
+  pub render_error: Option<JsFunction>,
+
+  pub render_error_meta: Option<BindingPluginHookMeta>,
+
+  #[derivative(Debug = "ignore")]
+  #[serde(skip_deserializing)]
+  #[napi(ts_type = "() => Promise<void>")]
+  pub close_bundle: Option<JsFunction>,
+
+  pub close_bundle_meta: Option<BindingPluginHookMeta>,
+}
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct BindingPluginHookMeta {
+  #[napi(ts_type = "'pre' | 'post'")]
+  pub order: Option<String>,
+  pub sequential: Option<bool>,
+}
+
+impl TryFrom<BindingPluginHookMeta> for rolldown_plugin::PluginHookMeta {
+  type Error = napi::Error;
+
+  fn try_from(value: BindingPluginHookMeta) -> Result<Self, Self::Error> {
+    let order = match value.order.as_deref() {
+      None => rolldown_plugin::PluginOrder::Normal,
+      Some("pre") => rolldown_plugin::PluginOrder::Pre,
+      Some("post") => rolldown_plugin::PluginOrder::Post,
+      Some(order) => {
+        return Err(napi::Error::new(
+          napi::Status::InvalidArg,
+          format!("Invalid hook order \"{order}\", expected \"pre\" or \"post\"."),
+        ))
+      }
+    };
+    Ok(Self { order, sequential: value.sequential.unwrap_or_default() })
+  }
 }
 
 #[napi_derive::napi(object)]
@@ -74,6 +170,46 @@ impl From<rolldown_plugin::HookResolveIdExtraOptions> for HookResolveIdArgsOptio
   }
 }
 
+/// Either `value` or `expression` is set.
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct BindingDynamicImportSpecifier {
+  /// The specifier of `import('./foo.js')`
+  pub value: Option<String>,
+  /// The argument of `import(`./locales/${lang}.js`)`
+  pub expression: Option<BindingDynamicImportExpression>,
+}
+
+#[napi_derive::napi(object)]
+#[derive(Deserialize, Default, Derivative)]
+#[serde(rename_all = "camelCase")]
+#[derivative(Debug)]
+pub struct BindingDynamicImportExpression {
+  pub source_text: String,
+  pub start: u32,
+  pub end: u32,
+}
+
+impl From<rolldown_plugin::DynamicImportSpecifier<'_>> for BindingDynamicImportSpecifier {
+  fn from(value: rolldown_plugin::DynamicImportSpecifier<'_>) -> Self {
+    match value {
+      rolldown_plugin::DynamicImportSpecifier::Str(value) => {
+        Self { value: Some(value.to_string()), expression: None }
+      }
+      rolldown_plugin::DynamicImportSpecifier::Expression { source_text, span } => Self {
+        value: None,
+        expression: Some(BindingDynamicImportExpression {
+          source_text: source_text.to_string(),
+          start: span.start,
+          end: span.end,
+        }),
+      },
+    }
+  }
+}
+
 #[napi_derive::napi(object)]
 #[derive(Deserialize, Default, Derivative)]
 #[serde(rename_all = "camelCase")]
@@ -121,11 +257,23 @@ impl TryFrom<SourceResult> for rolldown_plugin::HookLoadOutput {
 #[derivative(Debug)]
 pub struct HookRenderChunkOutput {
   pub code: String,
+  pub map: Option<String>,
 }
 
-impl From<HookRenderChunkOutput> for rolldown_plugin::HookRenderChunkOutput {
-  fn from(value: HookRenderChunkOutput) -> Self {
-    Self { code: value.code }
+impl TryFrom<HookRenderChunkOutput> for rolldown_plugin::HookRenderChunkOutput {
+  type Error = BuildError;
+
+  fn try_from(value: HookRenderChunkOutput) -> Result<Self, Self::Error> {
+    Ok(Self {
+      code: value.code,
+      map: value
+        .map
+        .map(|content| {
+          rolldown_sourcemap::SourceMap::from_slice(content.as_bytes())
+            .map_err(|e| BuildError::sourcemap_error(e.to_string()))
+        })
+        .transpose()?,
+    })
   }
 }
 
@@ -1,24 +1,36 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashMap};
 
+use crate::types::{
+  binding_module_info::BindingModuleInfo,
+  binding_normalized_output_options::BindingNormalizedOutputOptions,
+  binding_outputs::BindingOutputs,
+};
+use crate::utils::napi_error_ext::NapiErrorExt;
 use crate::utils::JsCallback;
-use crate::{types::binding_outputs::BindingOutputs, utils::napi_error_ext::NapiErrorExt};
 use derivative::Derivative;
-use rolldown_plugin::Plugin;
+use rolldown_plugin::{HookFilter, Plugin, PluginHook, PluginHookMeta};
 
 use super::plugin::{
-  HookRenderChunkOutput, HookResolveIdArgsOptions, PluginOptions, RenderedChunk, ResolveIdResult,
-  SourceResult,
+  BindingDynamicImportSpecifier, HookRenderChunkOutput, HookResolveIdArgsOptions, PluginOptions,
+  RenderedChunk, ResolveIdResult, SourceResult,
 };
 
 pub type BuildStartCallback = JsCallback<(), ()>;
 pub type ResolveIdCallback =
   JsCallback<(String, Option<String>, HookResolveIdArgsOptions), Option<ResolveIdResult>>;
+pub type ResolveDynamicImportCallback =
+  JsCallback<(BindingDynamicImportSpecifier, String), Option<ResolveIdResult>>;
 pub type LoadCallback = JsCallback<(String,), Option<SourceResult>>;
 pub type TransformCallback = JsCallback<(String, String), Option<SourceResult>>;
+pub type ModuleParsedCallback = JsCallback<(BindingModuleInfo,), ()>;
 pub type BuildEndCallback = JsCallback<(Option<String>,), ()>;
+pub type WatchChangeCallback = JsCallback<(String, String), ()>;
+pub type RenderStartCallback = JsCallback<(BindingNormalizedOutputOptions,), ()>;
 pub type RenderChunkCallback = JsCallback<(String, RenderedChunk), Option<HookRenderChunkOutput>>;
 pub type GenerateBundleCallback = JsCallback<(BindingOutputs, bool), Option<HookRenderChunkOutput>>;
 pub type WriteBundleCallback = JsCallback<(BindingOutputs,), ()>;
+pub type RenderErrorCallback = JsCallback<(String,), ()>;
+pub type CloseBundleCallback = JsCallback<(), ()>;
 
 #[derive(Derivative)]
 #[derivative(Debug)]
@@ -29,40 +41,97 @@ pub struct JsAdapterPlugin {
   #[derivative(Debug = "ignore")]
   resolve_id_fn: Option<ResolveIdCallback>,
   #[derivative(Debug = "ignore")]
+  resolve_dynamic_import_fn: Option<ResolveDynamicImportCallback>,
+  #[derivative(Debug = "ignore")]
   load_fn: Option<LoadCallback>,
   #[derivative(Debug = "ignore")]
   transform_fn: Option<TransformCallback>,
   #[derivative(Debug = "ignore")]
+  module_parsed_fn: Option<ModuleParsedCallback>,
+  #[derivative(Debug = "ignore")]
   build_end_fn: Option<BuildEndCallback>,
   #[derivative(Debug = "ignore")]
+  watch_change_fn: Option<WatchChangeCallback>,
+  #[derivative(Debug = "ignore")]
+  render_start_fn: Option<RenderStartCallback>,
+  #[derivative(Debug = "ignore")]
   render_chunk_fn: Option<RenderChunkCallback>,
   #[derivative(Debug = "ignore")]
   generate_bundle_fn: Option<GenerateBundleCallback>,
   #[derivative(Debug = "ignore")]
   write_bundle_fn: Option<WriteBundleCallback>,
+  #[derivative(Debug = "ignore")]
+  render_error_fn: Option<RenderErrorCallback>,
+  #[derivative(Debug = "ignore")]
+  close_bundle_fn: Option<CloseBundleCallback>,
+  hook_metas: HashMap<PluginHook, PluginHookMeta>,
+  hook_filters: HashMap<PluginHook, HookFilter>,
 }
 
 impl JsAdapterPlugin {
-  pub fn new(option: PluginOptions) -> napi::Result<Self> {
+  pub fn new(mut option: PluginOptions) -> napi::Result<Self> {
+    let hook_metas = [
+      (PluginHook::BuildStart, option.build_start_meta.take()),
+      (PluginHook::ResolveId, option.resolve_id_meta.take()),
+      (PluginHook::ResolveDynamicImport, option.resolve_dynamic_import_meta.take()),
+      (PluginHook::Load, option.load_meta.take()),
+      (PluginHook::Transform, option.transform_meta.take()),
+      (PluginHook::ModuleParsed, option.module_parsed_meta.take()),
+      (PluginHook::BuildEnd, option.build_end_meta.take()),
+      (PluginHook::WatchChange, option.watch_change_meta.take()),
+      (PluginHook::RenderStart, option.render_start_meta.take()),
+      (PluginHook::RenderChunk, option.render_chunk_meta.take()),
+      (PluginHook::GenerateBundle, option.generate_bundle_meta.take()),
+      (PluginHook::WriteBundle, option.write_bundle_meta.take()),
+      (PluginHook::RenderError, option.render_error_meta.take()),
+      (PluginHook::CloseBundle, option.close_bundle_meta.take()),
+    ]
+    .into_iter()
+    .filter_map(|(hook, meta)| Some(meta?.try_into().map(|meta| (hook, meta))))
+    .collect::<napi::Result<_>>()?;
+    let hook_filters = [
+      (PluginHook::ResolveId, option.resolve_id_filter.take()),
+      (PluginHook::Load, option.load_filter.take()),
+      (PluginHook::Transform, option.transform_filter.take()),
+    ]
+    .into_iter()
+    .filter_map(|(hook, filter)| Some(filter?.try_into().map(|filter| (hook, filter))))
+    .collect::<napi::Result<_>>()?;
     let build_start_fn = option.build_start.as_ref().map(BuildStartCallback::new).transpose()?;
     let resolve_id_fn = option.resolve_id.as_ref().map(ResolveIdCallback::new).transpose()?;
+    let resolve_dynamic_import_fn =
+      option.resolve_dynamic_import.as_ref().map(ResolveDynamicImportCallback::new).transpose()?;
     let load_fn = option.load.as_ref().map(LoadCallback::new).transpose()?;
     let transform_fn = option.transform.as_ref().map(TransformCallback::new).transpose()?;
+    let module_parsed_fn =
+      option.module_parsed.as_ref().map(ModuleParsedCallback::new).transpose()?;
     let build_end_fn = option.build_end.as_ref().map(BuildEndCallback::new).transpose()?;
+    let watch_change_fn = option.watch_change.as_ref().map(WatchChangeCallback::new).transpose()?;
+    let render_start_fn = option.render_start.as_ref().map(RenderStartCallback::new).transpose()?;
     let render_chunk_fn = option.render_chunk.as_ref().map(RenderChunkCallback::new).transpose()?;
     let generate_bundle_fn =
       option.generate_bundle.as_ref().map(GenerateBundleCallback::new).transpose()?;
     let write_bundle_fn = option.write_bundle.as_ref().map(WriteBundleCallback::new).transpose()?;
+    let render_error_fn = option.render_error.as_ref().map(RenderErrorCallback::new).transpose()?;
+    let close_bundle_fn = option.close_bundle.as_ref().map(CloseBundleCallback::new).transpose()?;
     Ok(Self {
       name: option.name,
       build_start_fn,
       resolve_id_fn,
+      resolve_dynamic_import_fn,
       load_fn,
       transform_fn,
+      module_parsed_fn,
       build_end_fn,
+      watch_change_fn,
+      render_start_fn,
       render_chunk_fn,
       generate_bundle_fn,
       write_bundle_fn,
+      render_error_fn,
+      close_bundle_fn,
+      hook_metas,
+      hook_filters,
     })
   }
 
@@ -77,6 +146,14 @@ impl Plugin for JsAdapterPlugin {
     Cow::Owned(self.name.to_string())
   }
 
+  fn hook_meta(&self, hook: PluginHook) -> PluginHookMeta {
+    self.hook_metas.get(&hook).copied().unwrap_or_default()
+  }
+
+  fn hook_filter(&self, hook: PluginHook) -> Option<HookFilter> {
+    self.hook_filters.get(&hook).cloned()
+  }
+
   #[allow(clippy::redundant_closure_for_method_calls)]
   async fn build_start(
     &self,
@@ -110,6 +187,24 @@ impl Plugin for JsAdapterPlugin {
     }
   }
 
+  #[allow(clippy::redundant_closure_for_method_calls)]
+  async fn resolve_dynamic_import(
+    &self,
+    _ctx: &rolldown_plugin::PluginContext,
+    args: &rolldown_plugin::HookResolveDynamicImportArgs,
+  ) -> rolldown_plugin::HookResolveIdReturn {
+    if let Some(cb) = &self.resolve_dynamic_import_fn {
+      let res = cb
+        .call_async((args.specifier.into(), args.importer.to_string()))
+        .await
+        .map_err(|e| e.into_bundle_error())?;
+
+      Ok(res.map(Into::into))
+    } else {
+      Ok(None)
+    }
+  }
+
   #[allow(clippy::redundant_closure_for_method_calls)]
   async fn load(
     &self,
@@ -141,6 +236,18 @@ impl Plugin for JsAdapterPlugin {
     }
   }
 
+  #[allow(clippy::redundant_closure_for_method_calls)]
+  async fn module_parsed(
+    &self,
+    _ctx: &rolldown_plugin::PluginContext,
+    module_info: &rolldown_common::ModuleInfo,
+  ) -> rolldown_plugin::HookNoopReturn {
+    if let Some(cb) = &self.module_parsed_fn {
+      cb.call_async((module_info.clone().into(),)).await.map_err(|e| e.into_bundle_error())?;
+    }
+    Ok(())
+  }
+
   #[allow(clippy::redundant_closure_for_method_calls)]
   async fn build_end(
     &self,
@@ -155,6 +262,32 @@ impl Plugin for JsAdapterPlugin {
     Ok(())
   }
 
+  #[allow(clippy::redundant_closure_for_method_calls)]
+  async fn watch_change(
+    &self,
+    _ctx: &rolldown_plugin::PluginContext,
+    args: &rolldown_plugin::HookWatchChangeArgs,
+  ) -> rolldown_plugin::HookNoopReturn {
+    if let Some(cb) = &self.watch_change_fn {
+      cb.call_async((args.id.to_string(), args.event.to_string()))
+        .await
+        .map_err(|e| e.into_bundle_error())?;
+    }
+    Ok(())
+  }
+
+  #[allow(clippy::redundant_closure_for_method_calls)]
+  async fn render_start(
+    &self,
+    _ctx: &rolldown_plugin::PluginContext,
+    args: &rolldown_plugin::HookRenderStartArgs,
+  ) -> rolldown_plugin::HookNoopReturn {
+    if let Some(cb) = &self.render_start_fn {
+      cb.call_async((args.output_options.into(),)).await.map_err(|e| e.into_bundle_error())?;
+    }
+    Ok(())
+  }
+
   #[allow(clippy::redundant_closure_for_method_calls)]
   async fn render_chunk(
     &self,
@@ -166,7 +299,7 @@ impl Plugin for JsAdapterPlugin {
         .call_async((args.code.to_string(), args.chunk.clone().into()))
         .await
         .map_err(|e| e.into_bundle_error())?;
-      return Ok(res.map(Into::into));
+      return res.map(TryInto::try_into).transpose();
     }
     Ok(None)
   }
@@ -195,4 +328,27 @@ impl Plugin for JsAdapterPlugin {
     }
     Ok(())
   }
+
+  #[allow(clippy::redundant_closure_for_method_calls)]
+  async fn render_error(
+    &self,
+    _ctx: &rolldown_plugin::PluginContext,
+    args: &rolldown_plugin::HookRenderErrorArgs,
+  ) -> rolldown_plugin::HookNoopReturn {
+    if let Some(cb) = &self.render_error_fn {
+      cb.call_async((args.error.to_string(),)).await.map_err(|e| e.into_bundle_error())?;
+    }
+    Ok(())
+  }
+
+  #[allow(clippy::redundant_closure_for_method_calls)]
+  async fn close_bundle(
+    &self,
+    _ctx: &rolldown_plugin::PluginContext,
+  ) -> rolldown_plugin::HookNoopReturn {
+    if let Some(cb) = &self.close_bundle_fn {
+      cb.call_async(()).await.map_err(|e| e.into_bundle_error())?;
+    }
+    Ok(())
+  }
 }
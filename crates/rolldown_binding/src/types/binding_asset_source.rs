@@ -0,0 +1,39 @@
+use napi::{
+  bindgen_prelude::{Buffer, FromNapiValue, ToNapiValue, TypeName},
+  sys, Either, ValueType,
+};
+use rolldown_common::AssetSource;
+
+/// The source of an asset, which is a `string` or a `Buffer` in JS. `Buffer` can't be sent across threads, so the
+/// conversion is delayed until the value is passed to JS.
+#[derive(Debug)]
+pub struct BindingAssetSource(pub AssetSource);
+
+impl TypeName for BindingAssetSource {
+  fn type_name() -> &'static str {
+    "string | Buffer"
+  }
+
+  fn value_type() -> ValueType {
+    ValueType::Unknown
+  }
+}
+
+impl ToNapiValue for BindingAssetSource {
+  unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> napi::Result<sys::napi_value> {
+    match val.0 {
+      AssetSource::String(value) => String::to_napi_value(env, value),
+      AssetSource::Buffer(value) => Buffer::to_napi_value(env, value.into()),
+    }
+  }
+}
+
+impl FromNapiValue for BindingAssetSource {
+  unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+    let source = match Either::<String, Buffer>::from_napi_value(env, napi_val)? {
+      Either::A(value) => AssetSource::String(value),
+      Either::B(value) => AssetSource::Buffer(value.to_vec()),
+    };
+    Ok(Self(source))
+  }
+}
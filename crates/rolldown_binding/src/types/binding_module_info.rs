@@ -0,0 +1,21 @@
+use napi_derive::napi;
+
+#[napi(object)]
+#[derive(Debug)]
+pub struct BindingModuleInfo {
+  pub id: String,
+  pub is_entry: bool,
+  pub imported_ids: Vec<String>,
+  pub dynamically_imported_ids: Vec<String>,
+}
+
+impl From<rolldown_common::ModuleInfo> for BindingModuleInfo {
+  fn from(value: rolldown_common::ModuleInfo) -> Self {
+    Self {
+      id: value.id,
+      is_entry: value.is_entry,
+      imported_ids: value.imported_ids,
+      dynamically_imported_ids: value.dynamically_imported_ids,
+    }
+  }
+}
@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+
+use napi_derive::napi;
+use rolldown_common::NormalizedOutputOptions;
+
+/// Normalized output options passed to the `renderStart` hook. Options with function values are left out.
+#[napi(object)]
+#[derive(Debug)]
+pub struct BindingNormalizedOutputOptions {
+  pub dir: String,
+  pub file: Option<String>,
+  #[napi(ts_type = "'esm' | 'cjs' | 'iife' | 'umd' | 'system'")]
+  pub format: String,
+  #[napi(ts_type = "'default' | 'named' | 'none' | 'auto'")]
+  pub exports: String,
+  #[napi(ts_type = "'file' | 'inline' | 'hidden'")]
+  pub sourcemap: String,
+  pub name: Option<String>,
+  pub globals: HashMap<String, String>,
+  pub entry_file_names: String,
+  pub chunk_file_names: String,
+  pub asset_file_names: String,
+  pub inline_dynamic_imports: bool,
+  pub preserve_modules: bool,
+  pub preserve_modules_root: Option<String>,
+}
+
+impl From<&NormalizedOutputOptions> for BindingNormalizedOutputOptions {
+  fn from(options: &NormalizedOutputOptions) -> Self {
+    Self {
+      dir: options.dir.clone(),
+      file: options.file.clone(),
+      format: options.format.to_string(),
+      exports: options.exports.to_string(),
+      sourcemap: options.sourcemap.to_string(),
+      name: options.name.clone(),
+      globals: options.globals.clone(),
+      entry_file_names: options.entry_file_names.template().to_string(),
+      chunk_file_names: options.chunk_file_names.template().to_string(),
+      asset_file_names: options.asset_file_names.template().to_string(),
+      inline_dynamic_imports: options.inline_dynamic_imports,
+      preserve_modules: options.preserve_modules,
+      preserve_modules_root: options.preserve_modules_root.clone(),
+    }
+  }
+}
@@ -1,17 +1,18 @@
 use derivative::Derivative;
-use serde::Deserialize;
+
+use super::binding_asset_source::BindingAssetSource;
 
 #[napi_derive::napi(object)]
-#[derive(Deserialize, Default, Derivative)]
-#[serde(rename_all = "camelCase")]
+#[derive(Derivative)]
 #[derivative(Debug)]
 pub struct BindingOutputAsset {
   pub file_name: String,
-  pub source: String,
+  #[napi(ts_type = "string | Buffer")]
+  pub source: BindingAssetSource,
 }
 
 impl From<Box<rolldown_common::OutputAsset>> for BindingOutputAsset {
   fn from(chunk: Box<rolldown_common::OutputAsset>) -> Self {
-    Self { source: chunk.source, file_name: chunk.file_name }
+    Self { source: BindingAssetSource(chunk.source), file_name: chunk.file_name }
   }
 }
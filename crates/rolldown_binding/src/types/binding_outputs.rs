@@ -1,11 +1,9 @@
 use derivative::Derivative;
-use serde::Deserialize;
 
 use super::{binding_output_asset::BindingOutputAsset, binding_output_chunk::BindingOutputChunk};
 
 #[napi_derive::napi(object)]
-#[derive(Deserialize, Default, Derivative)]
-#[serde(rename_all = "camelCase")]
+#[derive(Default, Derivative)]
 #[derivative(Debug)]
 pub struct BindingOutputs {
   pub chunks: Vec<BindingOutputChunk>,
@@ -1,3 +1,6 @@
+pub mod binding_asset_source;
+pub mod binding_module_info;
+pub mod binding_normalized_output_options;
 pub mod binding_output_asset;
 pub mod binding_output_chunk;
 pub mod binding_outputs;
@@ -1,10 +1,15 @@
 use std::path::PathBuf;
 
-use rolldown::{InputOptions, OutputOptions};
+use napi::{Either, JsFunction};
+use rolldown::{
+  AddonOutputOption, InputOptions, OutputOptions, PathsOutputOption, SanitizeFileName,
+};
 use rolldown_plugin::BoxPlugin;
 
 use crate::{
-  options::{plugin::JsAdapterPlugin, ExternalFn},
+  options::{
+    plugin::JsAdapterPlugin, AddonOutputFn, ExternalFn, PathsOutputFn, SanitizeFileNameFn,
+  },
   utils::napi_error_ext::NapiErrorExt,
 };
 
@@ -14,6 +19,58 @@ pub struct NormalizeBindingOptionsReturn {
   pub plugins: Vec<BoxPlugin>,
 }
 
+fn normalize_addon_option(
+  addon_option: Option<JsFunction>,
+) -> napi::Result<Option<AddonOutputOption>> {
+  addon_option
+    .map(|js_fn| {
+      AddonOutputFn::new(&js_fn).map(|addon_fn| {
+        AddonOutputOption::Fn(Box::new(move |chunk| {
+          let ts_fn = addon_fn.clone();
+          Box::pin(async move {
+            ts_fn.call_async((chunk.into(),)).await.map_err(NapiErrorExt::into_bundle_error)
+          })
+        }))
+      })
+    })
+    .transpose()
+}
+
+fn normalize_paths_option(
+  paths_option: Option<JsFunction>,
+) -> napi::Result<Option<PathsOutputOption>> {
+  paths_option
+    .map(|js_fn| {
+      PathsOutputFn::new(&js_fn).map(|paths_fn| {
+        PathsOutputOption::Fn(Box::new(move |id| {
+          let ts_fn = paths_fn.clone();
+          Box::pin(
+            async move { ts_fn.call_async((id,)).await.map_err(NapiErrorExt::into_bundle_error) },
+          )
+        }))
+      })
+    })
+    .transpose()
+}
+
+fn normalize_sanitize_file_name_option(
+  sanitize_option: Option<Either<bool, JsFunction>>,
+) -> napi::Result<Option<SanitizeFileName>> {
+  sanitize_option
+    .map(|option| match option {
+      Either::A(value) => Ok(SanitizeFileName::Boolean(value)),
+      Either::B(js_fn) => SanitizeFileNameFn::new(&js_fn).map(|sanitize_fn| {
+        SanitizeFileName::Fn(Box::new(move |name| {
+          let ts_fn = sanitize_fn.clone();
+          Box::pin(async move {
+            ts_fn.call_async((name,)).await.map_err(NapiErrorExt::into_bundle_error)
+          })
+        }))
+      }),
+    })
+    .transpose()
+}
+
 pub fn normalize_binding_options(
   input_options: crate::options::BindingInputOptions,
   output_options: crate::options::BindingOutputOptions,
@@ -46,6 +103,8 @@ pub fn normalize_binding_options(
     input: input_options.input.into_iter().map(Into::into).collect(),
     cwd: cwd.into(),
     external: external.into(),
+    dynamic_import_vars: input_options.dynamic_import_vars,
+    make_absolute_externals_relative: input_options.make_absolute_externals_relative,
     treeshake: true.into(),
     resolve: input_options.resolve.map(Into::into),
   };
@@ -55,9 +114,28 @@ pub fn normalize_binding_options(
   let normalized_output_options = OutputOptions {
     entry_file_names: output_options.entry_file_names,
     chunk_file_names: output_options.chunk_file_names,
+    asset_file_names: output_options.asset_file_names,
+    sanitize_file_name: normalize_sanitize_file_name_option(output_options.sanitize_file_name)?,
     dir: output_options.dir,
+    file: output_options.file,
+    format: output_options.format.map(Into::into),
+    exports: output_options.exports.map(Into::into),
     sourcemap: output_options.sourcemap.map(Into::into),
-    ..Default::default()
+    name: output_options.name,
+    globals: output_options.globals,
+    paths: normalize_paths_option(output_options.paths)?,
+    banner: normalize_addon_option(output_options.banner)?,
+    footer: normalize_addon_option(output_options.footer)?,
+    intro: normalize_addon_option(output_options.intro)?,
+    outro: normalize_addon_option(output_options.outro)?,
+    manual_chunks: output_options.manual_chunks.map(rolldown::ManualChunks::Map),
+    advanced_chunks: output_options.advanced_chunks.map(TryInto::try_into).transpose()?,
+    experimental_min_chunk_size: output_options
+      .experimental_min_chunk_size
+      .map(|size| size as usize),
+    inline_dynamic_imports: output_options.inline_dynamic_imports,
+    preserve_modules: output_options.preserve_modules,
+    preserve_modules_root: output_options.preserve_modules_root,
   };
 
   // Deal with plugins
@@ -65,11 +65,14 @@ pub fn bundle(file_list: Vec<FileItem>) -> Vec<AssetItem> {
           input,
           cwd: Some("/".into()),
           external: Some(External::ArrayString(vec![])),
+          dynamic_import_vars: None,
+          make_absolute_externals_relative: None,
           treeshake: Some(false),
           resolve: None,
         })
         .with_file_system(memory_fs)
-        .build();
+        .build()
+        .unwrap_or_else(|err| panic!("{err:?}"));
 
       match bundler.write().await {
         Ok(assets) => assets
@@ -77,7 +80,7 @@ pub fn bundle(file_list: Vec<FileItem>) -> Vec<AssetItem> {
           .into_iter()
           .map(|item| AssetItem {
             name: item.file_name().to_string(),
-            content: item.content().to_owned(),
+            content: String::from_utf8_lossy(item.content_as_bytes()).into_owned(),
           })
           .collect::<Vec<_>>(),
         Err(err) => {
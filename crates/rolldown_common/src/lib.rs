@@ -1,20 +1,45 @@
 mod module;
+mod options;
 mod types;
 
 // We don't want internal position adjustment of files affect users, so all items are exported in the root.
 pub use crate::{
   module::external_module::ExternalModule,
   module::normal_module::NormalModule,
+  options::{
+    file_name_template::{FileNameRenderOptions, FileNameTemplate},
+    input_options::{resolve_options::ResolveOptions, External, ExternalFn, InputOptions},
+    normalized_input_options::{NormalizedInputOptions, SharedNormalizedInputOptions},
+    normalized_output_options::NormalizedOutputOptions,
+    output_options::{OutputExports, OutputFormat, OutputOptions, SourceMapType},
+    types::{
+      addon_output_option::{AddonFn, AddonOutputOption},
+      advanced_chunks_options::{
+        AdvancedChunksOptions, MatchGroup, MatchGroupTest, MatchGroupTestFn,
+      },
+      input_item::InputItem,
+      manual_chunks::{ManualChunks, ManualChunksFn},
+      paths_output_option::{PathsFn, PathsOutputOption},
+      sanitize_file_name::{default_sanitize_file_name, SanitizeFileName, SanitizeFileNameFn},
+    },
+  },
+  types::asset_source::AssetSource,
   types::ast_scope::AstScope,
   types::batched_errors::{BatchedErrors, BatchedResult, IntoBatchedResult},
   types::chunk_id::ChunkId,
   types::chunk_kind::ChunkKind,
+  types::emitted_asset::EmittedAsset,
+  types::emitted_chunk::EmittedChunk,
+  types::emitted_file::EmittedFile,
   types::entry_point::{EntryPoint, EntryPointKind},
   types::exports_kind::ExportsKind,
   types::external_module_id::ExternalModuleId,
   types::file_path::{representative_name, FilePath},
-  types::import_record::{ImportKind, ImportRecord, ImportRecordId, RawImportRecord},
+  types::import_record::{
+    ImportKind, ImportRecord, ImportRecordId, ImportRecordKey, RawImportRecord,
+  },
   types::module_id::ModuleId,
+  types::module_info::ModuleInfo,
   types::module_path::ResourceId,
   types::module_type::ModuleType,
   types::named_export::LocalExport,
@@ -2,11 +2,11 @@ use std::{fmt::Debug, sync::Arc};
 
 use crate::{
   types::ast_scope::AstScope, DebugStmtInfoForTreeShaking, ExportsKind, ImportRecord,
-  ImportRecordId, LocalExport, ModuleId, ModuleType, NamedImport, NormalModuleId, ResourceId,
-  StmtInfo, StmtInfos, SymbolRef,
+  ImportRecordId, ImportRecordKey, LocalExport, ModuleId, ModuleType, NamedImport, NormalModuleId,
+  ResourceId, StmtInfo, StmtInfos, SymbolRef,
 };
 use index_vec::IndexVec;
-use oxc::{semantic::SymbolId, span::Span};
+use oxc::semantic::SymbolId;
 use rolldown_rstr::Rstr;
 use rustc_hash::FxHashMap;
 
@@ -27,9 +27,7 @@ pub struct NormalModule {
   /// `stmt_infos[0]` represents the namespace binding statement
   pub stmt_infos: StmtInfos,
   pub import_records: IndexVec<ImportRecordId, ImportRecord>,
-  /// The key is the `Span` of `ImportDeclaration`, `ImportExpression`, `ExportNamedDeclaration`, `ExportAllDeclaration`
-  /// and `CallExpression`(only when the callee is `require`).
-  pub imports: FxHashMap<Span, ImportRecordId>,
+  pub imports: FxHashMap<ImportRecordKey, ImportRecordId>,
   // [[StarExportEntries]] in https://tc39.es/ecma262/#sec-source-text-module-records
   pub star_exports: Vec<ImportRecordId>,
   pub exports_kind: ExportsKind,
@@ -0,0 +1,93 @@
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+
+/// `[hash]` or `[hash:8]`
+static HASH_PATTERN_REGEX: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"\[hash(?::(\d+))?\]").expect("Init HASH_PATTERN_REGEX failed"));
+
+pub const DEFAULT_HASH_LENGTH: usize = 8;
+
+#[derive(Debug)]
+pub struct FileNameTemplate {
+  template: String,
+}
+
+impl FileNameTemplate {
+  #[allow(dead_code)]
+  pub fn new(template: String) -> Self {
+    Self { template }
+  }
+
+  pub fn template(&self) -> &str {
+    &self.template
+  }
+}
+
+impl From<String> for FileNameTemplate {
+  fn from(template: String) -> Self {
+    Self { template }
+  }
+}
+
+#[derive(Default)]
+pub struct FileNameRenderOptions<'me> {
+  pub name: Option<&'me str>,
+  /// Receives the length of the `[hash]` pattern and returns the content to replace it with.
+  pub hash: Option<&'me dyn Fn(usize) -> String>,
+  /// The extension of assets without the leading dot, like `png`.
+  pub ext: Option<&'me str>,
+  /// The extension of assets with the leading dot, like `.png`. It's empty if there is no extension.
+  pub extname: Option<&'me str>,
+}
+
+impl FileNameTemplate {
+  pub fn render(&self, options: &FileNameRenderOptions) -> String {
+    let mut tmp = self.template.clone();
+    if let Some(name) = options.name {
+      tmp = tmp.replace("[name]", name);
+    }
+    if let Some(ext) = options.ext {
+      tmp = tmp.replace("[ext]", ext);
+    }
+    if let Some(extname) = options.extname {
+      tmp = tmp.replace("[extname]", extname);
+    }
+    if let Some(hash) = options.hash {
+      tmp = HASH_PATTERN_REGEX
+        .replace_all(&tmp, |caps: &Captures| hash(hash_length_of(caps)))
+        .into_owned();
+    }
+    tmp
+  }
+
+  pub fn has_name_pattern(&self) -> bool {
+    self.template.contains("[name]")
+  }
+
+  /// Lengths of all `[hash]` patterns in the template.
+  pub fn hash_lengths(&self) -> Vec<usize> {
+    HASH_PATTERN_REGEX.captures_iter(&self.template).map(|caps| hash_length_of(&caps)).collect()
+  }
+}
+
+fn hash_length_of(caps: &Captures) -> usize {
+  // Lengths that overflow `usize` are definitely too long and will be reported later
+  caps.get(1).map_or(DEFAULT_HASH_LENGTH, |len| len.as_str().parse().unwrap_or(usize::MAX))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn render_asset_file_name() {
+    let template = FileNameTemplate::from("assets/[name]-[hash:4].[ext]|[extname]".to_string());
+    let file_name = template.render(&FileNameRenderOptions {
+      name: Some("logo"),
+      hash: Some(&|len| "a".repeat(len)),
+      ext: Some("png"),
+      extname: Some(".png"),
+    });
+    assert_eq!(file_name, "assets/logo-aaaa.png|.png");
+  }
+}
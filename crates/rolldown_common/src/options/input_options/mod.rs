@@ -63,6 +63,11 @@ pub struct InputOptions {
   pub input: Vec<InputItem>,
   pub cwd: Option<PathBuf>,
   pub external: Option<External>,
+  /// Expand dynamic imports and requires with variables in their specifiers, like `import(`./locales/${lang}.js`)`,
+  /// into lookups over the files matching the implied glob. Defaults to `false`.
+  pub dynamic_import_vars: Option<bool>,
+  /// Render ids of external modules resolved to absolute paths relative to the output location of importers.
+  pub make_absolute_externals_relative: Option<bool>,
   pub treeshake: Option<bool>,
   pub resolve: Option<ResolveOptions>,
 }
@@ -5,9 +5,7 @@ use std::{path::PathBuf, sync::Arc};
 
 use derivative::Derivative;
 
-use crate::External;
-
-use super::types::input_item::InputItem;
+use crate::{External, InputItem};
 
 pub type SharedNormalizedInputOptions = Arc<NormalizedInputOptions>;
 
@@ -17,5 +15,7 @@ pub struct NormalizedInputOptions {
   pub input: Vec<InputItem>,
   pub cwd: PathBuf,
   pub external: External,
+  pub dynamic_import_vars: bool,
+  pub make_absolute_externals_relative: bool,
   pub treeshake: bool,
 }
@@ -0,0 +1,51 @@
+use std::{
+  collections::HashMap,
+  path::{Path, PathBuf},
+};
+
+use derivative::Derivative;
+
+use crate::{
+  AddonOutputOption, AdvancedChunksOptions, FileNameTemplate, ManualChunks, OutputExports,
+  OutputFormat, PathsOutputOption, SanitizeFileName, SourceMapType,
+};
+
+#[derive(Derivative)]
+#[derivative(Debug)]
+pub struct NormalizedOutputOptions {
+  pub entry_file_names: FileNameTemplate,
+  pub chunk_file_names: FileNameTemplate,
+  pub asset_file_names: FileNameTemplate,
+  pub sanitize_file_name: SanitizeFileName,
+  pub dir: String,
+  pub file: Option<String>,
+  pub format: OutputFormat,
+  pub exports: OutputExports,
+  pub sourcemap: SourceMapType,
+  pub name: Option<String>,
+  pub globals: HashMap<String, String>,
+  pub paths: Option<PathsOutputOption>,
+  pub banner: Option<AddonOutputOption>,
+  pub footer: Option<AddonOutputOption>,
+  pub intro: Option<AddonOutputOption>,
+  pub outro: Option<AddonOutputOption>,
+  pub manual_chunks: Option<ManualChunks>,
+  pub advanced_chunks: Option<AdvancedChunksOptions>,
+  pub experimental_min_chunk_size: usize,
+  pub inline_dynamic_imports: bool,
+  pub preserve_modules: bool,
+  pub preserve_modules_root: Option<String>,
+}
+
+impl NormalizedOutputOptions {
+  /// The directory where output files are written, which is the parent directory of `file` if it's specified.
+  pub fn out_dir(&self, cwd: &Path) -> PathBuf {
+    match &self.file {
+      Some(file) => {
+        let file = cwd.join(file);
+        file.parent().map(Path::to_path_buf).unwrap_or(file)
+      }
+      None => cwd.join(&self.dir),
+    }
+  }
+}
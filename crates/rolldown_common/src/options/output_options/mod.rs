@@ -0,0 +1,181 @@
+use std::{collections::HashMap, fmt::Display};
+
+use derivative::Derivative;
+
+use super::types::{
+  addon_output_option::AddonOutputOption, advanced_chunks_options::AdvancedChunksOptions,
+  manual_chunks::ManualChunks, paths_output_option::PathsOutputOption,
+  sanitize_file_name::SanitizeFileName,
+};
+
+#[derive(Debug)]
+pub enum OutputFormat {
+  Esm,
+  Cjs,
+  Iife,
+  Umd,
+  System,
+}
+
+impl OutputFormat {
+  /// Formats like IIFE and UMD wrap everything into a single script, so the output can't be split into multiple chunks.
+  pub fn supports_code_splitting(&self) -> bool {
+    matches!(self, Self::Esm | Self::Cjs | Self::System)
+  }
+}
+
+impl From<String> for OutputFormat {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "esm" => OutputFormat::Esm,
+      "cjs" => OutputFormat::Cjs,
+      "iife" => OutputFormat::Iife,
+      "umd" => OutputFormat::Umd,
+      "system" | "systemjs" => OutputFormat::System,
+      _ => unreachable!("unknown output format"),
+    }
+  }
+}
+
+impl Display for OutputFormat {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Esm => write!(f, "esm"),
+      Self::Cjs => write!(f, "cjs"),
+      Self::Iife => write!(f, "iife"),
+      Self::Umd => write!(f, "umd"),
+      Self::System => write!(f, "system"),
+    }
+  }
+}
+
+/// How entry chunks of CJS, IIFE and UMD output expose their exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputExports {
+  /// `default` if the entry only has a default export, `none` if it has no exports, `named` otherwise
+  Auto,
+  /// The default export becomes `module.exports` or the return value of the wrapper
+  Default,
+  /// Exports are properties of `exports`
+  Named,
+  /// The entry has no exports
+  None,
+}
+
+impl From<String> for OutputExports {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "auto" => OutputExports::Auto,
+      "default" => OutputExports::Default,
+      "named" => OutputExports::Named,
+      "none" => OutputExports::None,
+      _ => unreachable!("unknown exports mode"),
+    }
+  }
+}
+
+impl Display for OutputExports {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Auto => write!(f, "auto"),
+      Self::Default => write!(f, "default"),
+      Self::Named => write!(f, "named"),
+      Self::None => write!(f, "none"),
+    }
+  }
+}
+
+#[derive(Debug)]
+pub enum SourceMapType {
+  File,
+  Inline,
+  Hidden,
+}
+
+impl SourceMapType {
+  pub fn is_hidden(&self) -> bool {
+    matches!(self, Self::Hidden)
+  }
+}
+
+impl From<String> for SourceMapType {
+  fn from(value: String) -> Self {
+    match value.as_str() {
+      "file" => SourceMapType::File,
+      "inline" => SourceMapType::Inline,
+      "hidden" => SourceMapType::Hidden,
+      _ => unreachable!("unknown sourcemap type"),
+    }
+  }
+}
+
+impl Display for SourceMapType {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::File => write!(f, "file"),
+      Self::Inline => write!(f, "inline"),
+      Self::Hidden => write!(f, "hidden"),
+    }
+  }
+}
+
+#[derive(Derivative, Default)]
+#[derivative(Debug)]
+pub struct OutputOptions {
+  pub entry_file_names: Option<String>,
+  pub chunk_file_names: Option<String>,
+  /// The template for file names of emitted assets, which supports `[name]`, `[ext]`, `[extname]` and `[hash]`.
+  /// Defaults to `assets/[name]-[hash][extname]`.
+  pub asset_file_names: Option<String>,
+  /// Sanitize names of chunks and assets before they are rendered into file names. Defaults to `true`, which
+  /// replaces characters that are invalid on some file systems with `_`.
+  pub sanitize_file_name: Option<SanitizeFileName>,
+  /// The directory to write chunks into, either absolute or relative to `cwd`. Defaults to `dist`.
+  pub dir: Option<String>,
+  /// Write the bundle into a single file instead of `dir`. Only usable when the build produces exactly one chunk.
+  pub file: Option<String>,
+  pub format: Option<OutputFormat>,
+  pub exports: Option<OutputExports>,
+  pub sourcemap: Option<SourceMapType>,
+  /// The global variable name of the bundle for `iife` and `umd` formats. Dotted names like `a.b.c` create nested namespaces.
+  pub name: Option<String>,
+  /// Map ids of external modules to global variable names for `iife` and `umd` formats.
+  pub globals: Option<HashMap<String, String>>,
+  /// Rewrite ids of external modules in imports, such as mapping a package name to a CDN url.
+  pub paths: Option<PathsOutputOption>,
+  /// Code prepended to every chunk, outside of any format wrapper.
+  pub banner: Option<AddonOutputOption>,
+  /// Code appended to every chunk, outside of any format wrapper.
+  pub footer: Option<AddonOutputOption>,
+  /// Code prepended to every chunk, inside of the format wrapper.
+  pub intro: Option<AddonOutputOption>,
+  /// Code appended to every chunk, inside of the format wrapper.
+  pub outro: Option<AddonOutputOption>,
+  /// Put modules into named chunks instead of the ones determined by code splitting. Static dependencies of these
+  /// modules are put into the same chunk, unless they are assigned to another chunk.
+  pub manual_chunks: Option<ManualChunks>,
+  /// Group modules into chunks by rules. Modules in manual chunks are not affected.
+  pub advanced_chunks: Option<AdvancedChunksOptions>,
+  /// Common chunks smaller than this are merged into other chunks, as long as no entry ends up executing code that it
+  /// didn't load before.
+  pub experimental_min_chunk_size: Option<usize>,
+  /// Keep dynamically imported modules in the chunk of their importer, so the build produces a single chunk. Only
+  /// usable with a single entry.
+  pub inline_dynamic_imports: Option<bool>,
+  /// Put every module into its own chunk, named after its path relative to `preserve_modules_root`.
+  pub preserve_modules: Option<bool>,
+  /// The directory that names of preserved modules are relative to. Defaults to the common directory of entries.
+  pub preserve_modules_root: Option<String>,
+}
+
+// impl Default for OutputOptions {
+//   fn default() -> Self {
+//     Self {
+//       entry_file_names: FileNameTemplate::from("[name].js".to_string()),
+//       chunk_file_names: FileNameTemplate::from("[name]-[hash].js".to_string()),
+//       dir: "dist".into(),
+//       format: OutputFormat::Esm,
+//       sourcemap: SourceMapType::Hidden,
+//     }
+//   }
+// }
@@ -0,0 +1,42 @@
+use std::fmt::Debug;
+use std::pin::Pin;
+
+use crate::RenderedChunk;
+use futures::Future;
+use rolldown_error::BuildError;
+
+pub type AddonFn = dyn Fn(
+    RenderedChunk,
+  ) -> Pin<Box<(dyn Future<Output = Result<Option<String>, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+/// Value of the `banner`, `footer`, `intro` and `outro` options. The function variant is called for every chunk.
+pub enum AddonOutputOption {
+  String(String),
+  Fn(Box<AddonFn>),
+}
+
+impl Debug for AddonOutputOption {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::String(value) => write!(f, "AddonOutputOption::String({value:?})"),
+      Self::Fn(_) => write!(f, "AddonOutputOption::Fn(...)"),
+    }
+  }
+}
+
+impl From<String> for AddonOutputOption {
+  fn from(value: String) -> Self {
+    Self::String(value)
+  }
+}
+
+impl AddonOutputOption {
+  pub async fn call(&self, chunk: &RenderedChunk) -> Result<Option<String>, BuildError> {
+    match self {
+      Self::String(value) => Ok(Some(value.clone())),
+      Self::Fn(value) => value(chunk.clone()).await,
+    }
+  }
+}
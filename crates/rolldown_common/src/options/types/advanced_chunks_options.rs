@@ -0,0 +1,59 @@
+use std::fmt::Debug;
+use std::pin::Pin;
+
+use futures::Future;
+use regex::Regex;
+use rolldown_error::BuildError;
+
+pub type MatchGroupTestFn = dyn Fn(String) -> Pin<Box<(dyn Future<Output = Result<bool, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+/// Rule-based grouping of modules into chunks. Options of a group fall back to the ones here.
+#[derive(Debug, Default)]
+pub struct AdvancedChunksOptions {
+  /// Groups whose total size of modules is smaller than this are not created.
+  pub min_size: Option<usize>,
+  /// Groups whose total size of modules is larger than this are split into multiple chunks.
+  pub max_size: Option<usize>,
+  /// Only modules imported by at least this many entries are put into groups. Defaults to 1.
+  pub min_share_count: Option<u32>,
+  pub groups: Vec<MatchGroup>,
+}
+
+#[derive(Debug, Default)]
+pub struct MatchGroup {
+  pub name: String,
+  /// Modules that don't pass the test are not put into the group. All modules pass if it's not specified.
+  pub test: Option<MatchGroupTest>,
+  /// A module matched by multiple groups goes to the one with the highest priority. Defaults to 0.
+  pub priority: Option<u32>,
+  pub min_size: Option<usize>,
+  pub max_size: Option<usize>,
+  pub min_share_count: Option<u32>,
+}
+
+pub enum MatchGroupTest {
+  /// Tested against module ids.
+  Regex(Regex),
+  /// Receives module ids.
+  Fn(Box<MatchGroupTestFn>),
+}
+
+impl Debug for MatchGroupTest {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Regex(value) => write!(f, "MatchGroupTest::Regex({value:?})"),
+      Self::Fn(_) => write!(f, "MatchGroupTest::Fn(...)"),
+    }
+  }
+}
+
+impl MatchGroupTest {
+  pub async fn call(&self, module_id: &str) -> Result<bool, BuildError> {
+    match self {
+      Self::Regex(value) => Ok(value.is_match(module_id)),
+      Self::Fn(value) => value(module_id.to_string()).await,
+    }
+  }
+}
@@ -0,0 +1,31 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::pin::Pin;
+
+use crate::ModuleInfo;
+use futures::Future;
+use rolldown_error::BuildError;
+
+pub type ManualChunksFn = dyn Fn(
+    String,
+    ModuleInfo,
+  ) -> Pin<Box<(dyn Future<Output = Result<Option<String>, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+pub enum ManualChunks {
+  /// Map names of chunks to modules they contain. A module could be specified by its id, a path relative to `cwd`, or
+  /// the name of the package it belongs to.
+  Map(HashMap<String, Vec<String>>),
+  /// Receives the id and the info of each module and returns the name of the chunk it should be put into.
+  Fn(Box<ManualChunksFn>),
+}
+
+impl Debug for ManualChunks {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Map(value) => write!(f, "ManualChunks::Map({value:?})"),
+      Self::Fn(_) => write!(f, "ManualChunks::Fn(...)"),
+    }
+  }
+}
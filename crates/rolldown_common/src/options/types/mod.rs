@@ -0,0 +1,6 @@
+pub mod addon_output_option;
+pub mod advanced_chunks_options;
+pub mod input_item;
+pub mod manual_chunks;
+pub mod paths_output_option;
+pub mod sanitize_file_name;
@@ -0,0 +1,34 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::pin::Pin;
+
+use futures::Future;
+use rolldown_error::BuildError;
+
+pub type PathsFn = dyn Fn(String) -> Pin<Box<(dyn Future<Output = Result<String, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+/// Value of the `paths` option, which maps ids of external modules to the paths rendered in imports.
+pub enum PathsOutputOption {
+  Map(HashMap<String, String>),
+  Fn(Box<PathsFn>),
+}
+
+impl Debug for PathsOutputOption {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Map(value) => write!(f, "PathsOutputOption::Map({value:?})"),
+      Self::Fn(_) => write!(f, "PathsOutputOption::Fn(...)"),
+    }
+  }
+}
+
+impl PathsOutputOption {
+  pub async fn call(&self, id: &str) -> Result<String, BuildError> {
+    match self {
+      Self::Map(value) => Ok(value.get(id).cloned().unwrap_or_else(|| id.to_string())),
+      Self::Fn(value) => value(id.to_string()).await,
+    }
+  }
+}
@@ -0,0 +1,73 @@
+use std::fmt::Debug;
+use std::pin::Pin;
+
+use futures::Future;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use rolldown_error::BuildError;
+
+pub type SanitizeFileNameFn = dyn Fn(String) -> Pin<Box<(dyn Future<Output = Result<String, BuildError>> + Send + 'static)>>
+  + Send
+  + Sync;
+
+/// Characters that are invalid in file names on some file systems, or have special meanings in URLs.
+static INVALID_CHAR_REGEX: Lazy<Regex> = Lazy::new(|| {
+  Regex::new(r##"[\x00-\x1F"#$%&*+,:;<=>?\[\]^`{|}\x7F]"##).expect("Init INVALID_CHAR_REGEX failed")
+});
+
+static DRIVE_LETTER_REGEX: Lazy<Regex> =
+  Lazy::new(|| Regex::new(r"^[a-zA-Z]:").expect("Init DRIVE_LETTER_REGEX failed"));
+
+/// Value of the `sanitize_file_name` option, which is applied to names of chunks and assets before they are
+/// rendered into file names.
+pub enum SanitizeFileName {
+  /// `true` replaces invalid characters with `_`. `false` leaves names as they are.
+  Boolean(bool),
+  Fn(Box<SanitizeFileNameFn>),
+}
+
+impl Debug for SanitizeFileName {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Boolean(value) => write!(f, "SanitizeFileName::Boolean({value:?})"),
+      Self::Fn(_) => write!(f, "SanitizeFileName::Fn(...)"),
+    }
+  }
+}
+
+impl Default for SanitizeFileName {
+  fn default() -> Self {
+    Self::Boolean(true)
+  }
+}
+
+impl From<bool> for SanitizeFileName {
+  fn from(value: bool) -> Self {
+    Self::Boolean(value)
+  }
+}
+
+impl SanitizeFileName {
+  pub async fn call(&self, name: &str) -> Result<String, BuildError> {
+    match self {
+      Self::Boolean(true) => Ok(default_sanitize_file_name(name)),
+      Self::Boolean(false) => Ok(name.to_string()),
+      Self::Fn(value) => value(name.to_string()).await,
+    }
+  }
+}
+
+/// Replace invalid characters with `_`, like `\0virtual:foo` to `_virtual_foo`. Drive letters of Windows paths
+/// are kept.
+pub fn default_sanitize_file_name(name: &str) -> String {
+  let drive_letter = DRIVE_LETTER_REGEX.find(name).map_or("", |m| m.as_str());
+  format!("{drive_letter}{}", INVALID_CHAR_REGEX.replace_all(&name[drive_letter.len()..], "_"))
+}
+
+#[test]
+fn test_default_sanitize_file_name() {
+  assert_eq!(default_sanitize_file_name("\0virtual:foo"), "_virtual_foo");
+  assert_eq!(default_sanitize_file_name("C:/foo/bar?baz"), "C:/foo/bar_baz");
+  assert_eq!(default_sanitize_file_name("components/button"), "components/button");
+  assert_eq!(default_sanitize_file_name("100%"), "100_");
+}
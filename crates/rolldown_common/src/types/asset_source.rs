@@ -0,0 +1,39 @@
+/// The content of an asset. Binary assets, such as images and fonts, don't need to be valid UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AssetSource {
+  String(String),
+  Buffer(Vec<u8>),
+}
+
+impl AssetSource {
+  pub fn as_bytes(&self) -> &[u8] {
+    match self {
+      Self::String(value) => value.as_bytes(),
+      Self::Buffer(value) => value.as_slice(),
+    }
+  }
+}
+
+impl Default for AssetSource {
+  fn default() -> Self {
+    Self::String(String::new())
+  }
+}
+
+impl From<String> for AssetSource {
+  fn from(value: String) -> Self {
+    Self::String(value)
+  }
+}
+
+impl From<&str> for AssetSource {
+  fn from(value: &str) -> Self {
+    Self::String(value.to_string())
+  }
+}
+
+impl From<Vec<u8>> for AssetSource {
+  fn from(value: Vec<u8>) -> Self {
+    Self::Buffer(value)
+  }
+}
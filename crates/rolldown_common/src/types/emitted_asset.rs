@@ -0,0 +1,11 @@
+use crate::AssetSource;
+
+/// An asset emitted by plugins via `PluginContext::emit_file`.
+#[derive(Debug, Clone, Default)]
+pub struct EmittedAsset {
+  /// Used to render `[name]`, `[ext]` and `[extname]` in `asset_file_names`, like `logo.png`.
+  pub name: Option<String>,
+  /// The exact file name of the asset, which bypasses `asset_file_names`.
+  pub file_name: Option<String>,
+  pub source: AssetSource,
+}
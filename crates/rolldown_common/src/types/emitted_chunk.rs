@@ -0,0 +1,10 @@
+/// An extra entry chunk emitted by plugins via `PluginContext::emit_file`.
+#[derive(Debug, Clone, Default)]
+pub struct EmittedChunk {
+  /// The module id of the entry, which is resolved like `InputOptions.input`.
+  pub id: String,
+  pub name: Option<String>,
+  /// Ids of entries that are always loaded before the chunk. Modules that are loaded by all of these entries already
+  /// stay in their chunks instead of being put into the emitted chunk or shared chunks.
+  pub implicitly_loaded_after: Vec<String>,
+}
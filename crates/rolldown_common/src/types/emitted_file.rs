@@ -0,0 +1,19 @@
+use crate::{EmittedAsset, EmittedChunk};
+
+#[derive(Debug, Clone)]
+pub enum EmittedFile {
+  Asset(EmittedAsset),
+  Chunk(EmittedChunk),
+}
+
+impl From<EmittedAsset> for EmittedFile {
+  fn from(value: EmittedAsset) -> Self {
+    Self::Asset(value)
+  }
+}
+
+impl From<EmittedChunk> for EmittedFile {
+  fn from(value: EmittedChunk) -> Self {
+    Self::Chunk(value)
+  }
+}
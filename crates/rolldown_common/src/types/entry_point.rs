@@ -11,4 +11,8 @@ pub struct EntryPoint {
 pub enum EntryPointKind {
   UserDefined,
   DynamicImport,
+  /// Emitted by plugins via `PluginContext::emit_file`. The entries are resolved from `EmittedChunk::implicitly_loaded_after`.
+  EmittedChunk {
+    implicitly_loaded_after: Vec<NormalModuleId>,
+  },
 }
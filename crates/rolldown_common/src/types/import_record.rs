@@ -1,5 +1,6 @@
 use std::fmt::Display;
 
+use oxc::span::Span;
 use rolldown_rstr::Rstr;
 
 use crate::{ModuleId, SymbolRef};
@@ -74,3 +75,20 @@ pub struct ImportRecord {
   pub contains_import_star: bool,
   pub contains_import_default: bool,
 }
+
+/// Identifies the node an import record is created from, which is an `ImportDeclaration`, `ImportExpression`,
+/// `ExportNamedDeclaration`, `ExportAllDeclaration` or `CallExpression`(only when the callee is `require`).
+///
+/// Nodes generated by rolldown, like the `import()`s expanded from dynamic import vars, share the span of the
+/// expression they're generated from, so they're told apart by their specifiers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImportRecordKey {
+  pub span: Span,
+  pub specifier: Rstr,
+}
+
+impl ImportRecordKey {
+  pub fn new(span: Span, specifier: &str) -> Self {
+    Self { span, specifier: Rstr::new(specifier) }
+  }
+}
@@ -1,13 +1,18 @@
+pub mod asset_source;
 pub mod ast_scope;
 pub mod batched_errors;
 pub mod chunk_id;
 pub mod chunk_kind;
+pub mod emitted_asset;
+pub mod emitted_chunk;
+pub mod emitted_file;
 pub mod entry_point;
 pub mod exports_kind;
 pub mod external_module_id;
 pub mod file_path;
 pub mod import_record;
 pub mod module_id;
+pub mod module_info;
 pub mod module_path;
 pub mod module_type;
 pub mod named_export;
@@ -0,0 +1,10 @@
+/// Information of a module exposed to users, such as the `manual_chunks` function.
+#[derive(Debug, Clone)]
+pub struct ModuleInfo {
+  pub id: String,
+  pub is_entry: bool,
+  /// Ids of modules imported statically by this module, including external modules.
+  pub imported_ids: Vec<String>,
+  /// Ids of modules imported via `import()` by this module, including external modules.
+  pub dynamically_imported_ids: Vec<String>,
+}
@@ -1,9 +1,11 @@
-use crate::OutputChunk;
+use crate::{AssetSource, OutputChunk};
 
 #[derive(Debug, Clone)]
 pub struct OutputAsset {
+  /// The name passed to `emit_file`, if any. Sourcemaps don't have names.
+  pub name: Option<String>,
   pub file_name: String,
-  pub source: String,
+  pub source: AssetSource,
 }
 
 #[derive(Debug, Clone)]
@@ -20,10 +22,10 @@ impl Output {
     }
   }
 
-  pub fn content(&self) -> &str {
+  pub fn content_as_bytes(&self) -> &[u8] {
     match self {
-      Self::Chunk(chunk) => &chunk.code,
-      Self::Asset(asset) => &asset.source,
+      Self::Chunk(chunk) => chunk.code.as_bytes(),
+      Self::Asset(asset) => asset.source.as_bytes(),
     }
   }
 }
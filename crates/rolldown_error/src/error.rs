@@ -11,10 +11,25 @@ use oxc::span::Span;
 use crate::{
   diagnostic::Diagnostic,
   error_kind::{
-    external_entry::ExternalEntry, forbid_const_assign::ForbitConstAssign,
-    sourcemap_error::SourceMapError, unresolved_entry::UnresolvedEntry,
-    unresolved_import::UnresolvedImport, unsupported_eval::UnsupportedEval, BuildErrorLike,
-    NapiError,
+    external_entry::ExternalEntry,
+    file_name_conflict::FileNameConflict,
+    forbid_const_assign::ForbitConstAssign,
+    implicit_dependant_not_entry::ImplicitDependantNotEntry,
+    invalid_export_option::InvalidExportOption,
+    invalid_option::{InvalidOption, InvalidOptionType},
+    invalid_rollup_phase::InvalidRollupPhaseForChunkEmission,
+    missing_global_name::MissingGlobalName,
+    missing_name_option_for_iife_export::MissingNameOptionForIifeExport,
+    missing_name_option_for_umd_export::MissingNameOptionForUmdExport,
+    mixed_exports::MixedExports,
+    multiple_chunks_with_file_option::MultipleChunksWithFileOption,
+    plugin_error::{PluginError, PluginWarning},
+    sourcemap_broken::SourceMapBroken,
+    sourcemap_error::SourceMapError,
+    unresolved_entry::UnresolvedEntry,
+    unresolved_import::UnresolvedImport,
+    unsupported_eval::UnsupportedEval,
+    BuildErrorLike, NapiError,
   },
 };
 
@@ -94,6 +109,16 @@ impl BuildError {
     Self::new_inner(UnresolvedEntry { unresolved_id: unresolved_id.as_ref().to_path_buf() })
   }
 
+  pub fn implicit_dependant_not_entry(
+    id: impl AsRef<Path>,
+    emitted_chunk: impl AsRef<Path>,
+  ) -> Self {
+    Self::new_inner(ImplicitDependantNotEntry {
+      id: id.as_ref().to_path_buf(),
+      emitted_chunk: emitted_chunk.as_ref().to_path_buf(),
+    })
+  }
+
   pub fn unresolved_import(specifier: impl Into<StaticStr>, importer: impl Into<PathBuf>) -> Self {
     Self::new_inner(UnresolvedImport { specifier: specifier.into(), importer: importer.into() })
   }
@@ -102,6 +127,109 @@ impl BuildError {
     Self::new_inner(SourceMapError { reason })
   }
 
+  pub fn sourcemap_broken(plugin: impl Into<String>, chunk: impl Into<String>) -> Self {
+    Self::new_inner(SourceMapBroken { plugin: plugin.into(), chunk: chunk.into() })
+      .with_severity_warning()
+  }
+
+  pub fn unsupported_code_splitting_format(format: impl Into<String>) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_type: InvalidOptionType::UnsupportedCodeSplittingFormat(format.into()),
+    })
+  }
+
+  pub fn hash_too_long(length: usize, max_length: usize) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_type: InvalidOptionType::HashTooLong { length, max_length },
+    })
+  }
+
+  pub fn hash_too_short(length: usize, min_length: usize, chunk_count: usize) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_type: InvalidOptionType::HashTooShort { length, min_length, chunk_count },
+    })
+  }
+
+  pub fn inline_dynamic_imports_with_multiple_entries() -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_type: InvalidOptionType::InlineDynamicImportsWithMultipleEntries,
+    })
+  }
+
+  pub fn dir_with_file_option() -> Self {
+    Self::new_inner(InvalidOption { invalid_option_type: InvalidOptionType::DirWithFile })
+  }
+
+  pub fn unsupported_option_with_preserve_modules(option: &'static str) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_type: InvalidOptionType::UnsupportedWithPreserveModules(option),
+    })
+  }
+
+  pub fn advanced_chunk_name_conflict(group: impl Into<String>, name: impl Into<String>) -> Self {
+    Self::new_inner(InvalidOption {
+      invalid_option_type: InvalidOptionType::AdvancedChunkNameConflict {
+        group: group.into(),
+        name: name.into(),
+      },
+    })
+  }
+
+  pub fn missing_name_option_for_iife_export() -> Self {
+    Self::new_inner(MissingNameOptionForIifeExport)
+  }
+
+  pub fn missing_name_option_for_umd_export() -> Self {
+    Self::new_inner(MissingNameOptionForUmdExport)
+  }
+
+  pub fn multiple_chunks_with_file_option() -> Self {
+    Self::new_inner(MultipleChunksWithFileOption)
+  }
+
+  pub fn invalid_export_option(
+    export_mode: impl Into<String>,
+    entry_module: impl Into<PathBuf>,
+    export_keys: Vec<String>,
+  ) -> Self {
+    Self::new_inner(InvalidExportOption {
+      export_mode: export_mode.into(),
+      entry_module: entry_module.into(),
+      export_keys,
+    })
+  }
+
+  pub fn mixed_exports(entry_module: impl Into<PathBuf>, name: Option<String>) -> Self {
+    Self::new_inner(MixedExports { entry_module: entry_module.into(), name })
+  }
+
+  pub fn file_name_conflict(file_name: impl Into<String>) -> Self {
+    Self::new_inner(FileNameConflict { file_name: file_name.into() })
+  }
+
+  pub fn invalid_rollup_phase_for_chunk_emission() -> Self {
+    Self::new_inner(InvalidRollupPhaseForChunkEmission)
+  }
+
+  pub fn missing_global_name(
+    module_id: impl Into<String>,
+    guessed_name: impl Into<String>,
+  ) -> Self {
+    Self::new_inner(MissingGlobalName {
+      module_id: module_id.into(),
+      guessed_name: guessed_name.into(),
+    })
+  }
+
+  pub fn plugin_error(plugin: impl Into<String>, message: impl Into<String>) -> Self {
+    Self::new_inner(PluginError { plugin: plugin.into(), message: message.into() })
+  }
+
+  pub fn plugin_warning(plugin: impl Into<String>, message: impl Into<String>) -> Self {
+    Self::new_inner(PluginWarning { plugin: plugin.into(), message: message.into() })
+      .with_severity_warning()
+  }
+
   // --- rolldown specific
   pub fn napi_error(status: String, reason: String) -> Self {
     Self::new_inner(NapiError { status, reason })
@@ -0,0 +1,19 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct FileNameConflict {
+  pub(crate) file_name: String,
+}
+
+impl BuildErrorLike for FileNameConflict {
+  fn code(&self) -> &'static str {
+    "FILE_NAME_CONFLICT"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "The emitted file \"{}\" overwrites a previously emitted file of the same name.",
+      self.file_name
+    )
+  }
+}
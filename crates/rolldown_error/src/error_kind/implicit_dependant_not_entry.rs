@@ -0,0 +1,24 @@
+use crate::PathExt;
+use std::path::PathBuf;
+
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct ImplicitDependantNotEntry {
+  pub(crate) id: PathBuf,
+  pub(crate) emitted_chunk: PathBuf,
+}
+
+impl BuildErrorLike for ImplicitDependantNotEntry {
+  fn code(&self) -> &'static str {
+    "MISSING_IMPLICIT_DEPENDANT"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "Module {} that should be implicitly loaded before {} is not an entry.",
+      self.id.relative_display(),
+      self.emitted_chunk.relative_display()
+    )
+  }
+}
@@ -0,0 +1,25 @@
+use super::BuildErrorLike;
+use crate::PathExt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub struct InvalidExportOption {
+  pub(crate) export_mode: String,
+  pub(crate) entry_module: PathBuf,
+  pub(crate) export_keys: Vec<String>,
+}
+
+impl BuildErrorLike for InvalidExportOption {
+  fn code(&self) -> &'static str {
+    "INVALID_EXPORT_OPTION"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "\"{}\" was specified for \"output.exports\", but entry module \"{}\" has the following exports: {}",
+      self.export_mode,
+      self.entry_module.relative_display(),
+      self.export_keys.iter().map(|key| format!("\"{key}\"")).collect::<Vec<_>>().join(", ")
+    )
+  }
+}
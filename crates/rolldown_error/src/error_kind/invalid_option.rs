@@ -0,0 +1,51 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub enum InvalidOptionType {
+  UnsupportedCodeSplittingFormat(String),
+  HashTooLong { length: usize, max_length: usize },
+  HashTooShort { length: usize, min_length: usize, chunk_count: usize },
+  InlineDynamicImportsWithMultipleEntries,
+  DirWithFile,
+  UnsupportedWithPreserveModules(&'static str),
+  AdvancedChunkNameConflict { group: String, name: String },
+}
+
+#[derive(Debug)]
+pub struct InvalidOption {
+  pub(crate) invalid_option_type: InvalidOptionType,
+}
+
+impl BuildErrorLike for InvalidOption {
+  fn code(&self) -> &'static str {
+    "INVALID_OPTION"
+  }
+
+  fn message(&self) -> String {
+    match &self.invalid_option_type {
+      InvalidOptionType::UnsupportedCodeSplittingFormat(format) => {
+        format!("Invalid value \"{format}\" for option \"output.format\" - UMD and IIFE output formats are not supported for code-splitting builds.")
+      }
+      InvalidOptionType::HashTooLong { length, max_length } => {
+        format!("Hashes cannot be longer than {max_length} characters, received {length}.")
+      }
+      InvalidOptionType::HashTooShort { length, min_length, chunk_count } => {
+        format!(
+          "To generate hashes for this number of chunks (currently {chunk_count}), you need a minimum hash size of {min_length}, received {length}."
+        )
+      }
+      InvalidOptionType::InlineDynamicImportsWithMultipleEntries => {
+        "Invalid value \"true\" for option \"output.inlineDynamicImports\" - multiple inputs are not supported when \"output.inlineDynamicImports\" is true.".to_string()
+      }
+      InvalidOptionType::DirWithFile => {
+        "Invalid value for option \"output.dir\" - you must set either \"output.file\" for a single-file build or \"output.dir\" when generating multiple chunks, but not both.".to_string()
+      }
+      InvalidOptionType::UnsupportedWithPreserveModules(option) => {
+        format!("Invalid value for option \"{option}\" - this option is not supported for \"output.preserveModules\".")
+      }
+      InvalidOptionType::AdvancedChunkNameConflict { group, name } => {
+        format!("Invalid value for option \"output.advancedChunks\" - the chunk \"{name}\" split from group \"{group}\" has the same name as another group or manual chunk.")
+      }
+    }
+  }
+}
@@ -0,0 +1,14 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct InvalidRollupPhaseForChunkEmission;
+
+impl BuildErrorLike for InvalidRollupPhaseForChunkEmission {
+  fn code(&self) -> &'static str {
+    "INVALID_ROLLUP_PHASE"
+  }
+
+  fn message(&self) -> String {
+    "Cannot emit chunks after module loading has finished.".to_string()
+  }
+}
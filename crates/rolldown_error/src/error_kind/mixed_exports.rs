@@ -0,0 +1,23 @@
+use super::BuildErrorLike;
+use crate::PathExt;
+use std::path::PathBuf;
+
+#[derive(Debug)]
+pub struct MixedExports {
+  pub(crate) entry_module: PathBuf,
+  pub(crate) name: Option<String>,
+}
+
+impl BuildErrorLike for MixedExports {
+  fn code(&self) -> &'static str {
+    "MIXED_EXPORTS"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "Entry module \"{}\" is using named and default exports together. Consumers of your bundle will have to use `{}.default` to access the default export, which may not be what you want. Use `output.exports: \"named\"` to disable this warning.",
+      self.entry_module.relative_display(),
+      self.name.as_deref().unwrap_or("chunk")
+    )
+  }
+}
@@ -2,7 +2,19 @@ use std::fmt::Debug;
 
 use crate::diagnostic::DiagnosticBuilder;
 pub mod external_entry;
+pub mod file_name_conflict;
 pub mod forbid_const_assign;
+pub mod implicit_dependant_not_entry;
+pub mod invalid_export_option;
+pub mod invalid_option;
+pub mod invalid_rollup_phase;
+pub mod missing_global_name;
+pub mod missing_name_option_for_iife_export;
+pub mod missing_name_option_for_umd_export;
+pub mod mixed_exports;
+pub mod multiple_chunks_with_file_option;
+pub mod plugin_error;
+pub mod sourcemap_broken;
 pub mod sourcemap_error;
 pub mod unresolved_entry;
 pub mod unresolved_import;
@@ -0,0 +1,15 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct MultipleChunksWithFileOption;
+
+impl BuildErrorLike for MultipleChunksWithFileOption {
+  fn code(&self) -> &'static str {
+    "MULTIPLE_CHUNKS_WITH_FILE_OPTION"
+  }
+
+  fn message(&self) -> String {
+    "When building multiple chunks, the \"output.dir\" option must be used, not \"output.file\"."
+      .to_string()
+  }
+}
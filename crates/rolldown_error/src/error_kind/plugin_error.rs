@@ -0,0 +1,33 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct PluginError {
+  pub(crate) plugin: String,
+  pub(crate) message: String,
+}
+
+impl BuildErrorLike for PluginError {
+  fn code(&self) -> &'static str {
+    "PLUGIN_ERROR"
+  }
+
+  fn message(&self) -> String {
+    format!("[plugin {}] {}", self.plugin, self.message)
+  }
+}
+
+#[derive(Debug)]
+pub struct PluginWarning {
+  pub(crate) plugin: String,
+  pub(crate) message: String,
+}
+
+impl BuildErrorLike for PluginWarning {
+  fn code(&self) -> &'static str {
+    "PLUGIN_WARNING"
+  }
+
+  fn message(&self) -> String {
+    format!("[plugin {}] {}", self.plugin, self.message)
+  }
+}
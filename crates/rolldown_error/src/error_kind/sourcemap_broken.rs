@@ -0,0 +1,20 @@
+use super::BuildErrorLike;
+
+#[derive(Debug)]
+pub struct SourceMapBroken {
+  pub(crate) plugin: String,
+  pub(crate) chunk: String,
+}
+
+impl BuildErrorLike for SourceMapBroken {
+  fn code(&self) -> &'static str {
+    "SOURCEMAP_BROKEN"
+  }
+
+  fn message(&self) -> String {
+    format!(
+      "Sourcemap is likely to be incorrect: a plugin ({}) changed the code of chunk \"{}\" in the `render_chunk` hook, but didn't return a sourcemap.",
+      self.plugin, self.chunk
+    )
+  }
+}
@@ -1,4 +1,7 @@
-use std::{io, path::Path};
+use std::{
+  io,
+  path::{Path, PathBuf},
+};
 
 use oxc_resolver::FileSystem as OxcResolverFileSystem;
 
@@ -32,4 +35,16 @@ pub trait FileSystem: Send + Sync + OxcResolverFileSystem {
   ///
   /// * See [std::path::Path::exists]
   fn exists(&self, path: &Path) -> bool;
+
+  /// # Errors
+  ///
+  /// * See [std::path::Path::is_file]
+  fn is_file(&self, path: &Path) -> bool;
+
+  /// Returns paths of entries in the directory, in no particular order.
+  ///
+  /// # Errors
+  ///
+  /// * See [std::fs::read_dir]
+  fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
 }
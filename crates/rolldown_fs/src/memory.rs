@@ -79,6 +79,24 @@ impl FileSystem for MemoryFileSystem {
   fn exists(&self, path: &Path) -> bool {
     self.fs.exists(path.to_string_lossy().as_ref()).is_ok()
   }
+
+  fn is_file(&self, path: &Path) -> bool {
+    self
+      .fs
+      .metadata(path.to_string_lossy().as_ref())
+      .is_ok_and(|metadata| metadata.file_type == vfs::VfsFileType::File)
+  }
+
+  fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+    Ok(
+      self
+        .fs
+        .read_dir(&path.to_string_lossy())
+        .map_err(|err| io::Error::new(io::ErrorKind::NotFound, err))?
+        .map(|name| path.join(name))
+        .collect(),
+    )
+  }
 }
 
 impl OxcResolverFileSystem for MemoryFileSystem {
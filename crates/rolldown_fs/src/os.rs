@@ -31,6 +31,14 @@ impl FileSystem for OsFileSystem {
   fn exists(&self, path: &Path) -> bool {
     path.exists()
   }
+
+  fn is_file(&self, path: &Path) -> bool {
+    path.is_file()
+  }
+
+  fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+    std::fs::read_dir(path)?.map(|entry| entry.map(|entry| entry.path())).collect()
+  }
 }
 
 impl OxcResolverFileSystem for OsFileSystem {
@@ -63,15 +63,90 @@ impl<'ast> AstSnippet<'ast> {
     )
   }
 
+  /// `[object].[property]`
+  pub fn static_member_expr_expr(
+    &self,
+    object: ast::Expression<'ast>,
+    property: PassedStr,
+  ) -> ast::Expression<'ast> {
+    ast::Expression::MemberExpression(
+      ast::MemberExpression::StaticMemberExpression(ast::StaticMemberExpression {
+        object,
+        property: ast::IdentifierName { name: self.atom(property), ..Dummy::dummy(self.alloc) },
+        ..Dummy::dummy(self.alloc)
+      })
+      .into_in(self.alloc),
+    )
+  }
+
+  /// `"value"`
+  pub fn string_literal_expr(&self, value: PassedStr) -> ast::Expression<'ast> {
+    ast::Expression::StringLiteral(
+      ast::StringLiteral { value: self.atom(value), span: SPAN }.into_in(self.alloc),
+    )
+  }
+
   /// `name()`
   pub fn call_expr(&self, name: PassedStr) -> ast::CallExpression<'ast> {
+    self.callee_call_expr(self.id_ref_expr(name, SPAN))
+  }
+
+  /// `[callee]()`
+  pub fn callee_call_expr(&self, callee: ast::Expression<'ast>) -> ast::CallExpression<'ast> {
     ast::CallExpression {
-      callee: ast::Expression::Identifier(self.id_ref(name, SPAN).into_in(self.alloc)),
+      callee,
       arguments: allocator::Vec::new_in(self.alloc),
       ..Dummy::dummy(self.alloc)
     }
   }
 
+  /// `[callee]()`
+  pub fn callee_call_expr_expr(&self, callee: ast::Expression<'ast>) -> ast::Expression<'ast> {
+    ast::Expression::CallExpression(self.callee_call_expr(callee).into_in(self.alloc))
+  }
+
+  /// `[callee](arg)`
+  pub fn callee_call_expr_with_arg_expr(
+    &self,
+    callee: ast::Expression<'ast>,
+    arg: ast::Expression<'ast>,
+  ) -> ast::Expression<'ast> {
+    let mut call_expr = self.callee_call_expr(callee);
+    call_expr.arguments.push(ast::Argument::Expression(arg));
+    ast::Expression::CallExpression(call_expr.into_in(self.alloc))
+  }
+
+  /// `[callee](arg1, arg2)`
+  pub fn callee_call_expr_with_2arg_expr(
+    &self,
+    callee: ast::Expression<'ast>,
+    arg1: ast::Expression<'ast>,
+    arg2: ast::Expression<'ast>,
+  ) -> ast::Expression<'ast> {
+    let mut call_expr = self.callee_call_expr(callee);
+    call_expr.arguments.push(ast::Argument::Expression(arg1));
+    call_expr.arguments.push(ast::Argument::Expression(arg2));
+    ast::Expression::CallExpression(call_expr.into_in(self.alloc))
+  }
+
+  /// `[expr];`
+  pub fn expr_stmt(&self, expr: ast::Expression<'ast>) -> ast::Statement<'ast> {
+    ast::Statement::ExpressionStatement(
+      ast::ExpressionStatement { expression: expr, ..Dummy::dummy(self.alloc) }.into_in(self.alloc),
+    )
+  }
+
+  /// `Promise.resolve().then(() => [expr])`
+  pub fn promise_resolve_then_expr(&self, expr: ast::Expression<'ast>) -> ast::Expression<'ast> {
+    let promise_resolve = self.callee_call_expr_expr(
+      self.static_member_expr_expr(self.id_ref_expr("Promise", SPAN), "resolve"),
+    );
+    self.callee_call_expr_with_arg_expr(
+      self.static_member_expr_expr(promise_resolve, "then"),
+      self.only_return_arrow_expr(expr),
+    )
+  }
+
   /// `name()`
   pub fn call_expr_expr(&self, name: PassedStr) -> ast::Expression<'ast> {
     ast::Expression::CallExpression(self.call_expr(name).into_in(self.alloc))
@@ -185,7 +260,7 @@ impl<'ast> AstSnippet<'ast> {
   pub fn commonjs_wrapper_stmt(
     &'ast self,
     binding_name: PassedStr,
-    commonjs_name: PassedStr,
+    commonjs_expr: ast::Expression<'ast>,
     body: allocator::Vec<'ast, Statement<'ast>>,
   ) -> ast::Statement<'ast> {
     // (exports, module) => {}
@@ -213,7 +288,7 @@ impl<'ast> AstSnippet<'ast> {
     });
 
     //  __commonJS(...)
-    let mut commonjs_call_expr = self.call_expr(commonjs_name);
+    let mut commonjs_call_expr = self.callee_call_expr(commonjs_expr);
     commonjs_call_expr.arguments.push(ast::Argument::Expression(
       ast::Expression::ArrowFunctionExpression(arrow_expr.into_in(self.alloc)),
     ));
@@ -234,7 +309,7 @@ impl<'ast> AstSnippet<'ast> {
   pub fn esm_wrapper_stmt(
     &'ast self,
     binding_name: PassedStr,
-    esm_fn_name: PassedStr,
+    esm_fn_expr: ast::Expression<'ast>,
     body: allocator::Vec<'ast, Statement<'ast>>,
   ) -> ast::Statement<'ast> {
     // () => { ... }
@@ -244,7 +319,7 @@ impl<'ast> AstSnippet<'ast> {
     };
 
     //  __esm(...)
-    let mut commonjs_call_expr = self.call_expr(esm_fn_name);
+    let mut commonjs_call_expr = self.callee_call_expr(esm_fn_expr);
     commonjs_call_expr.arguments.push(ast::Argument::Expression(
       ast::Expression::ArrowFunctionExpression(arrow_expr.into_in(self.alloc)),
     ));
@@ -0,0 +1,17 @@
+use rolldown_error::BuildError;
+
+use crate::{HookResolveIdExtraOptions, PluginDriver, ResolvedId};
+
+/// Resolves ids for `PluginContext::resolve` the same way the bundler resolves imports. It's implemented
+/// by the bundler, which owns the resolver and the `external` option.
+#[async_trait::async_trait]
+pub trait ContextResolver: Send + Sync {
+  async fn resolve(
+    &self,
+    plugin_driver: &PluginDriver,
+    specifier: &str,
+    importer: Option<&str>,
+    options: HookResolveIdExtraOptions,
+    skipped_plugin: Option<usize>,
+  ) -> Result<ResolvedId, BuildError>;
+}
@@ -0,0 +1,95 @@
+use std::sync::{Arc, Mutex};
+
+use rolldown_common::{EmittedAsset, EmittedChunk, EmittedFile};
+use rolldown_error::BuildError;
+use rolldown_utils::xxhash::xxhash_base64_url;
+use rustc_hash::FxHashMap;
+
+pub type SharedFileEmitter = Arc<FileEmitter>;
+
+/// Collects files emitted by plugins during a build. Chunks are loaded as extra entries by the module loader, and
+/// assets are turned into outputs in the bundle stage.
+#[derive(Debug, Default)]
+pub struct FileEmitter {
+  assets: Mutex<Vec<(String, EmittedAsset)>>,
+  chunks: Mutex<Vec<(String, EmittedChunk)>>,
+  emitted_count: Mutex<usize>,
+  /// Resolved entry module ids of emitted chunks, by reference id
+  chunk_module_ids: Mutex<FxHashMap<String, String>>,
+  file_names: Mutex<FxHashMap<String, String>>,
+  /// Chunks emitted after modules finish loading would never be built
+  is_loading_finished: Mutex<bool>,
+}
+
+impl FileEmitter {
+  /// Returns the reference id of the emitted file.
+  pub fn emit_file(&self, file: EmittedFile) -> Result<String, BuildError> {
+    if matches!(file, EmittedFile::Chunk(_))
+      && *self.is_loading_finished.lock().expect("FileEmitter lock poisoned")
+    {
+      return Err(BuildError::invalid_rollup_phase_for_chunk_emission());
+    }
+    let mut emitted_count = self.emitted_count.lock().expect("FileEmitter lock poisoned");
+    let name = match &file {
+      EmittedFile::Asset(asset) => asset.name.as_deref().unwrap_or_default(),
+      EmittedFile::Chunk(chunk) => chunk.id.as_str(),
+    };
+    let reference_id =
+      xxhash_base64_url(format!("{emitted_count}:{name}").as_bytes())[..8].to_string();
+    *emitted_count += 1;
+    match file {
+      EmittedFile::Asset(asset) => {
+        self.assets.lock().expect("FileEmitter lock poisoned").push((reference_id.clone(), asset));
+      }
+      EmittedFile::Chunk(chunk) => {
+        self.chunks.lock().expect("FileEmitter lock poisoned").push((reference_id.clone(), chunk));
+      }
+    }
+    Ok(reference_id)
+  }
+
+  /// Takes all emitted assets along with their reference ids, in the order of emission.
+  pub fn take_emitted_assets(&self) -> Vec<(String, EmittedAsset)> {
+    std::mem::take(&mut *self.assets.lock().expect("FileEmitter lock poisoned"))
+  }
+
+  /// Takes chunks emitted since the last call along with their reference ids, in the order of emission.
+  pub fn take_emitted_chunks(&self) -> Vec<(String, EmittedChunk)> {
+    std::mem::take(&mut *self.chunks.lock().expect("FileEmitter lock poisoned"))
+  }
+
+  pub fn set_chunk_module_id(&self, reference_id: String, module_id: String) {
+    self
+      .chunk_module_ids
+      .lock()
+      .expect("FileEmitter lock poisoned")
+      .insert(reference_id, module_id);
+  }
+
+  pub fn chunk_module_ids(&self) -> FxHashMap<String, String> {
+    self.chunk_module_ids.lock().expect("FileEmitter lock poisoned").clone()
+  }
+
+  pub fn set_file_name(&self, reference_id: String, file_name: String) {
+    self.file_names.lock().expect("FileEmitter lock poisoned").insert(reference_id, file_name);
+  }
+
+  pub fn get_file_name(&self, reference_id: &str) -> Option<String> {
+    self.file_names.lock().expect("FileEmitter lock poisoned").get(reference_id).cloned()
+  }
+
+  /// Called once modules finish loading, after which chunks can't be emitted anymore.
+  pub fn finish_loading(&self) {
+    *self.is_loading_finished.lock().expect("FileEmitter lock poisoned") = true;
+  }
+
+  /// Files emitted in previous builds shouldn't leak into the next one.
+  pub fn clear(&self) {
+    self.assets.lock().expect("FileEmitter lock poisoned").clear();
+    self.chunks.lock().expect("FileEmitter lock poisoned").clear();
+    *self.emitted_count.lock().expect("FileEmitter lock poisoned") = 0;
+    self.chunk_module_ids.lock().expect("FileEmitter lock poisoned").clear();
+    self.file_names.lock().expect("FileEmitter lock poisoned").clear();
+    *self.is_loading_finished.lock().expect("FileEmitter lock poisoned") = false;
+  }
+}
@@ -1,22 +1,36 @@
+mod context_resolver;
+mod file_emitter;
+mod module_info_store;
 mod plugin;
 mod plugin_context;
 mod plugin_driver;
 mod types;
 
 pub use crate::{
+  context_resolver::ContextResolver,
+  file_emitter::{FileEmitter, SharedFileEmitter},
+  module_info_store::ModuleInfoStore,
   plugin::{
-    BoxPlugin, HookLoadReturn, HookNoopReturn, HookRenderChunkReturn, HookResolveIdReturn,
-    HookTransformReturn, Plugin,
+    BoxPlugin, HookInjectionOutputReturn, HookLoadReturn, HookNoopReturn, HookRenderChunkReturn,
+    HookResolveIdReturn, HookTransformReturn, Plugin,
   },
   plugin_context::PluginContext,
   plugin_driver::{PluginDriver, SharedPluginDriver},
   types::hook_build_end_args::HookBuildEndArgs,
+  types::hook_filter::{FilterPattern, HookFilter, PatternFilter},
   types::hook_load_args::HookLoadArgs,
   types::hook_load_output::HookLoadOutput,
   types::hook_render_chunk_args::HookRenderChunkArgs,
   types::hook_render_chunk_output::HookRenderChunkOutput,
+  types::hook_render_error_args::HookRenderErrorArgs,
+  types::hook_render_start_args::HookRenderStartArgs,
+  types::hook_resolve_dynamic_import_args::{DynamicImportSpecifier, HookResolveDynamicImportArgs},
   types::hook_resolve_id_args::HookResolveIdArgs,
   types::hook_resolve_id_extra_options::HookResolveIdExtraOptions,
   types::hook_resolve_id_output::HookResolveIdOutput,
   types::hook_transform_args::HookTransformArgs,
+  types::hook_watch_change_args::{HookWatchChangeArgs, WatcherChangeKind},
+  types::plugin_context_resolve_options::PluginContextResolveOptions,
+  types::plugin_hook_meta::{PluginHook, PluginHookMeta, PluginOrder},
+  types::resolved_id::ResolvedId,
 };
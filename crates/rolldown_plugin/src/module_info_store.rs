@@ -0,0 +1,134 @@
+use std::sync::Mutex;
+
+use rolldown_common::ModuleInfo;
+use rustc_hash::{FxHashMap, FxHashSet};
+use tokio::sync::Notify;
+
+type LoadModuleFn = dyn Fn(String) + Send + Sync;
+
+/// Infos of modules loaded in the current build. The module loader fills it as modules finish loading,
+/// so plugins could look up modules and wait for modules they requested via `PluginContext::load`.
+#[derive(Default)]
+pub struct ModuleInfoStore {
+  infos: Mutex<FxHashMap<String, ModuleInfo>>,
+  failed: Mutex<FxHashSet<String>>,
+  load_module: Mutex<Option<Box<LoadModuleFn>>>,
+  notify: Notify,
+}
+
+impl ModuleInfoStore {
+  pub fn get(&self, id: &str) -> Option<ModuleInfo> {
+    self.infos.lock().expect("ModuleInfoStore lock poisoned").get(id).cloned()
+  }
+
+  pub fn insert(&self, info: ModuleInfo) {
+    self.infos.lock().expect("ModuleInfoStore lock poisoned").insert(info.id.clone(), info);
+    self.notify.notify_waiters();
+  }
+
+  /// Marks a module that couldn't be loaded, so nobody waits for it forever.
+  pub fn mark_failed(&self, id: String) {
+    self.failed.lock().expect("ModuleInfoStore lock poisoned").insert(id);
+    self.notify.notify_waiters();
+  }
+
+  /// Set by the module loader while it's running. `load_module` is called with ids requested by plugins.
+  pub fn set_module_loader(&self, load_module: Option<Box<LoadModuleFn>>) {
+    *self.load_module.lock().expect("ModuleInfoStore lock poisoned") = load_module;
+  }
+
+  pub fn clear(&self) {
+    self.infos.lock().expect("ModuleInfoStore lock poisoned").clear();
+    self.failed.lock().expect("ModuleInfoStore lock poisoned").clear();
+  }
+
+  pub(crate) async fn load(&self, id: &str) -> Result<ModuleInfo, String> {
+    if let Some(info) = self.get(id) {
+      return Ok(info);
+    }
+    {
+      let load_module = self.load_module.lock().expect("ModuleInfoStore lock poisoned");
+      let Some(load_module) = load_module.as_ref() else {
+        return Err(format!(
+          "Could not load \"{id}\". Modules can only be loaded during the build."
+        ));
+      };
+      load_module(id.to_string());
+    }
+    loop {
+      // `Notified` receives notifications as soon as it's created, so none would be missed between the checks and awaiting.
+      let notified = self.notify.notified();
+      if let Some(info) = self.get(id) {
+        return Ok(info);
+      }
+      if self.failed.lock().expect("ModuleInfoStore lock poisoned").contains(id) {
+        return Err(format!("Could not load \"{id}\"."));
+      }
+      notified.await;
+    }
+  }
+}
+
+impl std::fmt::Debug for ModuleInfoStore {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("ModuleInfoStore").finish_non_exhaustive()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::sync::{mpsc, Arc};
+
+  use super::*;
+
+  fn module_info(id: &str) -> ModuleInfo {
+    ModuleInfo {
+      id: id.to_string(),
+      is_entry: false,
+      imported_ids: vec![],
+      dynamically_imported_ids: vec![],
+    }
+  }
+
+  /// A store whose module loader reports the requested ids.
+  fn store_with_loader() -> (Arc<ModuleInfoStore>, mpsc::Receiver<String>) {
+    let store = Arc::new(ModuleInfoStore::default());
+    let (tx, rx) = mpsc::channel();
+    store.set_module_loader(Some(Box::new(move |id| tx.send(id).unwrap())));
+    (store, rx)
+  }
+
+  #[tokio::test]
+  async fn load_outside_of_the_build() {
+    let store = ModuleInfoStore::default();
+    assert!(store.load("a").await.unwrap_err().contains("during the build"));
+
+    // Loaded modules are still available
+    store.insert(module_info("a"));
+    assert_eq!(store.load("a").await.unwrap().id, "a");
+  }
+
+  #[tokio::test]
+  async fn load_waits_until_the_module_is_inserted() {
+    let (store, requests) = store_with_loader();
+    let (info, ()) = tokio::join!(store.load("a"), async {
+      tokio::task::yield_now().await;
+      // Other modules don't wake up the load
+      store.insert(module_info("b"));
+      tokio::task::yield_now().await;
+      store.insert(module_info("a"));
+    });
+    assert_eq!(info.unwrap().id, "a");
+    assert_eq!(requests.try_iter().collect::<Vec<_>>(), ["a"]);
+  }
+
+  #[tokio::test]
+  async fn load_fails_when_the_module_fails() {
+    let (store, _requests) = store_with_loader();
+    let (info, ()) = tokio::join!(store.load("a"), async {
+      tokio::task::yield_now().await;
+      store.mark_failed("a".to_string());
+    });
+    assert_eq!(info.unwrap_err(), "Could not load \"a\".");
+  }
+}
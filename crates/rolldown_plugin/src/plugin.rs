@@ -2,10 +2,12 @@ use std::{borrow::Cow, fmt::Debug};
 
 use super::plugin_context::PluginContext;
 use crate::{
-  HookBuildEndArgs, HookLoadArgs, HookLoadOutput, HookRenderChunkArgs, HookRenderChunkOutput,
-  HookResolveIdArgs, HookResolveIdOutput, HookTransformArgs,
+  HookBuildEndArgs, HookFilter, HookLoadArgs, HookLoadOutput, HookRenderChunkArgs,
+  HookRenderChunkOutput, HookRenderErrorArgs, HookRenderStartArgs, HookResolveDynamicImportArgs,
+  HookResolveIdArgs, HookResolveIdOutput, HookTransformArgs, HookWatchChangeArgs, PluginHook,
+  PluginHookMeta,
 };
-use rolldown_common::Output;
+use rolldown_common::{InputOptions, ModuleInfo, Output, OutputOptions, RenderedChunk};
 use rolldown_error::BuildError;
 
 pub type HookResolveIdReturn = Result<Option<HookResolveIdOutput>, BuildError>;
@@ -13,12 +15,32 @@ pub type HookTransformReturn = Result<Option<HookLoadOutput>, BuildError>;
 pub type HookLoadReturn = Result<Option<HookLoadOutput>, BuildError>;
 pub type HookNoopReturn = Result<(), BuildError>;
 pub type HookRenderChunkReturn = Result<Option<HookRenderChunkOutput>, BuildError>;
+pub type HookInjectionOutputReturn = Result<Option<String>, BuildError>;
 
 #[async_trait::async_trait]
 pub trait Plugin: Debug + Send + Sync {
   fn name(&self) -> Cow<'static, str>;
 
-  // The `option` hook consider call at node side.
+  /// The `order` and `sequential` of each hook. `PluginDriver` reads them once while being created.
+  fn hook_meta(&self, _hook: PluginHook) -> PluginHookMeta {
+    PluginHookMeta::default()
+  }
+
+  /// Filter of `resolve_id`, `load` or `transform`. The hook isn't called for modules not matching it.
+  fn hook_filter(&self, _hook: PluginHook) -> Option<HookFilter> {
+    None
+  }
+
+  /// Called in `BundlerBuilder::build` to adjust options before they're normalized. It's called before creating
+  /// `PluginDriver`, so there is no `PluginContext`. JS plugins handle the `options` hook at node side.
+  fn options(&self, _options: &mut InputOptions) -> HookNoopReturn {
+    Ok(())
+  }
+
+  /// Same as `options`, but for output options.
+  fn output_options(&self, _options: &mut OutputOptions) -> HookNoopReturn {
+    Ok(())
+  }
 
   // --- Build hooks ---
 
@@ -34,6 +56,17 @@ pub trait Plugin: Debug + Send + Sync {
     Ok(None)
   }
 
+  /// Called for dynamic imports before `resolve_id`. Non-literal specifiers such as template literals are only
+  /// bundled if this hook resolves them, otherwise they are left as they are. Returning `None` for a string
+  /// specifier falls back to `resolve_id`.
+  async fn resolve_dynamic_import(
+    &self,
+    _ctx: &PluginContext,
+    _args: &HookResolveDynamicImportArgs,
+  ) -> HookResolveIdReturn {
+    Ok(None)
+  }
+
   async fn load(&self, _ctx: &PluginContext, _args: &HookLoadArgs) -> HookLoadReturn {
     Ok(None)
   }
@@ -46,6 +79,11 @@ pub trait Plugin: Debug + Send + Sync {
     Ok(None)
   }
 
+  /// Called when a module and its imports are resolved. Modules imported by it may not be loaded yet.
+  async fn module_parsed(&self, _ctx: &PluginContext, _module_info: &ModuleInfo) -> HookNoopReturn {
+    Ok(())
+  }
+
   async fn build_end(
     &self,
     _ctx: &PluginContext,
@@ -54,6 +92,24 @@ pub trait Plugin: Debug + Send + Sync {
     Ok(())
   }
 
+  /// Called via `Bundler::watch_change` when a file watched by the bundler changes.
+  async fn watch_change(
+    &self,
+    _ctx: &PluginContext,
+    _args: &HookWatchChangeArgs,
+  ) -> HookNoopReturn {
+    Ok(())
+  }
+
+  /// Called before generating the output, with the normalized options of the build.
+  async fn render_start(
+    &self,
+    _ctx: &PluginContext,
+    _args: &HookRenderStartArgs,
+  ) -> HookNoopReturn {
+    Ok(())
+  }
+
   async fn render_chunk(
     &self,
     _ctx: &PluginContext,
@@ -62,6 +118,30 @@ pub trait Plugin: Debug + Send + Sync {
     Ok(None)
   }
 
+  async fn banner(
+    &self,
+    _ctx: &PluginContext,
+    _chunk: &RenderedChunk,
+  ) -> HookInjectionOutputReturn {
+    Ok(None)
+  }
+
+  async fn footer(
+    &self,
+    _ctx: &PluginContext,
+    _chunk: &RenderedChunk,
+  ) -> HookInjectionOutputReturn {
+    Ok(None)
+  }
+
+  async fn intro(&self, _ctx: &PluginContext, _chunk: &RenderedChunk) -> HookInjectionOutputReturn {
+    Ok(None)
+  }
+
+  async fn outro(&self, _ctx: &PluginContext, _chunk: &RenderedChunk) -> HookInjectionOutputReturn {
+    Ok(None)
+  }
+
   // --- Generate hooks ---
 
   #[allow(clippy::ptr_arg)]
@@ -78,6 +158,20 @@ pub trait Plugin: Debug + Send + Sync {
   async fn write_bundle(&self, _ctx: &PluginContext, _bundle: &Vec<Output>) -> HookNoopReturn {
     Ok(())
   }
+
+  /// Called when generating the output fails, from `render_start` to `generate_bundle`.
+  async fn render_error(
+    &self,
+    _ctx: &PluginContext,
+    _args: &HookRenderErrorArgs,
+  ) -> HookNoopReturn {
+    Ok(())
+  }
+
+  /// Called via `Bundler::close`, but not when the bundler is dropped. Release resources held by the plugin here.
+  async fn close_bundle(&self, _ctx: &PluginContext) -> HookNoopReturn {
+    Ok(())
+  }
 }
 
 pub type BoxPlugin = Box<dyn Plugin>;
@@ -1,8 +1,94 @@
-use std::sync::Weak;
+use std::{
+  borrow::Cow,
+  sync::{Arc, Weak},
+};
 
-use crate::PluginDriver;
+use rolldown_common::{EmittedFile, ModuleInfo};
+use rolldown_error::BuildError;
+
+use crate::{HookResolveIdExtraOptions, PluginContextResolveOptions, PluginDriver, ResolvedId};
 
 #[derive(Debug, Default)]
 pub struct PluginContext {
-  pub(crate) _plugin_driver: Weak<PluginDriver>,
+  pub(crate) plugin_name: Cow<'static, str>,
+  /// Index of the plugin in `PluginDriver`, used to skip the plugin itself in `resolve`.
+  pub(crate) plugin_index: usize,
+  pub(crate) plugin_driver: Weak<PluginDriver>,
+}
+
+impl PluginContext {
+  fn plugin_driver(&self) -> Arc<PluginDriver> {
+    self.plugin_driver.upgrade().expect("PluginDriver should be alive while calling hooks")
+  }
+
+  /// Resolve `specifier` with the `resolve_id` hooks of plugins and the built-in resolver, just like
+  /// the bundler resolves imports. The `external` option is respected.
+  pub async fn resolve(
+    &self,
+    specifier: &str,
+    importer: Option<&str>,
+    options: Option<PluginContextResolveOptions>,
+  ) -> Result<ResolvedId, BuildError> {
+    let options = options.unwrap_or_default();
+    let plugin_driver = self.plugin_driver();
+    plugin_driver
+      .context_resolver
+      .resolve(
+        &plugin_driver,
+        specifier,
+        importer,
+        HookResolveIdExtraOptions { is_entry: options.is_entry, kind: options.import_kind },
+        options.skip_self.then_some(self.plugin_index),
+      )
+      .await
+  }
+
+  /// Load the module of the resolved `id` and wait until it's parsed. A module that fails to load fails
+  /// the build as well. Don't wait for a module that is waiting for the current hook, such as loading a
+  /// module in its own `transform` hook, which never ends.
+  pub async fn load(&self, id: &str) -> Result<ModuleInfo, BuildError> {
+    self.plugin_driver().module_infos.load(id).await.map_err(|message| self.error(message))
+  }
+
+  /// Emit an asset that is written along with chunks, or a chunk that is bundled as an extra entry. Returns the
+  /// reference id of the file. Assets can be emitted until `generate_bundle`. Chunks need to be emitted before modules
+  /// finish loading, which is before `build_end`, or it's an error.
+  pub fn emit_file(&self, file: impl Into<EmittedFile>) -> Result<String, BuildError> {
+    self.plugin_driver().file_emitter.emit_file(file.into())
+  }
+
+  /// Get the final file name of an emitted file. File names of chunks are available since `render_chunk`, and
+  /// file names of assets are available since `generate_bundle`.
+  pub fn get_file_name(&self, reference_id: &str) -> Result<String, BuildError> {
+    self.plugin_driver().file_emitter.get_file_name(reference_id).ok_or_else(|| {
+      self.error(format!(
+        "Unable to get the file name of emitted file \"{reference_id}\". Either it doesn't exist or it isn't named yet."
+      ))
+    })
+  }
+
+  /// Get the info of a module that is already loaded.
+  pub fn get_module_info(&self, id: &str) -> Option<ModuleInfo> {
+    self.plugin_driver().module_infos.get(id)
+  }
+
+  /// Add a file which the build depends on besides the loaded modules.
+  pub fn add_watch_file(&self, file: &str) {
+    let plugin_driver = self.plugin_driver();
+    let mut watch_files = plugin_driver.watch_files.lock().expect("PluginDriver lock poisoned");
+    if !watch_files.iter().any(|f| f == file) {
+      watch_files.push(file.to_string());
+    }
+  }
+
+  /// Report a warning, which shows up in `RolldownOutput::warnings`.
+  pub fn warn(&self, message: impl Into<String>) {
+    let warning = BuildError::plugin_warning(self.plugin_name.clone(), message);
+    self.plugin_driver().warnings.lock().expect("PluginDriver lock poisoned").push(warning);
+  }
+
+  /// Create an error of the plugin. Return it from a hook to abort the build.
+  pub fn error(&self, message: impl Into<String>) -> BuildError {
+    BuildError::plugin_error(self.plugin_name.clone(), message)
+  }
 }
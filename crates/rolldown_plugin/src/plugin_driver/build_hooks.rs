@@ -1,21 +1,29 @@
 use crate::{
   HookBuildEndArgs, HookLoadArgs, HookLoadReturn, HookNoopReturn, HookRenderChunkArgs,
-  HookResolveIdArgs, HookResolveIdReturn, HookTransformArgs, PluginDriver,
+  HookResolveDynamicImportArgs, HookResolveIdArgs, HookResolveIdReturn, HookTransformArgs,
+  HookWatchChangeArgs, PluginDriver, PluginHook,
 };
+use std::borrow::Cow;
+
+use rolldown_common::ModuleInfo;
 use rolldown_error::BuildError;
 use rolldown_sourcemap::SourceMap;
 
 impl PluginDriver {
   pub async fn build_start(&self) -> HookNoopReturn {
-    // TODO should call `build_start` of all plugins in parallel
-    for (plugin, ctx) in &self.plugins {
-      plugin.build_start(ctx).await?;
-    }
-    Ok(())
+    self.call_parallel_hook(PluginHook::BuildStart, |plugin, ctx| plugin.build_start(ctx)).await
   }
 
-  pub async fn resolve_id(&self, args: &HookResolveIdArgs<'_>) -> HookResolveIdReturn {
-    for (plugin, ctx) in &self.plugins {
+  /// `skipped_plugin` is the index of a plugin whose `resolve_id` hook shouldn't be called.
+  pub async fn resolve_id(
+    &self,
+    args: &HookResolveIdArgs<'_>,
+    skipped_plugin: Option<usize>,
+  ) -> HookResolveIdReturn {
+    for (_, plugin, ctx) in self.plugins_of(PluginHook::ResolveId).filter(|(idx, _, _)| {
+      Some(*idx) != skipped_plugin
+        && self.filter_matches(PluginHook::ResolveId, *idx, |filter| filter.matches_id(args.source))
+    }) {
       if let Some(r) = plugin.resolve_id(ctx, args).await? {
         return Ok(Some(r));
       }
@@ -23,8 +31,24 @@ impl PluginDriver {
     Ok(None)
   }
 
+  pub async fn resolve_dynamic_import(
+    &self,
+    args: &HookResolveDynamicImportArgs<'_>,
+  ) -> HookResolveIdReturn {
+    for (_, plugin, ctx) in self.plugins_of(PluginHook::ResolveDynamicImport) {
+      if let Some(r) = plugin.resolve_dynamic_import(ctx, args).await? {
+        return Ok(Some(r));
+      }
+    }
+    Ok(None)
+  }
+
   pub async fn load(&self, args: &HookLoadArgs<'_>) -> HookLoadReturn {
-    for (plugin, ctx) in &self.plugins {
+    for (_, plugin, ctx) in self.plugins_of(PluginHook::Load).filter(|(idx, _, _)| {
+      self.filter_matches(PluginHook::Load, *idx, |filter| {
+        filter.matches_id(args.id) && filter.matches_module_type(args.module_type)
+      })
+    }) {
       if let Some(r) = plugin.load(ctx, args).await? {
         return Ok(Some(r));
       }
@@ -38,9 +62,21 @@ impl PluginDriver {
   ) -> Result<(String, Vec<SourceMap>), BuildError> {
     let mut sourcemap_chain = vec![];
     let mut code = args.code.to_string();
-    for (plugin, ctx) in &self.plugins {
-      if let Some(r) =
-        plugin.transform(ctx, &HookTransformArgs { id: args.id, code: &code }).await?
+    for (idx, plugin, ctx) in self.plugins_of(PluginHook::Transform) {
+      // The code filter is tested against the code transformed by previous plugins.
+      if !self.filter_matches(PluginHook::Transform, idx, |filter| {
+        filter.matches_id(args.id)
+          && filter.matches_code(&code)
+          && filter.matches_module_type(args.module_type)
+      }) {
+        continue;
+      }
+      if let Some(r) = plugin
+        .transform(
+          ctx,
+          &HookTransformArgs { id: args.id, code: &code, module_type: args.module_type },
+        )
+        .await?
       {
         code = r.code;
         if let Some(map) = r.map {
@@ -51,23 +87,43 @@ impl PluginDriver {
     Ok((code, sourcemap_chain))
   }
 
+  pub async fn module_parsed(&self, module_info: &ModuleInfo) -> HookNoopReturn {
+    self
+      .call_parallel_hook(PluginHook::ModuleParsed, |plugin, ctx| {
+        plugin.module_parsed(ctx, module_info)
+      })
+      .await
+  }
+
   pub async fn build_end(&self, args: Option<&HookBuildEndArgs>) -> HookNoopReturn {
     tracing::info!("PluginDriver::build_end");
-    for (plugin, ctx) in &self.plugins {
-      plugin.build_end(ctx, args).await?;
-    }
-    Ok(())
+    self.call_parallel_hook(PluginHook::BuildEnd, |plugin, ctx| plugin.build_end(ctx, args)).await
+  }
+
+  pub async fn watch_change(&self, args: &HookWatchChangeArgs<'_>) -> HookNoopReturn {
+    self
+      .call_parallel_hook(PluginHook::WatchChange, |plugin, ctx| plugin.watch_change(ctx, args))
+      .await
   }
 
+  /// Returns the rendered code, the maps returned by plugins, and names of plugins that changed the code without
+  /// returning a map, which breaks the sourcemap of the chunk.
   pub async fn render_chunk(
     &self,
     mut args: HookRenderChunkArgs<'_>,
-  ) -> Result<String, BuildError> {
-    for (plugin, ctx) in &self.plugins {
+  ) -> Result<(String, Vec<SourceMap>, Vec<Cow<'static, str>>), BuildError> {
+    let mut sourcemap_chain = vec![];
+    let mut plugins_without_map = vec![];
+    for (_, plugin, ctx) in self.plugins_of(PluginHook::RenderChunk) {
       if let Some(r) = plugin.render_chunk(ctx, &args).await? {
+        if let Some(map) = r.map {
+          sourcemap_chain.push(map);
+        } else if r.code != args.code {
+          plugins_without_map.push(plugin.name());
+        }
         args.code = r.code;
       }
     }
-    Ok(args.code)
+    Ok((args.code, sourcemap_chain, plugins_without_map))
   }
 }
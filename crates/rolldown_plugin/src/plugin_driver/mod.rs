@@ -1,25 +1,282 @@
-use std::sync::{Arc, Weak};
+use std::{
+  future::Future,
+  sync::{Arc, Mutex, Weak},
+};
 
-use crate::{BoxPlugin, PluginContext};
+use futures::future::join_all;
+use rolldown_error::BuildError;
+use rustc_hash::FxHashMap;
+
+use crate::{
+  context_resolver::ContextResolver,
+  file_emitter::{FileEmitter, SharedFileEmitter},
+  module_info_store::ModuleInfoStore,
+  BoxPlugin, HookFilter, HookNoopReturn, PluginContext, PluginHook, PluginHookMeta,
+};
 
 mod build_hooks;
+mod options_hooks;
 mod output_hooks;
 
 pub type SharedPluginDriver = Arc<PluginDriver>;
 
 pub struct PluginDriver {
   plugins: Vec<(BoxPlugin, PluginContext)>,
+  /// Indices of plugins of each hook, sorted by `PluginHookMeta::order`
+  sorted_plugins: FxHashMap<PluginHook, Vec<(usize, PluginHookMeta)>>,
+  /// Filters of hooks, by hooks and indices of plugins
+  hook_filters: FxHashMap<(PluginHook, usize), HookFilter>,
+  pub(crate) context_resolver: Arc<dyn ContextResolver>,
+  pub(crate) file_emitter: SharedFileEmitter,
+  pub(crate) module_infos: ModuleInfoStore,
+  pub(crate) warnings: Mutex<Vec<BuildError>>,
+  pub(crate) watch_files: Mutex<Vec<String>>,
 }
 
 impl PluginDriver {
-  pub fn new_shared(plugins: Vec<BoxPlugin>) -> SharedPluginDriver {
+  pub fn new_shared(
+    plugins: Vec<BoxPlugin>,
+    context_resolver: Arc<dyn ContextResolver>,
+  ) -> SharedPluginDriver {
     Arc::new_cyclic(|plugin_driver| {
       let with_context = plugins
         .into_iter()
-        .map(|plugin| (plugin, PluginContext { _plugin_driver: Weak::clone(plugin_driver) }))
+        .enumerate()
+        .map(|(plugin_index, plugin)| {
+          let ctx = PluginContext {
+            plugin_name: plugin.name(),
+            plugin_index,
+            plugin_driver: Weak::clone(plugin_driver),
+          };
+          (plugin, ctx)
+        })
         .collect::<Vec<_>>();
 
-      Self { plugins: with_context }
+      let sorted_plugins = PluginHook::ALL
+        .into_iter()
+        .map(|hook| (hook, sort_plugins(with_context.iter().map(|(plugin, _)| plugin), hook)))
+        .collect();
+
+      let hook_filters = [PluginHook::ResolveId, PluginHook::Load, PluginHook::Transform]
+        .into_iter()
+        .flat_map(|hook| {
+          with_context.iter().enumerate().filter_map(move |(plugin_index, (plugin, _))| {
+            Some(((hook, plugin_index), plugin.hook_filter(hook)?))
+          })
+        })
+        .collect();
+
+      Self {
+        plugins: with_context,
+        sorted_plugins,
+        hook_filters,
+        context_resolver,
+        file_emitter: Arc::new(FileEmitter::default()),
+        module_infos: ModuleInfoStore::default(),
+        warnings: Mutex::default(),
+        watch_files: Mutex::default(),
+      }
+    })
+  }
+
+  /// Plugins in the order of calling `hook`, along with their indices.
+  fn plugins_of(
+    &self,
+    hook: PluginHook,
+  ) -> impl Iterator<Item = (usize, &BoxPlugin, &PluginContext)> {
+    self.sorted_plugins[&hook].iter().map(|(plugin_index, _)| {
+      let (plugin, ctx) = &self.plugins[*plugin_index];
+      (*plugin_index, plugin, ctx)
     })
   }
+
+  /// Plugins without a filter of `hook` always match.
+  fn filter_matches(
+    &self,
+    hook: PluginHook,
+    plugin_index: usize,
+    matches: impl FnOnce(&HookFilter) -> bool,
+  ) -> bool {
+    self.hook_filters.get(&(hook, plugin_index)).map_or(true, matches)
+  }
+
+  /// Calls a parallel hook of all plugins at the same time, except that a `sequential` one waits for
+  /// previous ones to finish and blocks following ones until it finishes.
+  async fn call_parallel_hook<'a, F, Fut>(&'a self, hook: PluginHook, call: F) -> HookNoopReturn
+  where
+    F: Fn(&'a BoxPlugin, &'a PluginContext) -> Fut,
+    Fut: Future<Output = HookNoopReturn>,
+  {
+    let mut pending = vec![];
+    for (plugin_index, meta) in &self.sorted_plugins[&hook] {
+      let (plugin, ctx) = &self.plugins[*plugin_index];
+      if meta.sequential {
+        for result in join_all(std::mem::take(&mut pending)).await {
+          result?;
+        }
+        call(plugin, ctx).await?;
+      } else {
+        pending.push(call(plugin, ctx));
+      }
+    }
+    for result in join_all(pending).await {
+      result?;
+    }
+    Ok(())
+  }
+
+  pub fn file_emitter(&self) -> &SharedFileEmitter {
+    &self.file_emitter
+  }
+
+  pub fn module_infos(&self) -> &ModuleInfoStore {
+    &self.module_infos
+  }
+
+  /// States collected by plugins in previous builds shouldn't leak into the next one.
+  pub fn clear_build_state(&self) {
+    self.file_emitter.clear();
+    self.module_infos.clear();
+    self.warnings.lock().expect("PluginDriver lock poisoned").clear();
+    self.watch_files.lock().expect("PluginDriver lock poisoned").clear();
+  }
+
+  pub fn take_warnings(&self) -> Vec<BuildError> {
+    std::mem::take(&mut *self.warnings.lock().expect("PluginDriver lock poisoned"))
+  }
+
+  pub fn watch_files(&self) -> Vec<String> {
+    self.watch_files.lock().expect("PluginDriver lock poisoned").clone()
+  }
+}
+
+/// Indices of `plugins` in the order of calling `hook`, along with their metas of `hook`.
+fn sort_plugins<'a>(
+  plugins: impl Iterator<Item = &'a BoxPlugin>,
+  hook: PluginHook,
+) -> Vec<(usize, PluginHookMeta)> {
+  let mut metas = plugins
+    .enumerate()
+    .map(|(plugin_index, plugin)| (plugin_index, plugin.hook_meta(hook)))
+    .collect::<Vec<_>>();
+  // Stable sorting keeps the registration order of plugins with the same order.
+  metas.sort_by_key(|(_, meta)| meta.order);
+  metas
+}
+
+#[cfg(test)]
+mod tests {
+  use std::borrow::Cow;
+
+  use super::*;
+  use crate::{HookResolveIdExtraOptions, Plugin, PluginOrder, ResolvedId};
+
+  /// Records when its `build_start` hook starts and ends, which is one yield later.
+  #[derive(Debug)]
+  struct TestPlugin {
+    name: &'static str,
+    meta: PluginHookMeta,
+    events: Arc<Mutex<Vec<String>>>,
+  }
+
+  #[async_trait::async_trait]
+  impl Plugin for TestPlugin {
+    fn name(&self) -> Cow<'static, str> {
+      self.name.into()
+    }
+
+    fn hook_meta(&self, hook: PluginHook) -> PluginHookMeta {
+      if hook == PluginHook::BuildStart {
+        self.meta
+      } else {
+        PluginHookMeta::default()
+      }
+    }
+
+    async fn build_start(&self, _ctx: &PluginContext) -> HookNoopReturn {
+      self.events.lock().unwrap().push(format!("{}:start", self.name));
+      tokio::task::yield_now().await;
+      self.events.lock().unwrap().push(format!("{}:end", self.name));
+      Ok(())
+    }
+  }
+
+  struct UnusedResolver;
+
+  #[async_trait::async_trait]
+  impl ContextResolver for UnusedResolver {
+    async fn resolve(
+      &self,
+      _plugin_driver: &PluginDriver,
+      _specifier: &str,
+      _importer: Option<&str>,
+      _options: HookResolveIdExtraOptions,
+      _skipped_plugin: Option<usize>,
+    ) -> Result<ResolvedId, BuildError> {
+      unreachable!("Plugins in these tests don't resolve")
+    }
+  }
+
+  fn plugins(
+    metas: &[(&'static str, PluginHookMeta)],
+    events: &Arc<Mutex<Vec<String>>>,
+  ) -> Vec<BoxPlugin> {
+    metas
+      .iter()
+      .map(|(name, meta)| {
+        Box::new(TestPlugin { name, meta: *meta, events: Arc::clone(events) }) as BoxPlugin
+      })
+      .collect()
+  }
+
+  fn meta(order: PluginOrder, sequential: bool) -> PluginHookMeta {
+    PluginHookMeta { order, sequential }
+  }
+
+  #[test]
+  fn sort_plugins_by_order() {
+    let plugins = plugins(
+      &[
+        ("a", meta(PluginOrder::Post, false)),
+        ("b", meta(PluginOrder::Normal, false)),
+        ("c", meta(PluginOrder::Pre, false)),
+        ("d", meta(PluginOrder::Pre, false)),
+        ("e", meta(PluginOrder::Normal, false)),
+      ],
+      &Arc::default(),
+    );
+    let indices_of = |hook| {
+      sort_plugins(plugins.iter(), hook).into_iter().map(|(index, _)| index).collect::<Vec<_>>()
+    };
+    // Plugins with the same order keep the registration order
+    assert_eq!(indices_of(PluginHook::BuildStart), [2, 3, 1, 4, 0]);
+    // Orders only apply to the hooks they are set for
+    assert_eq!(indices_of(PluginHook::Transform), [0, 1, 2, 3, 4]);
+  }
+
+  #[tokio::test]
+  async fn sequential_hooks_wait_for_previous_ones_and_block_following_ones() {
+    let events = Arc::default();
+    let plugins = plugins(
+      &[
+        ("a", meta(PluginOrder::Normal, false)),
+        ("b", meta(PluginOrder::Normal, false)),
+        ("c", meta(PluginOrder::Normal, true)),
+        ("d", meta(PluginOrder::Normal, false)),
+        ("e", meta(PluginOrder::Pre, false)),
+      ],
+      &events,
+    );
+    let plugin_driver = PluginDriver::new_shared(plugins, Arc::new(UnusedResolver));
+    plugin_driver.build_start().await.unwrap();
+
+    // Hooks before the sequential one run in parallel, but not along with it
+    assert_eq!(
+      *events.lock().unwrap(),
+      [
+        "e:start", "a:start", "b:start", "e:end", "a:end", "b:end", "c:start", "c:end", "d:start",
+        "d:end",
+      ]
+    );
+  }
 }
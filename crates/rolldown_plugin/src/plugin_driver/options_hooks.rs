@@ -0,0 +1,22 @@
+use rolldown_common::{InputOptions, OutputOptions};
+
+use super::sort_plugins;
+use crate::{BoxPlugin, HookNoopReturn, PluginDriver, PluginHook};
+
+impl PluginDriver {
+  /// Options need to be adjusted before they're used to create `PluginDriver`, so these hooks are called on
+  /// plugins directly.
+  pub fn call_options_hooks(
+    plugins: &[BoxPlugin],
+    input_options: &mut InputOptions,
+    output_options: &mut OutputOptions,
+  ) -> HookNoopReturn {
+    for (plugin_index, _) in sort_plugins(plugins.iter(), PluginHook::Options) {
+      plugins[plugin_index].options(input_options)?;
+    }
+    for (plugin_index, _) in sort_plugins(plugins.iter(), PluginHook::OutputOptions) {
+      plugins[plugin_index].output_options(output_options)?;
+    }
+    Ok(())
+  }
+}
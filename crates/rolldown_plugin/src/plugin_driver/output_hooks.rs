@@ -1,23 +1,75 @@
-use crate::{HookNoopReturn, PluginDriver};
-use futures::future::join_all;
-use rolldown_common::Output;
+use std::future::Future;
+
+use crate::{
+  BoxPlugin, HookInjectionOutputReturn, HookNoopReturn, HookRenderErrorArgs, HookRenderStartArgs,
+  PluginContext, PluginDriver, PluginHook,
+};
+use rolldown_common::{Output, RenderedChunk};
+use rolldown_error::BuildError;
 
 impl PluginDriver {
+  pub async fn render_start(&self, args: &HookRenderStartArgs<'_>) -> HookNoopReturn {
+    self
+      .call_parallel_hook(PluginHook::RenderStart, |plugin, ctx| plugin.render_start(ctx, args))
+      .await
+  }
+
   pub async fn generate_bundle(&self, bundle: &Vec<Output>, is_write: bool) -> HookNoopReturn {
-    for (plugin, ctx) in &self.plugins {
+    for (_, plugin, ctx) in self.plugins_of(PluginHook::GenerateBundle) {
       plugin.generate_bundle(ctx, bundle, is_write).await?;
     }
     Ok(())
   }
 
   pub async fn write_bundle(&self, bundle: &Vec<Output>) -> HookNoopReturn {
-    let results =
-      join_all(self.plugins.iter().map(|(plugin, ctx)| plugin.write_bundle(ctx, bundle))).await;
+    self
+      .call_parallel_hook(PluginHook::WriteBundle, |plugin, ctx| plugin.write_bundle(ctx, bundle))
+      .await
+  }
 
-    for result in results {
-      result?;
-    }
+  pub async fn render_error(&self, args: &HookRenderErrorArgs) -> HookNoopReturn {
+    self
+      .call_parallel_hook(PluginHook::RenderError, |plugin, ctx| plugin.render_error(ctx, args))
+      .await
+  }
 
-    Ok(())
+  pub async fn close_bundle(&self) -> HookNoopReturn {
+    self.call_parallel_hook(PluginHook::CloseBundle, |plugin, ctx| plugin.close_bundle(ctx)).await
+  }
+
+  /// Results of the `banner` hook from all plugins, in the order of plugins.
+  pub async fn banner(&self, chunk: &RenderedChunk) -> Result<Vec<String>, BuildError> {
+    self.call_addon_hook(PluginHook::Banner, |plugin, ctx| plugin.banner(ctx, chunk)).await
+  }
+
+  pub async fn footer(&self, chunk: &RenderedChunk) -> Result<Vec<String>, BuildError> {
+    self.call_addon_hook(PluginHook::Footer, |plugin, ctx| plugin.footer(ctx, chunk)).await
+  }
+
+  pub async fn intro(&self, chunk: &RenderedChunk) -> Result<Vec<String>, BuildError> {
+    self.call_addon_hook(PluginHook::Intro, |plugin, ctx| plugin.intro(ctx, chunk)).await
+  }
+
+  pub async fn outro(&self, chunk: &RenderedChunk) -> Result<Vec<String>, BuildError> {
+    self.call_addon_hook(PluginHook::Outro, |plugin, ctx| plugin.outro(ctx, chunk)).await
+  }
+
+  /// Calls an addon hook of plugins in order, skipping those returning `None`.
+  async fn call_addon_hook<'a, F, Fut>(
+    &'a self,
+    hook: PluginHook,
+    call: F,
+  ) -> Result<Vec<String>, BuildError>
+  where
+    F: Fn(&'a BoxPlugin, &'a PluginContext) -> Fut,
+    Fut: Future<Output = HookInjectionOutputReturn>,
+  {
+    let mut ret = vec![];
+    for (_, plugin, ctx) in self.plugins_of(hook) {
+      if let Some(addon) = call(plugin, ctx).await? {
+        ret.push(addon);
+      }
+    }
+    Ok(ret)
   }
 }
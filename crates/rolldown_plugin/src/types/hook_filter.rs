@@ -0,0 +1,144 @@
+use glob::{MatchOptions, Pattern};
+use regex::Regex;
+use rolldown_common::ModuleType;
+
+#[derive(Debug, Clone)]
+pub enum FilterPattern {
+  /// `*` doesn't match `/`, while `**` does. Globs are matched against the whole value, which is an absolute
+  /// path for module ids, so `*.vue` never matches a module id. Use `**/*.vue` instead.
+  Glob(Pattern),
+  Contains(String),
+  Regex(Regex),
+}
+
+impl FilterPattern {
+  pub fn glob(pattern: &str) -> Result<Self, glob::PatternError> {
+    Pattern::new(pattern).map(Self::Glob)
+  }
+
+  pub fn matches(&self, value: &str) -> bool {
+    match self {
+      Self::Glob(pattern) => pattern.matches_with(
+        value,
+        MatchOptions { require_literal_separator: true, ..MatchOptions::new() },
+      ),
+      Self::Contains(substring) => value.contains(substring.as_str()),
+      Self::Regex(regex) => regex.is_match(value),
+    }
+  }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct PatternFilter {
+  pub include: Vec<FilterPattern>,
+  pub exclude: Vec<FilterPattern>,
+}
+
+impl PatternFilter {
+  /// A value matching any of `exclude` never matches. Otherwise it needs to match any of `include`, unless
+  /// `include` is empty.
+  pub fn matches(&self, value: &str) -> bool {
+    if self.exclude.iter().any(|pattern| pattern.matches(value)) {
+      return false;
+    }
+    self.include.is_empty() || self.include.iter().any(|pattern| pattern.matches(value))
+  }
+}
+
+/// Lets `PluginDriver` skip calling a hook for modules the plugin doesn't care about. This saves the cost
+/// of calling hooks of JS plugins, which cross the N-API boundary.
+#[derive(Debug, Default, Clone)]
+pub struct HookFilter {
+  /// Tested against the specifier in `resolve_id`, and the module id in `load` and `transform`.
+  pub id: Option<PatternFilter>,
+  /// Tested against the code in `transform`.
+  pub code: Option<PatternFilter>,
+  /// Tested against the module type in `load` and `transform`.
+  pub module_type: Option<Vec<ModuleType>>,
+}
+
+impl HookFilter {
+  pub(crate) fn matches_id(&self, id: &str) -> bool {
+    self.id.as_ref().map_or(true, |filter| filter.matches(id))
+  }
+
+  pub(crate) fn matches_code(&self, code: &str) -> bool {
+    self.code.as_ref().map_or(true, |filter| filter.matches(code))
+  }
+
+  pub(crate) fn matches_module_type(&self, module_type: ModuleType) -> bool {
+    self.module_type.as_ref().map_or(true, |module_types| module_types.contains(&module_type))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn glob(pattern: &str) -> FilterPattern {
+    FilterPattern::glob(pattern).unwrap()
+  }
+
+  #[test]
+  fn glob_matches_whole_paths() {
+    assert!(!glob("*.vue").matches("/src/App.vue"));
+    assert!(glob("*.vue").matches("App.vue"));
+    assert!(glob("**/*.vue").matches("/src/App.vue"));
+    assert!(glob("/src/*.vue").matches("/src/App.vue"));
+    assert!(!glob("/src/*.vue").matches("/src/components/Button.vue"));
+    assert!(glob("/src/**/*.vue").matches("/src/components/Button.vue"));
+  }
+
+  #[test]
+  fn contains_and_regex() {
+    assert!(FilterPattern::Contains("?raw".to_string()).matches("/src/a.txt?raw"));
+    assert!(!FilterPattern::Contains("?raw".to_string()).matches("/src/a.txt"));
+    let regex = FilterPattern::Regex(Regex::new(r"\.tsx?$").unwrap());
+    assert!(regex.matches("/src/a.ts"));
+    assert!(regex.matches("/src/a.tsx"));
+    assert!(!regex.matches("/src/a.js"));
+  }
+
+  #[test]
+  fn pattern_filter_excludes_first() {
+    let filter = PatternFilter {
+      include: vec![glob("**/*.js")],
+      exclude: vec![FilterPattern::Contains("node_modules".to_string())],
+    };
+    assert!(filter.matches("/src/a.js"));
+    assert!(!filter.matches("/src/a.ts"));
+    assert!(!filter.matches("/node_modules/a/index.js"));
+
+    // Everything not excluded matches without `include`
+    let filter = PatternFilter { include: vec![], exclude: vec![glob("**/*.css")] };
+    assert!(filter.matches("/src/a.ts"));
+    assert!(!filter.matches("/src/a.css"));
+    assert!(PatternFilter::default().matches("/src/a.css"));
+  }
+
+  #[test]
+  fn hook_filter_matches_everything_by_default() {
+    let filter = HookFilter::default();
+    assert!(filter.matches_id("/src/a.js"));
+    assert!(filter.matches_code("export {}"));
+    assert!(filter.matches_module_type(ModuleType::CJS));
+  }
+
+  #[test]
+  fn hook_filter_checks_each_part() {
+    let filter = HookFilter {
+      id: Some(PatternFilter { include: vec![glob("**/*.js")], exclude: vec![] }),
+      code: Some(PatternFilter {
+        include: vec![FilterPattern::Contains("import.meta".to_string())],
+        exclude: vec![],
+      }),
+      module_type: Some(vec![ModuleType::EsmMjs, ModuleType::EsmPackageJson]),
+    };
+    assert!(filter.matches_id("/src/a.js"));
+    assert!(!filter.matches_id("/src/a.css"));
+    assert!(filter.matches_code("console.log(import.meta.url)"));
+    assert!(!filter.matches_code("console.log(1)"));
+    assert!(filter.matches_module_type(ModuleType::EsmMjs));
+    assert!(!filter.matches_module_type(ModuleType::CJS));
+  }
+}
@@ -1,4 +1,7 @@
+use rolldown_common::ModuleType;
+
 #[derive(Debug)]
 pub struct HookLoadArgs<'a> {
   pub id: &'a str,
+  pub module_type: ModuleType,
 }
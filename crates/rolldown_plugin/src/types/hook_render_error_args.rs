@@ -0,0 +1,4 @@
+#[derive(Debug, Default)]
+pub struct HookRenderErrorArgs {
+  pub error: String,
+}
@@ -0,0 +1,7 @@
+use rolldown_common::{NormalizedInputOptions, NormalizedOutputOptions};
+
+#[derive(Debug)]
+pub struct HookRenderStartArgs<'a> {
+  pub output_options: &'a NormalizedOutputOptions,
+  pub input_options: &'a NormalizedInputOptions,
+}
@@ -0,0 +1,15 @@
+use oxc::span::Span;
+
+#[derive(Debug, Clone, Copy)]
+pub enum DynamicImportSpecifier<'a> {
+  /// `import('./foo.js')`
+  Str(&'a str),
+  /// `import(`./locales/${lang}.js`)`, along with the span of the argument in the code of the importer.
+  Expression { source_text: &'a str, span: Span },
+}
+
+#[derive(Debug)]
+pub struct HookResolveDynamicImportArgs<'a> {
+  pub importer: &'a str,
+  pub specifier: DynamicImportSpecifier<'a>,
+}
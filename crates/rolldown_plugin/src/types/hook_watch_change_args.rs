@@ -0,0 +1,24 @@
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WatcherChangeKind {
+  Create,
+  Update,
+  Delete,
+}
+
+impl Display for WatcherChangeKind {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Create => write!(f, "create"),
+      Self::Update => write!(f, "update"),
+      Self::Delete => write!(f, "delete"),
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct HookWatchChangeArgs<'a> {
+  pub id: &'a str,
+  pub event: WatcherChangeKind,
+}
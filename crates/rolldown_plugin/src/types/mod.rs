@@ -1,9 +1,17 @@
 pub mod hook_build_end_args;
+pub mod hook_filter;
 pub mod hook_load_args;
 pub mod hook_load_output;
 pub mod hook_render_chunk_args;
 pub mod hook_render_chunk_output;
+pub mod hook_render_error_args;
+pub mod hook_render_start_args;
+pub mod hook_resolve_dynamic_import_args;
 pub mod hook_resolve_id_args;
 pub mod hook_resolve_id_extra_options;
 pub mod hook_resolve_id_output;
 pub mod hook_transform_args;
+pub mod hook_watch_change_args;
+pub mod plugin_context_resolve_options;
+pub mod plugin_hook_meta;
+pub mod resolved_id;
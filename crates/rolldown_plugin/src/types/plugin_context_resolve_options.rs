@@ -0,0 +1,15 @@
+use rolldown_common::ImportKind;
+
+#[derive(Debug, Clone)]
+pub struct PluginContextResolveOptions {
+  pub import_kind: ImportKind,
+  pub is_entry: bool,
+  /// Whether to skip the `resolve_id` hook of the plugin calling `PluginContext::resolve`. Defaults to `true`.
+  pub skip_self: bool,
+}
+
+impl Default for PluginContextResolveOptions {
+  fn default() -> Self {
+    Self { import_kind: ImportKind::Import, is_entry: false, skip_self: true }
+  }
+}
@@ -0,0 +1,67 @@
+/// Hooks of `Plugin`, used to look up the `PluginHookMeta` of each hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PluginHook {
+  Options,
+  OutputOptions,
+  BuildStart,
+  ResolveId,
+  ResolveDynamicImport,
+  Load,
+  Transform,
+  ModuleParsed,
+  BuildEnd,
+  WatchChange,
+  RenderStart,
+  RenderChunk,
+  Banner,
+  Footer,
+  Intro,
+  Outro,
+  GenerateBundle,
+  WriteBundle,
+  RenderError,
+  CloseBundle,
+}
+
+impl PluginHook {
+  pub const ALL: [PluginHook; 20] = [
+    PluginHook::Options,
+    PluginHook::OutputOptions,
+    PluginHook::BuildStart,
+    PluginHook::ResolveId,
+    PluginHook::ResolveDynamicImport,
+    PluginHook::Load,
+    PluginHook::Transform,
+    PluginHook::ModuleParsed,
+    PluginHook::BuildEnd,
+    PluginHook::WatchChange,
+    PluginHook::RenderStart,
+    PluginHook::RenderChunk,
+    PluginHook::Banner,
+    PluginHook::Footer,
+    PluginHook::Intro,
+    PluginHook::Outro,
+    PluginHook::GenerateBundle,
+    PluginHook::WriteBundle,
+    PluginHook::RenderError,
+    PluginHook::CloseBundle,
+  ];
+}
+
+/// Where a hook of a plugin runs relative to the same hook of other plugins. Plugins with the same order
+/// keep their registration order.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PluginOrder {
+  Pre,
+  #[default]
+  Normal,
+  Post,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct PluginHookMeta {
+  pub order: PluginOrder,
+  /// Only works for parallel hooks such as `build_start`. The hook waits for the hooks of previous plugins
+  /// to finish, and the hooks of following plugins wait for it.
+  pub sequential: bool,
+}
@@ -0,0 +1,5 @@
+#[derive(Debug, Clone)]
+pub struct ResolvedId {
+  pub id: String,
+  pub external: bool,
+}
@@ -96,7 +96,7 @@ impl<T: FileSystem + 'static + Default> Plugin for ViteScannerPlugin<T> {
   }
 
   async fn load(&self, _ctx: &PluginContext, args: &HookLoadArgs) -> HookLoadReturn {
-    let HookLoadArgs { id } = args;
+    let HookLoadArgs { id, .. } = args;
 
     // extract scripts inside HTML-like files and treat it as a js module
     if HTML_TYPE_REGEX.is_match(id) {
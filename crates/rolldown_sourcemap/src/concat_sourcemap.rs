@@ -33,7 +33,9 @@ pub fn concat_sourcemaps(
         );
       }
     }
-    line_offset += (content.lines().count() + 1) as u32;
+    // Every line break of the content plus the `\n` used to join the next content. Counting with `lines()` is off
+    // by one for contents with a trailing line break or no content at all.
+    line_offset += (content.matches('\n').count() + 1) as u32;
   }
 
   Ok((s, sourcemap_builder.into_sourcemap()))
@@ -75,7 +77,7 @@ mod tests {
       content,
       "\nconsole.log()\nfunction sayHello(name: string) {\n  console.log(`Hello, ${name}`);\n}\n"
     );
-    let expected = "{\"version\":3,\"sources\":[\"index.ts\"],\"sourcesContent\":[\"function sayHello(name: string) {\\n  console.log(`Hello, ${name}`);\\n}\\n\"],\"names\":[],\"mappings\":\";;;AAAA,SAAS,QAAQ,CAAC,IAAY;IAC5B,OAAO,CAAC,GAAG,CAAC,iBAAU,IAAI,CAAE,CAAC,CAAC;AAChC,CAAC\"}";
+    let expected = "{\"version\":3,\"sources\":[\"index.ts\"],\"sourcesContent\":[\"function sayHello(name: string) {\\n  console.log(`Hello, ${name}`);\\n}\\n\"],\"names\":[],\"mappings\":\";;AAAA,SAAS,QAAQ,CAAC,IAAY;IAC5B,OAAO,CAAC,GAAG,CAAC,iBAAU,IAAI,CAAE,CAAC,CAAC;AAChC,CAAC\"}";
     assert_eq!(map, expected);
   }
 }
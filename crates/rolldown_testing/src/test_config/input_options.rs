@@ -7,6 +7,8 @@ use std::collections::HashMap;
 pub struct InputOptions {
   pub input: Option<Vec<InputItem>>,
   pub external: Option<Vec<String>>,
+  pub dynamic_import_vars: Option<bool>,
+  pub make_absolute_externals_relative: Option<bool>,
   pub treeshake: Option<bool>,
   pub resolve: Option<ResolveOptions>,
 }
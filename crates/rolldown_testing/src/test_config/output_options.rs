@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use schemars::JsonSchema;
 use serde::Deserialize;
 
@@ -18,6 +20,46 @@ pub struct OutputOptions {
   pub format: String,
   #[serde(default = "auto_by_default")]
   pub export_mode: String,
+  pub name: Option<String>,
+  pub globals: Option<HashMap<String, String>>,
+  pub paths: Option<HashMap<String, String>>,
+  pub entry_file_names: Option<String>,
+  pub chunk_file_names: Option<String>,
+  pub sanitize_file_name: Option<bool>,
+  pub dir: Option<String>,
+  pub file: Option<String>,
+  pub banner: Option<String>,
+  pub footer: Option<String>,
+  pub intro: Option<String>,
+  pub outro: Option<String>,
+  pub manual_chunks: Option<HashMap<String, Vec<String>>>,
+  pub advanced_chunks: Option<AdvancedChunksOptions>,
+  pub experimental_min_chunk_size: Option<usize>,
+  pub inline_dynamic_imports: Option<bool>,
+  pub preserve_modules: Option<bool>,
+  pub preserve_modules_root: Option<String>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct AdvancedChunksOptions {
+  pub min_size: Option<usize>,
+  pub max_size: Option<usize>,
+  pub min_share_count: Option<u32>,
+  #[serde(default)]
+  pub groups: Vec<MatchGroup>,
+}
+
+#[derive(Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MatchGroup {
+  pub name: String,
+  /// Regular expression tested against module ids
+  pub test: Option<String>,
+  pub priority: Option<u32>,
+  pub min_size: Option<usize>,
+  pub max_size: Option<usize>,
+  pub min_share_count: Option<u32>,
 }
 
 impl_serde_default!(OutputOptions);
@@ -18,6 +18,22 @@ impl BitSet {
     self.entries[bit as usize / 8] |= 1 << (bit & 7);
   }
 
+  pub fn clear_bit(&mut self, bit: u32) {
+    self.entries[bit as usize / 8] &= !(1 << (bit & 7));
+  }
+
+  pub fn union(&mut self, other: &Self) {
+    self.entries.iter_mut().zip(&other.entries).for_each(|(a, b)| *a |= b);
+  }
+
+  pub fn is_subset_of(&self, other: &Self) -> bool {
+    self.entries.iter().zip(&other.entries).all(|(a, b)| a & b == *a)
+  }
+
+  pub fn count_ones(&self) -> u32 {
+    self.entries.iter().map(|e| e.count_ones()).sum()
+  }
+
   pub fn is_empty(&self) -> bool {
     self.entries.iter().all(|&e| e == 0)
   }
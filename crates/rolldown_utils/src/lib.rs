@@ -2,6 +2,7 @@
 
 mod bitset;
 mod magic_string_ext;
+pub mod xxhash;
 
 use std::future::Future;
 pub use {crate::magic_string_ext::MagicStringExt, bitset::BitSet};
@@ -0,0 +1,7 @@
+use xxhash_rust::xxh3::xxh3_128;
+
+/// Hash the input and encode the hash with URL-safe base64, so it could be used in file names.
+pub fn xxhash_base64_url(input: &[u8]) -> String {
+  let hash = xxh3_128(input).to_le_bytes();
+  base64_simd::Base64::URL_SAFE_NO_PAD.encode_to_boxed_str(&hash).into_string()
+}
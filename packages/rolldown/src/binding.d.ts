@@ -9,6 +9,43 @@ export class Bundler {
   write(): Promise<BindingOutputs>
   generate(): Promise<BindingOutputs>
   scan(): Promise<void>
+  close(): Promise<void>
+}
+
+export interface BindingAdvancedChunksOptions {
+  minSize?: number
+  maxSize?: number
+  minShareCount?: number
+  groups?: Array<BindingMatchGroup>
+}
+
+export interface BindingDynamicImportExpression {
+  sourceText: string
+  start: number
+  end: number
+}
+
+/** Either `value` or `expression` is set. */
+export interface BindingDynamicImportSpecifier {
+  /** The specifier of `import('./foo.js')` */
+  value?: string
+  /** The argument of `import(`./locales/${lang}.js`)` */
+  expression?: BindingDynamicImportExpression
+}
+
+export interface BindingFilterPattern {
+  /** A glob when filtering ids, or a substring when filtering code */
+  value?: string
+  /** Source of a regular expression */
+  regex?: string
+}
+
+export interface BindingHookFilter {
+  id?: BindingPatternFilter
+  code?: BindingPatternFilter
+  moduleType?: Array<
+    'unknown' | 'cjs' | 'cjs-package-json' | 'esm-mjs' | 'esm-package-json'
+  >
 }
 
 export interface BindingInputItem {
@@ -24,15 +61,51 @@ export interface BindingInputOptions {
         importer: string | undefined,
         isResolved: boolean,
       ) => boolean)
+  dynamicImportVars?: boolean
   input: Array<BindingInputItem>
+  makeAbsoluteExternalsRelative?: boolean
   plugins: Array<PluginOptions>
   resolve?: BindingResolveOptions
   cwd: string
 }
 
+export interface BindingMatchGroup {
+  name: string
+  /** Source of a regular expression that is tested against module ids */
+  test?: string
+  priority?: number
+  minSize?: number
+  maxSize?: number
+  minShareCount?: number
+}
+
+export interface BindingModuleInfo {
+  id: string
+  isEntry: boolean
+  importedIds: Array<string>
+  dynamicallyImportedIds: Array<string>
+}
+
+/** Normalized output options passed to the `renderStart` hook. Options with function values are left out. */
+export interface BindingNormalizedOutputOptions {
+  dir: string
+  file?: string
+  format: 'esm' | 'cjs' | 'iife' | 'umd' | 'system'
+  exports: 'default' | 'named' | 'none' | 'auto'
+  sourcemap: 'file' | 'inline' | 'hidden'
+  name?: string
+  globals: Record<string, string>
+  entryFileNames: string
+  chunkFileNames: string
+  assetFileNames: string
+  inlineDynamicImports: boolean
+  preserveModules: boolean
+  preserveModulesRoot?: string
+}
+
 export interface BindingOutputAsset {
   fileName: string
-  source: string
+  source: string | Buffer
 }
 
 export interface BindingOutputChunk {
@@ -49,10 +122,26 @@ export interface BindingOutputChunk {
 export interface BindingOutputOptions {
   entryFileNames?: string
   chunkFileNames?: string
+  assetFileNames?: string
+  banner?: (chunk: RenderedChunk) => Promise<undefined | string>
   dir?: string
   exports?: 'default' | 'named' | 'none' | 'auto'
-  format?: 'esm' | 'cjs'
+  experimentalMinChunkSize?: number
+  footer?: (chunk: RenderedChunk) => Promise<undefined | string>
+  file?: string
+  format?: 'esm' | 'cjs' | 'iife' | 'umd' | 'system'
+  globals?: Record<string, string>
+  inlineDynamicImports?: boolean
+  intro?: (chunk: RenderedChunk) => Promise<undefined | string>
+  manualChunks?: Record<string, Array<string>>
+  advancedChunks?: BindingAdvancedChunksOptions
+  name?: string
+  outro?: (chunk: RenderedChunk) => Promise<undefined | string>
+  paths?: (id: string) => string
   plugins: Array<PluginOptions>
+  preserveModules?: boolean
+  preserveModulesRoot?: string
+  sanitizeFileName?: boolean | ((name: string) => string)
   sourcemap?: 'file' | 'inline' | 'hidden'
 }
 
@@ -61,6 +150,16 @@ export interface BindingOutputs {
   assets: Array<BindingOutputAsset>
 }
 
+export interface BindingPatternFilter {
+  include?: Array<BindingFilterPattern>
+  exclude?: Array<BindingFilterPattern>
+}
+
+export interface BindingPluginHookMeta {
+  order?: 'pre' | 'post'
+  sequential?: boolean
+}
+
 export interface BindingRenderedModule {
   code?: string
 }
@@ -79,6 +178,7 @@ export interface BindingResolveOptions {
 
 export interface HookRenderChunkOutput {
   code: string
+  map?: string
 }
 
 export interface HookResolveIdArgsOptions {
@@ -89,20 +189,49 @@ export interface HookResolveIdArgsOptions {
 export interface PluginOptions {
   name: string
   buildStart?: () => Promise<void>
+  buildStartMeta?: BindingPluginHookMeta
   resolveId?: (
     specifier: string,
     importer?: string,
     options?: HookResolveIdArgsOptions,
   ) => Promise<undefined | ResolveIdResult>
+  resolveIdMeta?: BindingPluginHookMeta
+  resolveIdFilter?: BindingHookFilter
+  resolveDynamicImport?: (
+    specifier: BindingDynamicImportSpecifier,
+    importer: string,
+  ) => Promise<undefined | ResolveIdResult>
+  resolveDynamicImportMeta?: BindingPluginHookMeta
   load?: (id: string) => Promise<undefined | SourceResult>
+  loadMeta?: BindingPluginHookMeta
+  loadFilter?: BindingHookFilter
   transform?: (id: string, code: string) => Promise<undefined | SourceResult>
+  transformMeta?: BindingPluginHookMeta
+  transformFilter?: BindingHookFilter
+  moduleParsed?: (moduleInfo: BindingModuleInfo) => Promise<void>
+  moduleParsedMeta?: BindingPluginHookMeta
   buildEnd?: (error: string) => Promise<void>
+  buildEndMeta?: BindingPluginHookMeta
+  watchChange?: (
+    id: string,
+    event: 'create' | 'update' | 'delete',
+  ) => Promise<void>
+  watchChangeMeta?: BindingPluginHookMeta
+  renderStart?: (outputOptions: BindingNormalizedOutputOptions) => Promise<void>
+  renderStartMeta?: BindingPluginHookMeta
   renderChunk?: (
     code: string,
     chunk: RenderedChunk,
   ) => Promise<undefined | HookRenderChunkOutput>
+  renderChunkMeta?: BindingPluginHookMeta
   generateBundle?: (bundle: Outputs, isWrite: boolean) => Promise<void>
+  generateBundleMeta?: BindingPluginHookMeta
   writeBundle?: (bundle: Outputs) => Promise<void>
+  writeBundleMeta?: BindingPluginHookMeta
+  renderError?: (error: string) => Promise<void>
+  renderErrorMeta?: BindingPluginHookMeta
+  closeBundle?: () => Promise<void>
+  closeBundleMeta?: BindingPluginHookMeta
 }
 
 export interface PreRenderedChunk {
@@ -1,41 +1,163 @@
 import type { Plugin, NormalizedInputOptions } from '../rollup-types'
+import type { RolldownPlugin } from './input-options'
+import { bindingifyHookFilter } from './hook-filter'
 import type {
   PluginOptions,
   SourceResult,
   ResolveIdResult,
+  BindingDynamicImportSpecifier,
   RenderedChunk,
   HookRenderChunkOutput,
+  BindingModuleInfo,
+  BindingNormalizedOutputOptions,
   BindingOutputs as Outputs,
 } from '../binding'
 import {
   transformToOutputBundle,
   unimplemented,
   transformSourcemap,
+  normalizeHook,
+  type HookHandler,
 } from '../utils'
 
 // Note: because napi not catch error, so we need to catch error and print error to debugger in adapter.
 export function createBuildPluginAdapter(
-  plugin: Plugin,
+  plugin: RolldownPlugin,
   options: NormalizedInputOptions,
 ): PluginOptions {
+  const [buildStartFn, buildStartMeta] = normalizeHook(plugin.buildStart)
+  const [resolveIdFn, resolveIdMeta, resolveIdFilter] = normalizeHook(
+    plugin.resolveId,
+  )
+  const [resolveDynamicImportFn, resolveDynamicImportMeta] = normalizeHook(
+    plugin.resolveDynamicImport,
+  )
+  const [loadFn, loadMeta, loadFilter] = normalizeHook(plugin.load)
+  const [transformFn, transformMeta, transformFilter] = normalizeHook(
+    plugin.transform,
+  )
+  const [moduleParsedFn, moduleParsedMeta] = normalizeHook(plugin.moduleParsed)
+  const [buildEndFn, buildEndMeta] = normalizeHook(plugin.buildEnd)
+  const [watchChangeFn, watchChangeMeta] = normalizeHook(plugin.watchChange)
+  const [renderStartFn, renderStartMeta] = normalizeHook(plugin.renderStart)
+  const [renderChunkFn, renderChunkMeta] = normalizeHook(plugin.renderChunk)
+  const [generateBundleFn, generateBundleMeta] = normalizeHook(
+    plugin.generateBundle,
+  )
+  const [writeBundleFn, writeBundleMeta] = normalizeHook(plugin.writeBundle)
+  const [renderErrorFn, renderErrorMeta] = normalizeHook(plugin.renderError)
+  const [closeBundleFn, closeBundleMeta] = normalizeHook(plugin.closeBundle)
   return {
     name: plugin.name ?? 'unknown',
-    buildStart: buildStart(plugin.buildStart, options),
-    resolveId: resolveId(plugin.resolveId),
-    load: load(plugin.load),
-    transform: transform(plugin.transform),
-    buildEnd: buildEnd(plugin.buildEnd),
-    renderChunk: renderChunk(plugin.renderChunk),
-    generateBundle: generateBundle(plugin.generateBundle),
-    writeBundle: writeBundle(plugin.writeBundle),
+    buildStart: buildStart(buildStartFn, options),
+    buildStartMeta,
+    resolveId: resolveId(resolveIdFn),
+    resolveIdMeta,
+    resolveIdFilter: bindingifyHookFilter(resolveIdFilter),
+    resolveDynamicImport: resolveDynamicImport(resolveDynamicImportFn),
+    resolveDynamicImportMeta,
+    load: load(loadFn),
+    loadMeta,
+    loadFilter: bindingifyHookFilter(loadFilter),
+    transform: transform(transformFn),
+    transformMeta,
+    transformFilter: bindingifyHookFilter(transformFilter),
+    moduleParsed: moduleParsed(moduleParsedFn),
+    moduleParsedMeta,
+    buildEnd: buildEnd(buildEndFn),
+    buildEndMeta,
+    watchChange: watchChange(watchChangeFn),
+    watchChangeMeta,
+    renderStart: renderStart(renderStartFn, options),
+    renderStartMeta,
+    renderChunk: renderChunk(renderChunkFn),
+    renderChunkMeta,
+    generateBundle: generateBundle(generateBundleFn),
+    generateBundleMeta,
+    writeBundle: writeBundle(writeBundleFn),
+    writeBundleMeta,
+    renderError: renderError(renderErrorFn),
+    renderErrorMeta,
+    closeBundle: closeBundle(closeBundleFn),
+    closeBundleMeta,
   }
 }
 
-function writeBundle(hook: Plugin['writeBundle']) {
+function closeBundle(hook: HookHandler<Plugin['closeBundle']>) {
   if (hook) {
-    if (typeof hook !== 'function') {
-      return unimplemented()
+    return async () => {
+      try {
+        await hook.call({} as any)
+      } catch (error) {
+        console.error(error)
+        throw error
+      }
+    }
+  }
+}
+
+function renderError(hook: HookHandler<Plugin['renderError']>) {
+  if (hook) {
+    return async (e: string) => {
+      try {
+        await hook.call({} as any, new Error(e))
+      } catch (error) {
+        console.error(error)
+        throw error
+      }
+    }
+  }
+}
+
+function renderStart(
+  hook: HookHandler<Plugin['renderStart']>,
+  options: NormalizedInputOptions,
+) {
+  if (hook) {
+    return async (outputOptions: BindingNormalizedOutputOptions) => {
+      try {
+        await hook.call(
+          {} as any,
+          Object.freeze(outputOptions) as any,
+          Object.freeze(options),
+        )
+      } catch (error) {
+        console.error(error)
+        throw error
+      }
+    }
+  }
+}
+
+function watchChange(hook: HookHandler<Plugin['watchChange']>) {
+  if (hook) {
+    return async (id: string, event: 'create' | 'update' | 'delete') => {
+      try {
+        await hook.call({} as any, id, { event })
+      } catch (error) {
+        console.error(error)
+        throw error
+      }
+    }
+  }
+}
+
+function moduleParsed(hook: HookHandler<Plugin['moduleParsed']>) {
+  if (hook) {
+    return async (moduleInfo: BindingModuleInfo) => {
+      try {
+        // @ts-expect-error: other fields of `ModuleInfo` aren't available yet
+        await hook.call({} as any, moduleInfo)
+      } catch (error) {
+        console.error(error)
+        throw error
+      }
     }
+  }
+}
+
+function writeBundle(hook: HookHandler<Plugin['writeBundle']>) {
+  if (hook) {
     return async (outputs: Outputs) => {
       try {
         // @ts-expect-error: FIXME: hyf0
@@ -48,11 +170,8 @@ function writeBundle(hook: Plugin['writeBundle']) {
   }
 }
 
-function generateBundle(hook: Plugin['generateBundle']) {
+function generateBundle(hook: HookHandler<Plugin['generateBundle']>) {
   if (hook) {
-    if (typeof hook !== 'function') {
-      return unimplemented()
-    }
     return async (outputs: Outputs, isWrite: boolean) => {
       try {
         await hook.call(
@@ -70,11 +189,8 @@ function generateBundle(hook: Plugin['generateBundle']) {
   }
 }
 
-function renderChunk(hook: Plugin['renderChunk']) {
+function renderChunk(hook: HookHandler<Plugin['renderChunk']>) {
   if (hook) {
-    if (typeof hook !== 'function') {
-      return unimplemented()
-    }
     return async (
       code: string,
       chunk: RenderedChunk,
@@ -143,8 +259,7 @@ function renderChunk(hook: Plugin['renderChunk']) {
           return { code: value }
         }
         if (typeof value === 'object') {
-          // TODO other filed
-          return { code: value.code }
+          return { code: value.code, map: transformSourcemap(value.map) }
         }
       } catch (error) {
         console.error(error)
@@ -155,13 +270,10 @@ function renderChunk(hook: Plugin['renderChunk']) {
 }
 
 function buildStart(
-  hook: Plugin['buildStart'],
+  hook: HookHandler<Plugin['buildStart']>,
   options: NormalizedInputOptions,
 ) {
   if (hook) {
-    if (typeof hook !== 'function') {
-      return unimplemented()
-    }
     return async () => {
       try {
         // Here use `Object.freeze` to prevent plugin from modifying the options.
@@ -174,11 +286,8 @@ function buildStart(
   }
 }
 
-function buildEnd(hook: Plugin['buildEnd']) {
+function buildEnd(hook: HookHandler<Plugin['buildEnd']>) {
   if (hook) {
-    if (typeof hook !== 'function') {
-      return unimplemented()
-    }
     return async (e: string) => {
       try {
         await hook.call({} as any, e ? new Error(e) : undefined)
@@ -190,11 +299,8 @@ function buildEnd(hook: Plugin['buildEnd']) {
   }
 }
 
-function transform(hook: Plugin['transform']) {
+function transform(hook: HookHandler<Plugin['transform']>) {
   if (hook) {
-    if (typeof hook !== 'function') {
-      return unimplemented()
-    }
     return async (
       code: string,
       id: string,
@@ -221,11 +327,8 @@ function transform(hook: Plugin['transform']) {
   }
 }
 
-function resolveId(hook: Plugin['resolveId']) {
+function resolveId(hook: HookHandler<Plugin['resolveId']>) {
   if (hook) {
-    if (typeof hook !== 'function') {
-      return unimplemented()
-    }
     return async (
       source: string,
       importer?: string,
@@ -262,11 +365,51 @@ function resolveId(hook: Plugin['resolveId']) {
   }
 }
 
-function load(hook: Plugin['load']) {
+function resolveDynamicImport(
+  hook: HookHandler<Plugin['resolveDynamicImport']>,
+) {
   if (hook) {
-    if (typeof hook !== 'function') {
-      return unimplemented()
+    return async (
+      specifier: BindingDynamicImportSpecifier,
+      importer: string,
+    ): Promise<undefined | ResolveIdResult> => {
+      try {
+        // Rollup passes the AST node of a non-literal specifier. Only its source text and position are
+        // available here.
+        const value = await hook.call(
+          {} as any,
+          specifier.value ?? (specifier.expression as any),
+          importer,
+          {} as any,
+        )
+        if (value === undefined || value === null) {
+          return
+        }
+        if (typeof value === 'string') {
+          return { id: value }
+        }
+        if (value === false) {
+          if (specifier.value === undefined) {
+            return
+          }
+          return { id: specifier.value, external: true }
+        }
+        if (value.external === 'absolute' || value.external === 'relative') {
+          throw new Error(
+            `External module type {${value.external}} is not supported yet.`,
+          )
+        }
+        return value as ResolveIdResult
+      } catch (error) {
+        console.error(error)
+        throw error
+      }
     }
+  }
+}
+
+function load(hook: HookHandler<Plugin['load']>) {
+  if (hook) {
     return async (id: string): Promise<undefined | SourceResult> => {
       try {
         const value = await hook.call({} as any, id)
@@ -0,0 +1,79 @@
+import type {
+  BindingFilterPattern,
+  BindingHookFilter,
+  BindingPatternFilter,
+} from '../binding'
+import { ensureArray } from '../utils/ensure-array'
+
+type StringOrRegExp = string | RegExp
+type MaybeArray<T> = T | T[]
+
+export type FilterPattern =
+  | MaybeArray<StringOrRegExp>
+  | {
+      include?: MaybeArray<StringOrRegExp>
+      exclude?: MaybeArray<StringOrRegExp>
+    }
+
+export interface HookFilter {
+  /**
+   * Tested against the specifier in `resolveId`, and the module id in `load` and `transform`.
+   * Strings are globs, which match the whole absolute id, so use `**\/*.vue` rather than `*.vue`.
+   */
+  id?: FilterPattern
+  /**
+   * Tested against the code in `transform`. Strings are substrings.
+   */
+  code?: FilterPattern
+  moduleType?: BindingHookFilter['moduleType']
+}
+
+export function bindingifyHookFilter(
+  filter?: HookFilter,
+): BindingHookFilter | undefined {
+  if (filter == null) {
+    return
+  }
+  return {
+    id: bindingifyPatternFilter(filter.id),
+    code: bindingifyPatternFilter(filter.code),
+    moduleType: filter.moduleType,
+  }
+}
+
+function bindingifyPatternFilter(
+  filter?: FilterPattern,
+): BindingPatternFilter | undefined {
+  if (filter == null) {
+    return
+  }
+  if (
+    typeof filter === 'string' ||
+    filter instanceof RegExp ||
+    Array.isArray(filter)
+  ) {
+    return { include: ensureArray(filter).map(bindingifyFilterPattern) }
+  }
+  return {
+    include: filter.include
+      ? ensureArray(filter.include).map(bindingifyFilterPattern)
+      : undefined,
+    exclude: filter.exclude
+      ? ensureArray(filter.exclude).map(bindingifyFilterPattern)
+      : undefined,
+  }
+}
+
+function bindingifyFilterPattern(
+  pattern: StringOrRegExp,
+): BindingFilterPattern {
+  if (pattern instanceof RegExp) {
+    // Only the `i` flag could be carried over, as an inline flag of the Rust regex.
+    return {
+      regex: pattern.flags.includes('i')
+        ? `(?i)${pattern.source}`
+        : pattern.source,
+    }
+  }
+  return { value: pattern }
+}
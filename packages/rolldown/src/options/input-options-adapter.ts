@@ -15,6 +15,8 @@ export function createInputOptionsAdapter(
     ),
     cwd: process.cwd(),
     external: inputOptions.external ? options.external : undefined,
+    makeAbsoluteExternalsRelative: inputOptions.makeAbsoluteExternalsRelative,
+    dynamicImportVars: inputOptions.dynamicImportVars,
     resolve: options.resolve,
   }
 }
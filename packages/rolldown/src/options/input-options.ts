@@ -5,13 +5,33 @@ import {
 } from '../rollup-types'
 import { ensureArray, normalizePluginOption } from '../utils'
 import { BindingResolveOptions } from '../binding'
+import type { HookFilter } from './hook-filter'
+
+type ObjectHookWithFilter<Hook, Filter> =
+  | Hook
+  | (Extract<Hook, { handler: unknown }> & { filter?: Filter })
 
 // TODO export compat plugin type
-export type RolldownPlugin = Plugin
+export type RolldownPlugin = Omit<
+  Plugin,
+  'resolveId' | 'load' | 'transform'
+> & {
+  resolveId?: ObjectHookWithFilter<
+    Plugin['resolveId'],
+    Pick<HookFilter, 'id'>
+  >
+  load?: ObjectHookWithFilter<
+    Plugin['load'],
+    Pick<HookFilter, 'id' | 'moduleType'>
+  >
+  transform?: ObjectHookWithFilter<Plugin['transform'], HookFilter>
+}
 export interface InputOptions {
   input?: RollupInputOptions['input']
   plugins?: RolldownPlugin[]
   external?: RollupInputOptions['external']
+  makeAbsoluteExternalsRelative?: boolean
+  dynamicImportVars?: boolean
   resolve?: RolldownResolveOptions
 }
 
@@ -1,19 +1,60 @@
 import { OutputOptions as RollupOutputOptions } from '../rollup-types'
-import { BindingOutputOptions } from '../binding'
+import { BindingOutputOptions, RenderedChunk } from '../binding'
 import { unimplemented } from '../utils'
 
 export interface OutputOptions {
   dir?: RollupOutputOptions['dir']
-  format?: 'esm'
+  file?: RollupOutputOptions['file']
+  format?: 'esm' | 'cjs' | 'iife' | 'umd' | 'system' | 'systemjs'
+  assetFileNames?: string
+  sanitizeFileName?: RollupOutputOptions['sanitizeFileName']
   exports?: RollupOutputOptions['exports']
   sourcemap?: RollupOutputOptions['sourcemap']
+  name?: RollupOutputOptions['name']
+  globals?: Record<string, string>
+  paths?: RollupOutputOptions['paths']
+  banner?: AddonOption
+  footer?: AddonOption
+  intro?: AddonOption
+  outro?: AddonOption
+  manualChunks?: RollupOutputOptions['manualChunks']
+  advancedChunks?: {
+    minSize?: number
+    maxSize?: number
+    minShareCount?: number
+    groups?: {
+      name: string
+      test?: string | RegExp
+      priority?: number
+      minSize?: number
+      maxSize?: number
+      minShareCount?: number
+    }[]
+  }
+  experimentalMinChunkSize?: RollupOutputOptions['experimentalMinChunkSize']
+  inlineDynamicImports?: RollupOutputOptions['inlineDynamicImports']
+  preserveModules?: RollupOutputOptions['preserveModules']
+  preserveModulesRoot?: RollupOutputOptions['preserveModulesRoot']
 }
 
+type AddonOption =
+  | string
+  | ((chunk: RenderedChunk) => string | Promise<string>)
+
 function normalizeFormat(
   format: OutputOptions['format'],
 ): BindingOutputOptions['format'] {
-  if (format == null || format === 'esm' || format === 'cjs') {
+  if (
+    format == null ||
+    format === 'esm' ||
+    format === 'cjs' ||
+    format === 'iife' ||
+    format === 'umd' ||
+    format === 'system'
+  ) {
     return format
+  } else if (format === 'systemjs') {
+    return 'system'
   } else {
     return unimplemented(`output.format: ${format}`)
   }
@@ -39,15 +80,97 @@ function normalizeSourcemap(
   }
 }
 
+function normalizeAddon(
+  addon: AddonOption | undefined,
+): BindingOutputOptions['banner'] {
+  if (addon == null) {
+    return undefined
+  }
+  return async (chunk) => (typeof addon === 'function' ? addon(chunk) : addon)
+}
+
+function normalizePaths(
+  paths: OutputOptions['paths'],
+): BindingOutputOptions['paths'] {
+  if (paths == null) {
+    return undefined
+  }
+  if (typeof paths === 'function') {
+    return paths
+  }
+  return (id) => paths[id] ?? id
+}
+
+function normalizeManualChunks(
+  manualChunks: OutputOptions['manualChunks'],
+): BindingOutputOptions['manualChunks'] {
+  if (typeof manualChunks === 'function') {
+    return unimplemented('output.manualChunks as a function')
+  }
+  return manualChunks
+}
+
+function normalizeAdvancedChunks(
+  advancedChunks: OutputOptions['advancedChunks'],
+): BindingOutputOptions['advancedChunks'] {
+  if (advancedChunks == null) {
+    return undefined
+  }
+  return {
+    ...advancedChunks,
+    groups: advancedChunks.groups?.map((group) => ({
+      ...group,
+      test: group.test instanceof RegExp ? group.test.source : group.test,
+    })),
+  }
+}
+
 export function normalizeOutputOptions(
   opts: OutputOptions,
 ): BindingOutputOptions {
-  const { dir, format, exports, sourcemap } = opts
+  const {
+    dir,
+    file,
+    format,
+    assetFileNames,
+    sanitizeFileName,
+    exports,
+    sourcemap,
+    name,
+    globals,
+    paths,
+    banner,
+    footer,
+    intro,
+    outro,
+    manualChunks,
+    advancedChunks,
+    experimentalMinChunkSize,
+    inlineDynamicImports,
+    preserveModules,
+    preserveModulesRoot,
+  } = opts
   return {
     dir: dir,
+    file,
     format: normalizeFormat(format),
+    assetFileNames,
+    sanitizeFileName,
     exports,
     sourcemap: normalizeSourcemap(sourcemap),
+    name,
+    globals,
+    paths: normalizePaths(paths),
+    banner: normalizeAddon(banner),
+    footer: normalizeAddon(footer),
+    intro: normalizeAddon(intro),
+    outro: normalizeAddon(outro),
+    manualChunks: normalizeManualChunks(manualChunks),
+    advancedChunks: normalizeAdvancedChunks(advancedChunks),
+    experimentalMinChunkSize,
+    inlineDynamicImports,
+    preserveModules,
+    preserveModulesRoot,
     plugins: [],
   }
 }
@@ -31,6 +31,10 @@ export class RolldownBuild {
     const output = await bundler.write()
     return transformToRollupOutput(output)
   }
+
+  async close(): Promise<void> {
+    await this.#bundler?.close()
+  }
 }
 
 function _assert() {
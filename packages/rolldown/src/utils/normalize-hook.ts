@@ -0,0 +1,29 @@
+import type { BindingPluginHookMeta } from '../binding'
+import type { HookFilter } from '../options/hook-filter'
+
+type ObjectHookMeta = {
+  order?: 'pre' | 'post' | null
+  sequential?: boolean
+  filter?: HookFilter
+}
+
+export type HookHandler<Hook> = Hook extends { handler: infer Handler }
+  ? Handler
+  : Hook
+
+// Splits a hook of the object form `{ handler, order, sequential, filter }` into its handler, meta and filter.
+export function normalizeHook<Hook>(
+  hook: Hook,
+): [
+  HookHandler<Hook> | undefined,
+  BindingPluginHookMeta | undefined,
+  HookFilter | undefined,
+] {
+  if (typeof hook === 'object' && hook !== null && 'handler' in hook) {
+    const { handler, order, sequential, filter } = hook as ObjectHookMeta & {
+      handler: HookHandler<Hook>
+    }
+    return [handler, { order: order ?? undefined, sequential }, filter]
+  }
+  return [hook as HookHandler<Hook> | undefined, undefined, undefined]
+}